@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use loose_sqlparser::Options;
+
+const SQL: &str = r#"
+-- Create the employees of the department.
+INSERT INTO employee (employee_id, first_name, manager_id, status, notes)
+  VALUES (1, 'Ada', NULL, 'active', 'Founder; first employee'), (2, 'Alan', 1, 'active', E'O\'Reilly');
+/* Promote the managers (the ones with at least 3 employees; see the review). */
+UPDATE employee SET level = level + 1
+  WHERE employee_id IN (SELECT manager_id FROM employee GROUP BY manager_id HAVING count(*) >= 3);
+CREATE FUNCTION add(a integer, b integer) RETURNS integer AS $$ SELECT a + b; $$ LANGUAGE SQL;
+SELECT e.employee_id, e.first_name, "Level; Name" FROM employee e WHERE e.notes LIKE '%;%' AND e.level <= $1;
+"#;
+
+// A large script made of many statements.
+fn script() -> String {
+    SQL.repeat(1000)
+}
+
+fn parse(sql: &str) -> usize {
+    loose_sqlparser::parse(sql).count()
+}
+
+fn split(sql: &str) -> usize {
+    loose_sqlparser::split(sql, &Options::default()).unwrap().count()
+}
+
+fn bench_split(c: &mut Criterion) {
+    let sql = script();
+    assert_eq!(parse(&sql), split(&sql));
+    let mut group = c.benchmark_group("Split");
+    group.bench_function("parse", |b| b.iter(|| parse(&sql)));
+    group.bench_function("split", |b| b.iter(|| split(&sql)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_split);
+criterion_main!(benches);
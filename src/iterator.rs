@@ -0,0 +1,92 @@
+use crate::tokenizer::Tokenizer;
+use crate::{Options, Position, Statement};
+
+/// An iterator over the statements of a SQL input, returned by [`crate::loose_sqlparse`] and the other parsing
+/// functions.
+///
+/// Between two statements, the iterator can tell where it is in the input, allowing to interleave the parsing with the
+/// execution of the statements or to report the progress of the parsing of large scripts.
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::loose_sqlparse;
+/// let mut statements = loose_sqlparse("SELECT 1;\nSELECT 2;");
+/// statements.next();
+/// assert_eq!(statements.remaining(), "\nSELECT 2;");
+/// assert_eq!(statements.position().offset, 9);
+/// ```
+pub struct StatementIterator<'s> {
+    tokenizer: Tokenizer<'s>,
+}
+
+impl<'s> StatementIterator<'s> {
+    pub(crate) fn new(input: &'s str, options: Options) -> Self {
+        Self { tokenizer: Tokenizer::new(input, options) }
+    }
+
+    /// The input following the last statement returned.
+    pub fn remaining(&self) -> &'s str {
+        self.tokenizer.remaining()
+    }
+
+    /// The position following the last statement returned (the start of the input before the first statement).
+    pub fn position(&self) -> Position {
+        self.tokenizer.position()
+    }
+
+    /// The options used to parse the next statement.
+    ///
+    /// They are the options given to the parser, unless changed by a directive comment (see
+    /// [`Options::honor_directive_comments`]).
+    pub fn options(&self) -> &Options {
+        self.tokenizer.options()
+    }
+}
+
+impl<'s> Iterator for StatementIterator<'s> {
+    type Item = Statement<'s>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tokenizer.next()
+    }
+}
+
+impl std::fmt::Debug for StatementIterator<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatementIterator")
+            .field("position", &self.position())
+            .field("options", self.options())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{loose_sqlparse, loose_sqlparse_with_options, Dialect};
+
+    #[test]
+    fn test_statement_iterator() {
+        let sql = "SELECT 1; -- one\nSELECT 'é';\r\n  SELECT 3";
+        let mut statements = loose_sqlparse(sql);
+        assert_eq!(statements.remaining(), sql);
+        assert_eq!(statements.position(), Position::new(1, 1, 0));
+        assert_eq!(statements.next().unwrap().sql(), "SELECT 1;");
+        assert_eq!(statements.remaining(), " -- one\nSELECT 'é';\r\n  SELECT 3");
+        assert_eq!(statements.position(), Position::new(1, 10, 9));
+        assert_eq!(statements.next().unwrap().sql(), "-- one\nSELECT 'é';");
+        assert_eq!(statements.position(), Position::new(2, 12, 29));
+        assert_eq!(statements.next().unwrap().sql(), "SELECT 3");
+        assert_eq!(statements.remaining(), "");
+        assert_eq!(statements.position().offset, sql.len());
+        assert!(statements.next().is_none());
+
+        // The options are the ones changed by the directive comments.
+        let options = Options { honor_directive_comments: true, ..Options::default() };
+        let mut statements =
+            loose_sqlparse_with_options("-- loose-sqlparse: dialect=mysql\nSELECT 1;", options).unwrap();
+        assert_eq!(statements.options().dialect, Dialect::Generic);
+        statements.next();
+        assert_eq!(statements.options().dialect, Dialect::MySql);
+    }
+}
@@ -0,0 +1,214 @@
+// The keywords reserved by most SQL dialects, they cannot be used as unquoted identifiers.
+//
+// The list is sorted to allow binary searches.
+const RESERVED_KEYWORDS: [&str; 78] = [
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BOTH",
+    "BY",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_USER",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FETCH",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GRANT",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "LEADING",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "SOME",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRAILING",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+// The keywords which are not reserved by most SQL dialects, they can also be used as unquoted identifiers (ex: a column
+// named `date` or `name`).
+//
+// The list is sorted to allow binary searches.
+const NON_RESERVED_KEYWORDS: [&str; 91] = [
+    "ACTION",
+    "ADD",
+    "AFTER",
+    "ALWAYS",
+    "ANALYZE",
+    "ARRAY",
+    "BEFORE",
+    "BEGIN",
+    "BIGINT",
+    "BOOLEAN",
+    "CALL",
+    "CASCADE",
+    "CHAR",
+    "COMMENT",
+    "COMMIT",
+    "CONFLICT",
+    "COPY",
+    "CURSOR",
+    "DATA",
+    "DATABASE",
+    "DATE",
+    "DAY",
+    "DECIMAL",
+    "DECLARE",
+    "DELIMITER",
+    "DESCRIBE",
+    "DO",
+    "EACH",
+    "ESCAPE",
+    "EXECUTE",
+    "EXPLAIN",
+    "FIRST",
+    "FOLLOWING",
+    "FUNCTION",
+    "HOUR",
+    "IF",
+    "INDEX",
+    "INT",
+    "INTEGER",
+    "INTERVAL",
+    "KEY",
+    "LANGUAGE",
+    "LAST",
+    "LEVEL",
+    "LIST",
+    "LOCK",
+    "MATERIALIZED",
+    "MERGE",
+    "MINUTE",
+    "MONTH",
+    "NAME",
+    "NO",
+    "NULLS",
+    "OF",
+    "ONLY",
+    "OVER",
+    "PARTITION",
+    "PRAGMA",
+    "PRECEDING",
+    "PROCEDURE",
+    "RANGE",
+    "RECURSIVE",
+    "RENAME",
+    "REPLACE",
+    "RESTRICT",
+    "RETURN",
+    "RETURNING",
+    "RETURNS",
+    "ROLE",
+    "ROLLBACK",
+    "ROW",
+    "ROWS",
+    "SCHEMA",
+    "SECOND",
+    "SEQUENCE",
+    "SESSION",
+    "SHOW",
+    "START",
+    "TEMPORARY",
+    "TEXT",
+    "TIME",
+    "TIMESTAMP",
+    "TRANSACTION",
+    "TRIGGER",
+    "TRUNCATE",
+    "TYPE",
+    "VARCHAR",
+    "VIEW",
+    "WITHOUT",
+    "WORK",
+    "ZONE",
+];
+
+// Check if the given word is a reserved keyword (case-insensitive).
+pub(crate) fn is_reserved_keyword(word: &str) -> bool {
+    RESERVED_KEYWORDS.binary_search(&word.to_ascii_uppercase().as_str()).is_ok()
+}
+
+// Check if the given word is a keyword, reserved or not (case-insensitive).
+pub(crate) fn is_keyword(word: &str) -> bool {
+    let word = word.to_ascii_uppercase();
+    RESERVED_KEYWORDS.binary_search(&word.as_str()).is_ok()
+        || NON_RESERVED_KEYWORDS.binary_search(&word.as_str()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_tables_are_sorted() {
+        assert!(RESERVED_KEYWORDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(NON_RESERVED_KEYWORDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(!NON_RESERVED_KEYWORDS.iter().any(|k| is_reserved_keyword(k)));
+    }
+
+    #[test]
+    fn test_is_keyword() {
+        assert!(is_reserved_keyword("select"));
+        assert!(!is_reserved_keyword("date"));
+        assert!(is_keyword("Date"));
+        assert!(is_keyword("FROM"));
+        assert!(!is_keyword("users"));
+    }
+}
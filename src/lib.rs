@@ -3,16 +3,24 @@
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 
+mod iterator;
+mod keywords;
 mod options;
+mod splitter;
 mod statement;
+mod streaming;
 mod tokenizer;
 mod tokens;
 
 // Re-export the public API
-pub use options::Options;
-pub use statement::Statement;
+pub use iterator::StatementIterator;
+pub use options::{Dialect, Options, OptionsBuilder, OptionsError};
+pub use splitter::StatementSpan;
+pub use statement::{BindStyle, Diagnostic, DiagnosticKind, ObjectType, Statement, StatementType};
+pub use streaming::{OwnedStatement, StreamingParser};
 pub use tokens::{Token, TokenValue, Tokens};
 
+use splitter::Splitter;
 use tokenizer::Tokenizer;
 
 /// A position in the input string given to the parser.
@@ -36,7 +44,7 @@ use tokenizer::Tokenizer;
 /// assert_eq!(stmt.start().offset, 10);
 /// assert_eq!(&input[stmt.tokens()[1].start.offset..stmt.tokens()[1].end.offset], "2");
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Position {
     /// Line number (1-based).
@@ -63,8 +71,8 @@ impl Position {
 ///
 /// The iterator will return a {{SqlStatement}} for each statement found in the input string.
 /// Statements are separated by a semicolon (`;`).
-pub fn loose_sqlparse(sql: &str) -> impl Iterator<Item = Statement<'_>> {
-    Tokenizer::new(sql, Options::default())
+pub fn loose_sqlparse(sql: &str) -> StatementIterator<'_> {
+    StatementIterator::new(sql, Options::default())
 }
 
 /// Scans a SQL string and returns an iterator over the statements.
@@ -73,18 +81,229 @@ pub fn loose_sqlparse(sql: &str) -> impl Iterator<Item = Statement<'_>> {
 ///
 /// The iterator will return a {{Statement}} for each statement found in the input string.
 /// Statements are separated by the given delimiter.
-pub fn loose_sqlparse_with_options(sql: &str, options: Options) -> impl Iterator<Item = Statement<'_>> {
-    Tokenizer::new(sql, options)
+///
+/// Returns an error if the options are not usable by the parser (see [`Options::validate`]).
+pub fn loose_sqlparse_with_options(sql: &str, options: Options) -> Result<StatementIterator<'_>, OptionsError> {
+    options.validate()?;
+    Ok(StatementIterator::new(sql, options))
 }
 
 /// Alias of {{loose_sqlparse}}.
-pub fn parse(sql: &str) -> impl Iterator<Item = Statement<'_>> {
-    Tokenizer::new(sql, Options::default())
+pub fn parse(sql: &str) -> StatementIterator<'_> {
+    StatementIterator::new(sql, Options::default())
 }
 
 /// Alias of {{loose_sqlparse_with_options}}.
-pub fn parse_with_options(sql: &str, options: Options) -> impl Iterator<Item = Statement<'_>> {
-    Tokenizer::new(sql, options)
+pub fn parse_with_options(sql: &str, options: Options) -> Result<StatementIterator<'_>, OptionsError> {
+    loose_sqlparse_with_options(sql, options)
+}
+
+/// Tokenizes a SQL snippet as a single unit, without splitting it into statements.
+///
+/// This is useful for the snippets that are not full statements (ex: an expression). The statement delimiter (and the
+/// other terminators enabled by the options) are regular tokens, and the client commands (see
+/// [`TokenValue::ClientCommand`]) are not recognized. The positions of the tokens are the ones in the snippet.
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{tokenize, Options};
+/// let tokens = tokenize("a = ';' ; b IN (1; 2)", &Options::default());
+/// assert_eq!(tokens.as_str_array(), ["a", "=", "';'", ";", "b", "IN", "(", "1", ";", "2", ")"]);
+/// assert!(tokens[7].is_fragment());
+/// ```
+pub fn tokenize<'s>(sql: &'s str, options: &Options) -> Tokens<'s> {
+    Tokenizer::tokenize(sql, options)
+}
+
+/// Check if a SQL input is complete, typically to decide if an interactive shell should execute the input or show a
+/// continuation prompt (like `sqlite3_complete()`).
+///
+/// The input is complete if its last statement is complete (see [`Statement::is_complete`]): terminated by the
+/// statement delimiter of the options (or by a delimiter changed by the input), with all its strings, quoted
+/// identifiers, comments and brackets closed. Comments following the last statement are ignored.
+///
+/// Returns `false` for an input without any statement or if the options are not usable by the parser.
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{is_complete, Options};
+/// let options = Options::default();
+/// assert!(is_complete("SELECT 1;", &options));
+/// assert!(!is_complete("SELECT 1", &options));
+/// assert!(!is_complete("SELECT 'a;", &options));
+/// assert!(!is_complete("SELECT (1;", &options));
+/// ```
+pub fn is_complete(sql: &str, options: &Options) -> bool {
+    if options.validate().is_err() {
+        return false;
+    }
+    let mut complete = false;
+    for statement in Tokenizer::new(sql, options.clone()) {
+        if !statement.is_empty() || statement.is_terminated() || !statement.diagnostics().is_empty() {
+            complete = statement.is_complete();
+        }
+    }
+    complete
+}
+
+/// Splits a SQL string into statements and returns an iterator over their spans.
+///
+/// The spans are the same as the ones of the statements returned by [`loose_sqlparse_with_options`] (see
+/// [`StatementSpan`]), but the tokens are not built when the options are allowing it: the input is only scanned for
+/// the strings, quoted identifiers, comments, dollar-quoted strings and brackets that may contain the statement
+/// delimiter. This is significantly faster for the callers only needing the boundaries of the statements.
+///
+/// The tokens are still built if the options are changing the boundaries of the statements depending on the tokens
+/// (blocks, client commands, delimiter changes, `@` variables, array subscripts...) or if the statement delimiter may
+/// be part of another token. Among the dialect presets, only [`Dialect::Generic`], [`Dialect::DuckDb`] and
+/// [`Dialect::ClickHouse`] are scanned, the other ones are tokenized.
+///
+/// Returns an error if the options are not usable by the parser (see [`Options::validate`]).
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{split, Options};
+/// let spans: Vec<_> = split("SELECT ';';\nSELECT 2", &Options::default()).unwrap().collect();
+/// assert_eq!(spans.len(), 2);
+/// assert_eq!(spans[0].sql(), "SELECT ';';");
+/// assert_eq!(spans[1].sql(), "SELECT 2");
+/// assert_eq!(spans[1].start().line, 2);
+/// ```
+pub fn split<'s>(
+    sql: &'s str,
+    options: &Options,
+) -> Result<impl Iterator<Item = StatementSpan<'s>> + 's, OptionsError> {
+    options.validate()?;
+    Ok(Splitter::new(sql, options.clone()))
+}
+
+/// Splits a SQL string into the SQL of its statements, using the default options.
+///
+/// The statements include their delimiter and the empty statements are kept (see [`split_sql_with_options`]).
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::split_sql;
+/// assert_eq!(split_sql("SELECT 1;\nSELECT ';' -- c\n;\nSELECT 3"), ["SELECT 1;", "SELECT ';' -- c\n;", "SELECT 3"]);
+/// ```
+pub fn split_sql(sql: &str) -> Vec<&str> {
+    Splitter::new(sql, Options::default()).map(|span| span.sql()).collect()
+}
+
+/// Splits a SQL string into the SQL of its statements (see [`split`]).
+///
+/// - The delimiter of the statements is only included if [`Options::include_delimiter_token`] is enabled.
+/// - The empty statements (only containing comments or a delimiter) are dropped if
+///   [`Options::skip_empty_statements`] is enabled.
+/// - The last statement is included even if it is not terminated by a delimiter.
+///
+/// Returns an error if the options are not usable by the parser (see [`Options::validate`]).
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{split_sql_with_options, Options};
+/// let options = Options::builder().include_delimiter_token(false).skip_empty_statements(true).build().unwrap();
+/// assert_eq!(split_sql_with_options("SELECT 1; ; SELECT 2", options).unwrap(), ["SELECT 1", "SELECT 2"]);
+/// ```
+pub fn split_sql_with_options(sql: &str, options: Options) -> Result<Vec<&str>, OptionsError> {
+    options.validate()?;
+    Ok(Splitter::new(sql, options).map(|span| span.sql()).collect())
+}
+
+/// Parses the statement at the given byte offset of a SQL string (ex: the statement under the cursor of an editor).
+///
+/// The statement returned is the one containing the offset, its delimiter included. If the offset is between two
+/// statements (whitespace, or an empty statement skipped, see [`Options::skip_empty_statements`]), the following
+/// statement is returned. Only the statement returned is tokenized when the options are allowing to scan the input
+/// (see [`split`]).
+///
+/// Returns `None` if the offset is not in the input (`offset >= sql.len()`), if there is no statement following the
+/// offset or if the options are not usable by the parser (see [`Options::validate`]).
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{parse_at, Options};
+/// let sql = "SELECT 1;\n\nSELECT 'a;b';\n";
+/// let options = Options::default();
+/// assert_eq!(parse_at(sql, 3, &options).unwrap().sql(), "SELECT 1;");
+/// assert_eq!(parse_at(sql, 8, &options).unwrap().sql(), "SELECT 1;");
+/// assert_eq!(parse_at(sql, 10, &options).unwrap().sql(), "SELECT 'a;b';");
+/// assert!(parse_at(sql, sql.len() - 1, &options).is_none());
+/// ```
+pub fn parse_at<'s>(sql: &'s str, offset: usize, options: &Options) -> Option<Statement<'s>> {
+    if options.validate().is_err() {
+        return None;
+    }
+    splitter::statement_at(sql, offset, options.clone())
+}
+
+/// Maps the lines of a SQL string to the statements covering them (ex: to show a "run" button in the gutter of an
+/// editor).
+///
+/// The element `i` of the returned vector is the index of the statement covering the line `i + 1` (in the statements
+/// returned by [`loose_sqlparse_with_options`]), or `None` if the line is not covered by any statement:
+/// - A statement spanning several lines covers all of them (see [`Statement::line_range`]).
+/// - A line covered by several statements is mapped to the first one.
+/// - The comments preceding a statement are part of it, so their lines are covered by the statement (unless it only
+///   contains comments and [`Options::skip_empty_statements`] is enabled).
+/// - The blank lines between the statements are not covered.
+///
+/// Returns an error if the options are not usable by the parser (see [`Options::validate`]).
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{line_index, Options};
+/// let sql = "SELECT 1; SELECT 2;\n\n-- three\nSELECT\n  3;\n";
+/// assert_eq!(line_index(sql, &Options::default()).unwrap(), [Some(0), None, Some(2), Some(2), Some(2), None]);
+/// ```
+pub fn line_index(sql: &str, options: &Options) -> Result<Vec<Option<usize>>, OptionsError> {
+    let mut lines = vec![None; line_count(sql, options)];
+    for (index, span) in split(sql, options)?.enumerate() {
+        for line in &mut lines[span.line_range().start() - 1..*span.line_range().end()] {
+            line.get_or_insert(index);
+        }
+    }
+    Ok(lines)
+}
+
+// Count the lines of the input, using the same line breaks as the positions.
+fn line_count(sql: &str, options: &Options) -> usize {
+    let mut chars = sql.chars().peekable();
+    let mut count = 1;
+    while let Some(c) = chars.next() {
+        count += match c {
+            '\n' => 1,
+            '\r' => usize::from(chars.peek() != Some(&'\n')),
+            '\u{0085}' | '\u{2028}' | '\u{2029}' => usize::from(options.unicode_line_breaks),
+            _ => 0,
+        };
+    }
+    count
+}
+
+/// Counts the statements of a SQL string.
+///
+/// The count is the number of statements returned by [`loose_sqlparse_with_options`]. The empty statements are not
+/// counted if [`Options::skip_empty_statements`] is enabled.
+///
+/// When the options are allowing to scan the input (see [`split`]), the tokens are not built: only a copy of the
+/// options and the stack of the brackets opened in the current statement are allocated. Otherwise, which is the case
+/// of most dialect presets, the statements are tokenized and their tokens dropped once counted.
+///
+/// Returns `0` if the options are not usable by the parser (see [`Options::validate`]).
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{count_statements, Options};
+/// assert_eq!(count_statements("SELECT 1; ; SELECT 2", &Options::default()), 3);
+/// let options = Options::builder().skip_empty_statements(true).build().unwrap();
+/// assert_eq!(count_statements("SELECT 1; ; SELECT 2", &options), 2);
+/// ```
+pub fn count_statements(sql: &str, options: &Options) -> usize {
+    if options.validate().is_err() {
+        return 0;
+    }
+    Splitter::new(sql, options.clone()).count()
 }
 
 #[cfg(test)]
@@ -101,19 +320,216 @@ mod tests {
         assert_eq!(statements[0].tokens().as_str_array(), ["SELECT", "/* one */", "1", ";"]);
         assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2"]);
 
-        let options = Options { statement_delimiter: "\\".to_string() };
-        let statements: Vec<_> = loose_sqlparse_with_options("SELECT /* one */ 1\\SELECT 2", options).collect();
+        let options = Options { statement_delimiter: "\\".to_string(), ..Default::default() };
+        let statements: Vec<_> =
+            loose_sqlparse_with_options("SELECT /* one */ 1\\SELECT 2", options).unwrap().collect();
         assert_eq!(statements[0].tokens().as_str_array(), ["SELECT", "/* one */", "1", "\\"]);
         assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2"]);
 
-        let options = Options { statement_delimiter: "\\".to_string() };
-        let statements: Vec<_> = parse_with_options("SELECT /* one */ 1\\SELECT 2", options).collect();
+        let options = Options { statement_delimiter: "\\".to_string(), ..Default::default() };
+        let statements: Vec<_> = parse_with_options("SELECT /* one */ 1\\SELECT 2", options).unwrap().collect();
         assert_eq!(statements[0].tokens().as_str_array(), ["SELECT", "/* one */", "1", "\\"]);
         assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2"]);
     }
 
     #[test]
-    fn test_loose_sqlparse_with_options() {}
+    fn test_loose_sqlparse_with_options() {
+        let options = Options { statement_delimiter: String::new(), ..Default::default() };
+        assert_eq!(loose_sqlparse_with_options("SELECT 1", options).err(), Some(OptionsError::EmptyDelimiter));
+        let options = Options { statement_delimiter: "--".to_string(), ..Default::default() };
+        assert!(matches!(parse_with_options("SELECT 1", options), Err(OptionsError::DelimiterCollision(_))));
+    }
+
+    #[test]
+    fn test_is_complete() {
+        let options = Options::default();
+        for sql in ["SELECT 1;", "SELECT 1; \n", ";", "SELECT 1; -- comment", "SELECT 1;\n/* comment */", "SELECT (1);"]
+        {
+            assert!(is_complete(sql, &options), "{sql}");
+        }
+        for sql in ["", " \n", "SELECT 1", "SELECT 1; SELECT 2", "-- comment", "SELECT 1; /* comment", "SELECT 'a;"] {
+            assert!(!is_complete(sql, &options), "{sql}");
+        }
+        for sql in ["SELECT \"a;", "SELECT $$ a; $$", "SELECT $$ a;", "SELECT (1;", "SELECT ((1);", "SELECT f(a, (b);"]
+        {
+            assert!(!is_complete(sql, &options), "{sql}");
+        }
+        assert!(is_complete("SELECT $$ a; $$;", &options));
+        assert!(is_complete("SELECT 1);", &options));
+
+        // Custom delimiters.
+        let options = Options::new("//").unwrap();
+        assert!(!is_complete("SELECT 1;", &options));
+        assert!(is_complete("SELECT 1;//", &options));
+        let options = Options::for_dialect(Dialect::MySql);
+        assert!(!is_complete("DELIMITER //\nSELECT 1;", &options));
+        assert!(is_complete("DELIMITER //\nSELECT 1;//", &options));
+        let options = Options::for_dialect(Dialect::SqlServer);
+        assert!(!is_complete("SELECT 1;", &options));
+        assert!(is_complete("SELECT 1;\nGO", &options));
+
+        // Client commands and blocks.
+        assert!(is_complete("\\c mydb", &Options::for_dialect(Dialect::PostgreSql)));
+        let options = Options::for_dialect(Dialect::Sqlite);
+        assert!(!is_complete("CREATE TRIGGER t AFTER INSERT ON a BEGIN DELETE FROM b;", &options));
+        assert!(is_complete("CREATE TRIGGER t AFTER INSERT ON a BEGIN DELETE FROM b; END;", &options));
+
+        // Options not usable by the parser.
+        assert!(!is_complete("SELECT 1;", &Options { statement_delimiter: String::new(), ..Default::default() }));
+    }
+
+    #[test]
+    fn test_tokenize() {
+        // Same tokens as the ones of a statement without delimiter.
+        let sql = "a.b = 'x' -- c\n  AND f(1, (2)) > $1 /* d */";
+        let statement = loose_sqlparse(sql).next().unwrap();
+        assert_eq!(format!("{:?}", tokenize(sql, &Options::default())), format!("{:?}", statement.tokens()));
+
+        // The delimiters are regular tokens.
+        let tokens = tokenize("a; b\nGO\n(c;\n/\n", &Options::for_dialect(Dialect::SqlServer));
+        assert_eq!(tokens.as_str_array(), ["a", ";", "b", "GO", "(", "c", ";", "/"]);
+        assert!(tokens.iter().all(|token| !token.is_statement_delimiter()));
+        assert_eq!((tokens[3].start.line, tokens[3].start.column), (2, 1));
+        let fragment = &tokens[5];
+        assert!(fragment.is_fragment());
+        assert_eq!((fragment.end.line, fragment.end.column), (5, 0));
+        assert_eq!(tokenize("1)", &Options::default()).as_str_array(), ["1", ")"]);
+
+        // The client commands are not recognized.
+        let tokens = tokenize("x\n\\c db", &Options::for_dialect(Dialect::PostgreSql));
+        assert!(tokens.iter().all(|token| !token.is_client_command()));
+        assert!(tokenize("", &Options::default()).is_empty());
+    }
+
+    #[test]
+    fn test_line_index() {
+        let options = Options::default();
+        // Several statements on a line, a statement spanning several lines and comment lines between statements.
+        let sql = "SELECT 1; SELECT 2; -- two\nSELECT\n  3;\n\n-- four\n/* still\nfour */\nSELECT 4;\n-- trailing";
+        assert_eq!(
+            line_index(sql, &options).unwrap(),
+            [Some(0), Some(2), Some(2), None, Some(3), Some(3), Some(3), Some(3), Some(4)]
+        );
+        // The trailing comment-only statement is skipped.
+        let skip_empty = Options { skip_empty_statements: true, ..Options::default() };
+        assert_eq!(line_index(sql, &skip_empty).unwrap()[8], None);
+        // The comment following a statement on the same line starts the next statement.
+        assert_eq!(line_index("SELECT 1; -- c\n\nSELECT 2;", &options).unwrap(), [Some(0), Some(1), Some(1)]);
+
+        // Line breaks.
+        assert_eq!(line_index("SELECT 1;\r\n\r\nSELECT\r2;", &options).unwrap(), [Some(0), None, Some(1), Some(1)]);
+        let sql = "SELECT 1;\u{2028}SELECT 2;";
+        assert_eq!(line_index(sql, &options).unwrap(), [Some(0)]);
+        let unicode_line_breaks = Options { unicode_line_breaks: true, ..Options::default() };
+        assert_eq!(line_index(sql, &unicode_line_breaks).unwrap(), [Some(0), Some(1)]);
+
+        // A line break ending an unterminated string does not cover the following line.
+        assert_eq!(line_index("SELECT 'a\n", &options).unwrap(), [Some(0), None]);
+        assert_eq!(line_index("", &options).unwrap(), [None]);
+        assert_eq!(line_index(" \n", &options).unwrap(), [None, None]);
+
+        let statement = loose_sqlparse("SELECT 1;\n\nSELECT (\n2\n);").nth(1).unwrap();
+        assert_eq!(statement.line_range(), 3..=5);
+        assert!(line_index("", &Options { statement_delimiter: String::new(), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_split_sql() {
+        let sql = "SELECT 1;\n;\n-- comment\n;SELECT 'a;b' /* c */ ; SELECT 2";
+        assert_eq!(split_sql(sql), ["SELECT 1;", ";", "-- comment\n;", "SELECT 'a;b' /* c */ ;", "SELECT 2"]);
+        let options = Options { include_delimiter_token: false, ..Default::default() };
+        assert_eq!(
+            split_sql_with_options(sql, options.clone()).unwrap(),
+            ["SELECT 1", "", "-- comment", "SELECT 'a;b' /* c */", "SELECT 2"]
+        );
+        let options = Options { skip_empty_statements: true, ..options };
+        assert_eq!(
+            split_sql_with_options(sql, options.clone()).unwrap(),
+            ["SELECT 1", "SELECT 'a;b' /* c */", "SELECT 2"]
+        );
+
+        // Only whitespace.
+        assert!(split_sql("").is_empty());
+        assert!(split_sql(" \n\t").is_empty());
+
+        // Only comments.
+        assert_eq!(split_sql("-- a\n/* b */\n"), ["-- a\n/* b */"]);
+        assert!(split_sql_with_options("-- a\n/* b */\n", options.clone()).unwrap().is_empty());
+
+        // No terminator on the last statement.
+        assert_eq!(split_sql("SELECT 1;\nSELECT 2 -- c"), ["SELECT 1;", "SELECT 2 -- c"]);
+        assert_eq!(split_sql("SELECT 'a;"), ["SELECT 'a;"]);
+
+        // The blocks and delimiters of the dialects are used.
+        let sql = "DELIMITER //\nCREATE PROCEDURE p() BEGIN SELECT 1; END//\nDELIMITER ;\nSELECT 2;";
+        let options = Options { skip_empty_statements: true, ..Options::for_dialect(Dialect::MySql) };
+        assert_eq!(
+            split_sql_with_options(sql, options).unwrap(),
+            ["DELIMITER //", "CREATE PROCEDURE p() BEGIN SELECT 1; END//", "DELIMITER ;", "SELECT 2;"]
+        );
+        let options = Options { statement_delimiter: String::new(), ..Default::default() };
+        assert_eq!(split_sql_with_options("SELECT 1", options).err(), Some(OptionsError::EmptyDelimiter));
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn test_serialization_of_numeric_constants() {
+        let statements: Vec<_> = loose_sqlparse("SELECT 0b101, 0xFF").collect();
+        let json = serde_json::to_value(statements[0].tokens()).unwrap();
+        assert_eq!(json[1]["type"], "NumericConstant");
+        assert_eq!(json[1]["value"], "0b101");
+        assert_eq!(json[3]["type"], "NumericConstant");
+        assert_eq!(json[3]["value"], "0xFF");
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn test_serialization_of_placeholders() {
+        let statements: Vec<_> = loose_sqlparse("SELECT ?, ?2, :a").collect();
+        let json = serde_json::to_value(statements[0].tokens()).unwrap();
+        assert_eq!(json[1]["type"], "Placeholder");
+        assert_eq!(json[1]["value"], "?");
+        assert_eq!(json[3]["type"], "Placeholder");
+        assert_eq!(json[3]["value"], "?2");
+        assert_eq!(json[5]["type"], "ParameterMarker");
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn test_serialization_of_options() {
+        let options = Options::for_dialect(Dialect::MySql);
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(json["dialect"], "MySql");
+        assert_eq!(json["statement_delimiter"], ";");
+        assert_eq!(json["interpret_delimiter_command"], true);
+        let options: Options = serde_json::from_value(json).unwrap();
+        assert_eq!(options.dialect, Dialect::MySql);
+        assert!(options.interpret_delimiter_command);
+        // The options that are not provided are taken from the presets of the dialect.
+        let options: Options = serde_json::from_str(r#"{"dialect": "MySql", "at_variables": false}"#).unwrap();
+        assert_eq!(options.dialect, Dialect::MySql);
+        assert!(options.interpret_delimiter_command);
+        assert!(!options.at_variables);
+        let options: Options = serde_json::from_str(r#"{"statement_delimiter": "//"}"#).unwrap();
+        assert_eq!(options.dialect, Dialect::Generic);
+        assert_eq!(options.statement_delimiter, "//");
+        assert!(options.hash_comments);
+        let options: Options = serde_json::from_str(r#"{"dialect": "PostgreSql", "tab_width": 4}"#).unwrap();
+        assert!(options.psql_meta_commands);
+        assert_eq!(options.tab_width, Some(4));
+        assert!(serde_json::from_str::<Options>(r#"{"dialect": "Cobol"}"#).is_err());
+        assert!(serde_json::from_str::<Options>(r#"{"hash_comments": 1}"#).is_err());
+        assert!(serde_json::from_str::<Options>("[]").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn test_serialization_of_hints() {
+        let statements: Vec<_> = loose_sqlparse("SELECT /*+ INDEX(t idx) */ * FROM t").collect();
+        let json = serde_json::to_value(statements[0].tokens()).unwrap();
+        assert_eq!(json[1]["type"], "Hint");
+        assert_eq!(json[1]["value"], "/*+ INDEX(t idx) */");
+    }
 
     #[test]
     #[cfg(feature = "serialize")]
@@ -122,7 +538,10 @@ mod tests {
         let json = serde_json::to_string(&statements).unwrap();
         assert_eq!(
             json,
-            r#"[{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"IdentifierOrKeyword","value":"SELECT","start":{"line":1,"column":1,"offset":0},"end":{"line":1,"column":6,"offset":6}},{"type":"Comment","value":"/* one */","start":{"line":1,"column":8,"offset":7},"end":{"line":1,"column":16,"offset":16}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":18,"offset":17},"end":{"line":1,"column":18,"offset":18}},{"type":"StatementDelimiter","value":";","start":{"line":1,"column":19,"offset":18},"end":{"line":1,"column":19,"offset":19}}]},{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"IdentifierOrKeyword","value":"SELECT","start":{"line":1,"column":21,"offset":20},"end":{"line":1,"column":26,"offset":26}},{"type":"Any","value":"(","start":{"line":1,"column":28,"offset":27},"end":{"line":1,"column":28,"offset":28}},{"type":"Fragment","value":[{"type":"NumericConstant","value":"2","start":{"line":1,"column":29,"offset":28},"end":{"line":1,"column":29,"offset":29}},{"type":"Operator","value":"+","start":{"line":1,"column":30,"offset":29},"end":{"line":1,"column":30,"offset":30}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":31,"offset":30},"end":{"line":1,"column":31,"offset":31}}],"start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":31,"offset":31}},{"type":"Any","value":")","start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":32,"offset":32}}]}]"#
+            r#"[{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"Keyword","value":"SELECT","start":{"line":1,"column":1,"offset":0},"end":{"line":1,"column":6,"offset":6}},{"type":"Comment","value":"/* one */","start":{"line":1,"column":8,"offset":7},"end":{"line":1,"column":16,"offset":16}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":18,"offset":17},"end":{"line":1,"column":18,"offset":18}},{"type":"StatementDelimiter","value":";","start":{"line":1,"column":19,"offset":18},"end":{"line":1,"column":19,"offset":19}}]},{"input":"SELECT /* one */ 1; SELECT (2+1)","tokens":[{"type":"Keyword","value":"SELECT","start":{"line":1,"column":21,"offset":20},"end":{"line":1,"column":26,"offset":26}},{"type":"Punctuation","value":"(","start":{"line":1,"column":28,"offset":27},"end":{"line":1,"column":28,"offset":28}},{"type":"Fragment","value":[{"type":"NumericConstant","value":"2","start":{"line":1,"column":29,"offset":28},"end":{"line":1,"column":29,"offset":29}},{"type":"Operator","value":"+","start":{"line":1,"column":30,"offset":29},"end":{"line":1,"column":30,"offset":30}},{"type":"NumericConstant","value":"1","start":{"line":1,"column":31,"offset":30},"end":{"line":1,"column":31,"offset":31}}],"start":{"line":1,"column":29,"offset":28},"end":{"line":1,"column":31,"offset":31}},{"type":"Punctuation","value":")","start":{"line":1,"column":32,"offset":31},"end":{"line":1,"column":32,"offset":32}}]}]"#
         );
+        let fragment = &statements[1].tokens()[2];
+        assert!(fragment.is_fragment());
+        assert_eq!(&statements[1].input[fragment.start.offset..fragment.end.offset], "2+1");
     }
 }
@@ -1,17 +1,870 @@
 #[cfg(feature = "serialize")]
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[cfg_attr(feature = "serialize", derive(Deserialize))]
+// The options are deserialized by the `Deserialize` implementation below.
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 /// Parser options.
+///
+/// New options can be added without a breaking change, the options are created by [`Options::default`],
+/// [`Options::for_dialect`] or [`Options::builder`] and their fields set afterwards.
 pub struct Options {
+    /// The SQL dialect the options were created for (see [`Options::for_dialect`]).
+    ///
+    /// The behavior of the parser is driven by the other options: setting this field does not apply the presets of the
+    /// dialect, use [`Options::for_dialect`] or [`OptionsBuilder::dialect`] instead. When the options are deserialized,
+    /// the options that are not provided are taken from the presets of this dialect (ex: `{"dialect": "MySql"}` are the
+    /// MySQL options).
+    /// The default is [`Dialect::Generic`].
+    pub dialect: Dialect,
+
     /// The delimiter used to separate statements.
     /// The default is `;`.
     pub statement_delimiter: String,
+
+    /// Whether the `statement_delimiter` is matched ignoring the ASCII case (ex: `GO`, `go` and `Go`).
+    /// The default is `false`.
+    pub delimiter_case_insensitive: bool,
+
+    /// Whether the `statement_delimiter` must be alone on its line to terminate a statement (ex: `GO` or `/`).
+    /// The default is `false`.
+    pub delimiter_requires_own_line: bool,
+
+    /// Whether the statement delimiter is included in the tokens (and the SQL) of the statement it terminates.
+    /// When disabled, the delimiter is still consumed and available from [`crate::Statement::terminator`].
+    /// The default is `true`.
+    pub include_delimiter_token: bool,
+
+    /// Whether empty statements (see [`crate::Statement::is_empty`]) are skipped by the parser.
+    /// The default is `false`.
+    pub skip_empty_statements: bool,
+
+    /// Whether triple-quoted strings (`'''...'''` and `"""..."""`) are supported (BigQuery).
+    /// The default is `false` because `''''` is a string constant containing a single quote in most SQL dialects.
+    pub triple_quoted_strings: bool,
+
+    /// Whether dollar-quoted strings (`$$...$$` and `$tag$...$tag$`) are supported (PostgreSQL).
+    /// The default is `true`.
+    pub dollar_quoted_strings: bool,
+
+    /// Whether `@` introduces a stage reference (Snowflake: `@my_stage/path/file.csv`) rather than a parameter marker.
+    /// The default is `false`.
+    pub stage_references: bool,
+
+    /// Whether `@name` and `@@name` are variables (MySQL: `@rownum`, `@'my var'`, `@@session.sql_mode`, SQL Server:
+    /// `@id`, `@@ROWCOUNT`) rather than parameter markers.
+    /// Stage references are taking precedence over `@name` variables when `stage_references` is also enabled.
+    /// The default is `false`.
+    pub at_variables: bool,
+
+    /// Whether `%s` and `%(name)s` are parameter markers (Python DB-API `format` and `pyformat` styles, psycopg).
+    /// When enabled, a `%` which is not followed by `s` or `(name)s` is still the modulo operator.
+    /// The default is `false`.
+    pub pyformat_placeholders: bool,
+
+    /// Whether `#{...}` and `${...}` are parameter markers (MyBatis mapper files, templating engines).
+    /// When enabled, they are taking precedence over `#` comments and dollar-quoted strings.
+    /// The default is `false`.
+    pub template_placeholders: bool,
+
+    /// Whether `${...}` is a substitution variable (Hive, Spark SQL: `${hivevar:path}`, `${env:HOME}`).
+    /// When enabled, it is taking precedence over dollar-quoted strings and `template_placeholders`.
+    /// The default is `false`.
+    pub hive_substitutions: bool,
+
+    /// Whether `{ ... }` is a comment (Informix).
+    /// The default is `false`.
+    pub brace_comments: bool,
+
+    /// Whether `[ ... ]` is a nested fragment like `( ... )` (PostgreSQL, DuckDB arrays: `arr[1]`, `arr[2:3]`,
+    /// `ARRAY[1, 2]`).
+    /// Inside the brackets, `:` is a slice separator rather than the start of a parameter marker.
+    /// The default is `false` because square brackets are quoting identifiers in SQL Server (`[My Table]`).
+    pub array_subscripts: bool,
+
+    /// Whether `{ ... }` is a nested fragment like `( ... )` (JDBC/ODBC escape sequences: `{fn CONCAT(a, b)}`,
+    /// `{d '2024-01-01'}`, `{call my_proc(?)}`).
+    /// Brace comments are taking precedence over escape sequences when `brace_comments` is also enabled.
+    /// The default is `false`.
+    pub jdbc_escapes: bool,
+
+    /// Whether `CASE ... END` expressions are nested fragments like `( ... )`, including the nested `CASE` expressions.
+    /// The fragment includes the `CASE` and `END` keywords, an unterminated `CASE` is closed by the end of the
+    /// statement.
+    /// The default is `false`.
+    pub case_fragments: bool,
+
+    /// Whether `--` only starts a comment when followed by a whitespace or the end of the input (MySQL).
+    /// When enabled, `5--2` is tokenized as `5`, `-`, `-`, `2`.
+    /// The default is `false`.
+    pub require_space_after_double_dash: bool,
+
+    /// Additional operators captured as single `Operator` tokens (ex: `["<=>", "|/"]`).
+    /// They are matched together with the built-in operators, the longest matching operator wins (ex: `!=` is still
+    /// captured as a single operator if `!` is an additional operator). Operators starting with a character having
+    /// another meaning (comment, quote, parameter marker, ...) may not be recognized.
+    /// The default is empty.
+    pub additional_operators: Vec<String>,
+
+    /// Whether a run of operator characters (`+ - * / < > = ~ ! @ # % ^ & | \` ?`) is captured as a single operator
+    /// (PostgreSQL custom operators: `<->`, `@@`, `|/`).
+    /// As in PostgreSQL, `--` and `/*` are starting comments and an operator cannot end with `+` or `-` unless it also
+    /// contains one of `~ ! @ # % ^ & | \` ?` (`a<->-1` is `a`, `<->`, `-`, `1`). A single operator character is
+    /// still captured by the other rules (parameter markers, variables, ...).
+    /// The default is `false`.
+    pub greedy_operators: bool,
+
+    /// Whether `#` starts a single-line comment (MySQL).
+    /// When disabled, `#` is tokenized as an operator (PostgreSQL). `#>` and `#>>` are always tokenized as operators.
+    /// The default is `true`.
+    pub hash_comments: bool,
+
+    /// Whether statements are separated by `GO` batch separators (SQL Server) instead of the `statement_delimiter`.
+    /// `GO` is only recognized when it's the only word on its line, optionally followed by a repeat count (`GO 5`).
+    /// The default is `false`.
+    pub go_batch_separator: bool,
+
+    /// Whether a `/` alone on its line terminates statements (Oracle SQL*Plus).
+    /// PL/SQL blocks (`DECLARE`, `BEGIN`, `CREATE PROCEDURE`, ...) can only be terminated by such a `/`, the
+    /// `statement_delimiter` found inside these blocks is not separating statements.
+    /// The default is `false`.
+    pub slash_terminator: bool,
+
+    /// Whether the `BEGIN ... END` body of a routine definition (SQL Server: `CREATE [OR ALTER] PROCEDURE | FUNCTION |
+    /// TRIGGER`, SQLite: `CREATE [TEMP] TRIGGER`) is kept in a single statement.
+    /// The `statement_delimiter` (or `GO`) found before the end of the outermost `BEGIN ... END` block is not
+    /// separating statements.
+    /// The default is `false`.
+    pub routine_body_blocks: bool,
+
+    /// Whether the body of a stored program definition (MySQL: `CREATE ... TRIGGER | PROCEDURE | FUNCTION | EVENT`) is
+    /// kept in a single statement without having to change the delimiter with a `DELIMITER` command, or to quote the
+    /// body with dollar quotes (PL/pgSQL).
+    /// The `statement_delimiter` found inside the `BEGIN ... END`, `DECLARE ... BEGIN ... END`, `IF ... END IF`,
+    /// `LOOP ... END LOOP`, `WHILE ... END WHILE | LOOP`, `REPEAT ... END REPEAT` and `CASE ... END CASE` blocks is not
+    /// separating statements.
+    /// The default is `false`.
+    pub stored_program_blocks: bool,
+
+    /// Whether the `BEGIN ATOMIC ... END` compound statements (SQL standard, PostgreSQL, DB2) are kept in a single
+    /// statement.
+    /// The `statement_delimiter` found before the `END` matching the `BEGIN ATOMIC` is not separating statements.
+    /// The default is `false`.
+    pub begin_atomic_blocks: bool,
+
+    /// Whether `DELIMITER` commands (MySQL) are changing the statement delimiter for the rest of the input.
+    /// The command must be at the beginning of a line and is captured as a statement on its own.
+    /// The default is `false`.
+    pub interpret_delimiter_command: bool,
+
+    /// Whether `\G` and `\g` (MySQL client) are terminating statements like the `statement_delimiter`.
+    /// The default is `false`.
+    pub backslash_g_terminators: bool,
+
+    /// Whether a line starting with `\` is a meta-command (psql: `\connect mydb`, `\set ON_ERROR_STOP on`).
+    /// The meta-command ends at the end of the line and is captured as a statement on its own.
+    /// The default is `false`.
+    pub psql_meta_commands: bool,
+
+    /// Whether a line starting with `.` is a dot-command (SQLite CLI: `.mode csv`, `.import data.csv t`).
+    /// The dot-command must be at the beginning of a statement, it ends at the end of the line and is captured as a
+    /// statement on its own.
+    /// The default is `false`.
+    pub sqlite_dot_commands: bool,
+
+    /// Whether a line starting with `@` or `@@` is a script include directive (Oracle SQL*Plus: `@schema/tables.sql`).
+    /// The directive must be at the beginning of a statement, it ends at the end of the line and is captured as a
+    /// statement on its own.
+    /// The default is `false`.
+    pub sqlplus_script_includes: bool,
+
+    /// Whether `&name` and `&&name` are substitution variables (Oracle SQL*Plus) rather than `&` operators.
+    /// The default is `false`.
+    pub substitution_variables: bool,
+
+    /// Whether `--#SET TERMINATOR` directives (Db2 CLP) are changing the statement delimiter for the rest of the input.
+    /// The directive must be at the beginning of a statement and is captured as a comment-only statement.
+    /// The default is `false`.
+    pub interpret_terminator_directive: bool,
+
+    /// Whether `SET TERM <new> <old>` statements (Firebird isql) are changing the statement delimiter for the rest of
+    /// the input.
+    /// The statement must be terminated by the active statement delimiter and is captured as a regular statement.
+    /// The default is `false`.
+    pub interpret_set_term: bool,
+
+    /// Whether one or more blank lines are separating statements (notebooks, snippets files).
+    /// Blank lines found inside strings, comments or parentheses are not separating statements.
+    /// The default is `false`.
+    pub split_on_blank_lines: bool,
+
+    /// Whether the inline data following a `COPY ... FROM STDIN` statement (PostgreSQL) is captured as a statement on
+    /// its own, up to the `\.` line terminating the data.
+    /// The default is `false`.
+    pub copy_from_stdin_data: bool,
+
+    /// Whether a line starting with `!` is a command (SnowSQL: `!source file.sql`, `!set variable_substitution=true`).
+    /// The command must be at the beginning of a statement, it ends at the end of the line and is captured as a
+    /// statement on its own.
+    /// The default is `false`.
+    pub snowsql_bang_commands: bool,
+
+    /// Whether directive comments are changing the options for the rest of the input.
+    ///
+    /// A directive comment is a single-line comment using one of the following forms:
+    /// - `-- loose-sqlparse: <name>=<value> [<name>=<value> ...]` where `<name>` is `dialect`, `delimiter` or the name
+    ///   of any boolean option (ex: `-- loose-sqlparse: delimiter=// hash_comments=false`),
+    /// - `-- dialect: <dialect>` (ex: `-- dialect: tsql`).
+    ///
+    /// Supported dialects are `mysql`, `postgresql`, `tsql`, `oracle`, `sqlite`, `bigquery`, `snowflake`, `informix`,
+    /// `db2`, `firebird`, `duckdb` and `clickhouse`, a dialect resets all other options to their default value. Unknown
+    /// directives are ignored, as well as the directives making the options unusable (see [`Options::validate`], ex:
+    /// `delimiter=--`).
+    /// The directive comment is still captured as a comment, a new delimiter is only effective from the next statement.
+    /// The default is `false`.
+    pub honor_directive_comments: bool,
+
+    /// The number of columns between tab stops used to compute the column of the positions (ex: `Some(4)`).
+    /// When set, a `\t` advances the column to the next tab stop as rendered by editors, when `None` every character
+    /// counts as one column.
+    /// The default is `None`.
+    pub tab_width: Option<usize>,
+
+    /// Whether the Unicode line breaks (NEL `U+0085`, LS `U+2028` and PS `U+2029`) are starting a new line in the
+    /// positions of the tokens.
+    /// They are still tokenized as whitespace, `\n`, `\r\n` and `\r` alone are always starting a new line.
+    /// The default is `false`.
+    pub unicode_line_breaks: bool,
+
+    /// Whether `CALL ...`, `EXEC ...` and `EXECUTE ...` statements are considered queries by
+    /// [`crate::Statement::is_query`], as the stored procedure or the prepared statement may return rows.
+    /// The tokenization is not affected by this option.
+    /// The default is `false`.
+    pub calls_are_queries: bool,
+
+    /// Whether statements starting with `FROM` (DuckDB, ClickHouse: `FROM t SELECT a`, `FROM t`) are considered queries
+    /// by [`crate::Statement::is_query`].
+    /// The tokenization is not affected by this option.
+    /// The default is `false`.
+    pub from_first_queries: bool,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Self { statement_delimiter: ";".to_string() }
+        Self {
+            dialect: Dialect::Generic,
+            statement_delimiter: ";".to_string(),
+            delimiter_case_insensitive: false,
+            delimiter_requires_own_line: false,
+            include_delimiter_token: true,
+            skip_empty_statements: false,
+            triple_quoted_strings: false,
+            dollar_quoted_strings: true,
+            stage_references: false,
+            at_variables: false,
+            pyformat_placeholders: false,
+            template_placeholders: false,
+            hive_substitutions: false,
+            brace_comments: false,
+            array_subscripts: false,
+            jdbc_escapes: false,
+            case_fragments: false,
+            require_space_after_double_dash: false,
+            additional_operators: Vec::new(),
+            greedy_operators: false,
+            hash_comments: true,
+            go_batch_separator: false,
+            slash_terminator: false,
+            routine_body_blocks: false,
+            stored_program_blocks: false,
+            begin_atomic_blocks: false,
+            interpret_delimiter_command: false,
+            backslash_g_terminators: false,
+            psql_meta_commands: false,
+            sqlite_dot_commands: false,
+            sqlplus_script_includes: false,
+            substitution_variables: false,
+            interpret_terminator_directive: false,
+            interpret_set_term: false,
+            split_on_blank_lines: false,
+            copy_from_stdin_data: false,
+            snowsql_bang_commands: false,
+            honor_directive_comments: false,
+            tab_width: None,
+            unicode_line_breaks: false,
+            calls_are_queries: false,
+            from_first_queries: false,
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> Deserialize<'de> for Options {
+    // The options that are not provided are taken from the presets of the dialect (see `Options::dialect`).
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ProvidedOptions::deserialize(deserializer).map(ProvidedOptions::apply)
+    }
+}
+
+// Generate the deserialized options, each option provided is applied over the presets of the dialect.
+macro_rules! provided_options {
+    ($($name:ident: $type:ty),* $(,)?) => {
+        #[cfg(feature = "serialize")]
+        #[derive(Deserialize)]
+        struct ProvidedOptions {
+            dialect: Option<Dialect>,
+            $($name: Option<$type>,)*
+        }
+
+        #[cfg(feature = "serialize")]
+        impl ProvidedOptions {
+            fn apply(self) -> Options {
+                let mut options = Options::for_dialect(self.dialect.unwrap_or_default());
+                // Listing all the fields, an option missing from `provided_options!` is not compiling.
+                let Options { dialect: _, $($name: _),* } = &options;
+                $(
+                    if let Some(value) = self.$name {
+                        options.$name = value;
+                    }
+                )*
+                options
+            }
+        }
+    };
+}
+
+provided_options!(
+    statement_delimiter: String,
+    delimiter_case_insensitive: bool,
+    delimiter_requires_own_line: bool,
+    include_delimiter_token: bool,
+    skip_empty_statements: bool,
+    triple_quoted_strings: bool,
+    dollar_quoted_strings: bool,
+    stage_references: bool,
+    at_variables: bool,
+    pyformat_placeholders: bool,
+    template_placeholders: bool,
+    hive_substitutions: bool,
+    brace_comments: bool,
+    array_subscripts: bool,
+    jdbc_escapes: bool,
+    case_fragments: bool,
+    require_space_after_double_dash: bool,
+    additional_operators: Vec<String>,
+    greedy_operators: bool,
+    hash_comments: bool,
+    go_batch_separator: bool,
+    slash_terminator: bool,
+    routine_body_blocks: bool,
+    stored_program_blocks: bool,
+    begin_atomic_blocks: bool,
+    interpret_delimiter_command: bool,
+    backslash_g_terminators: bool,
+    psql_meta_commands: bool,
+    sqlite_dot_commands: bool,
+    sqlplus_script_includes: bool,
+    substitution_variables: bool,
+    interpret_terminator_directive: bool,
+    interpret_set_term: bool,
+    split_on_blank_lines: bool,
+    copy_from_stdin_data: bool,
+    snowsql_bang_commands: bool,
+    honor_directive_comments: bool,
+    tab_width: Option<usize>,
+    unicode_line_breaks: bool,
+    calls_are_queries: bool,
+    from_first_queries: bool,
+);
+
+/// A SQL dialect, used to create [`Options`] with sensible presets (see [`Options::for_dialect`]).
+#[cfg_attr(feature = "serialize", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// No dialect specific behavior (the default options).
+    #[default]
+    Generic,
+
+    /// PostgreSQL: `#` is an operator, psql meta-commands and `COPY ... FROM STDIN` inline data.
+    PostgreSql,
+
+    /// MySQL and MariaDB: `--` must be followed by a whitespace, `DELIMITER` commands and `\G` terminators.
+    MySql,
+
+    /// SQL Server: `GO` batch separators, `#` is a temporary table prefix and `$` a money prefix.
+    SqlServer,
+
+    /// Oracle: `/` terminators, SQL*Plus script includes and substitution variables.
+    Oracle,
+
+    /// SQLite: dot-commands.
+    Sqlite,
+
+    /// Snowflake: stage references and SnowSQL commands.
+    Snowflake,
+
+    /// BigQuery: triple-quoted strings.
+    BigQuery,
+
+    /// Informix: brace comments.
+    Informix,
+
+    /// Db2: `--#SET TERMINATOR` directives.
+    Db2,
+
+    /// Firebird: `SET TERM` statements.
+    Firebird,
+
+    /// DuckDB: FROM-first queries.
+    DuckDb,
+
+    /// ClickHouse: FROM-first queries.
+    ClickHouse,
+}
+
+impl Dialect {
+    // Get a dialect from its name (case-insensitive), as used by directive comments.
+    pub(crate) fn from_name(name: &str) -> Option<Dialect> {
+        match name.to_ascii_lowercase().as_str() {
+            "generic" => Some(Dialect::Generic),
+            "postgresql" | "postgres" => Some(Dialect::PostgreSql),
+            "mysql" | "mariadb" => Some(Dialect::MySql),
+            "tsql" | "mssql" | "sqlserver" => Some(Dialect::SqlServer),
+            "oracle" => Some(Dialect::Oracle),
+            "sqlite" => Some(Dialect::Sqlite),
+            "snowflake" => Some(Dialect::Snowflake),
+            "bigquery" => Some(Dialect::BigQuery),
+            "informix" => Some(Dialect::Informix),
+            "db2" => Some(Dialect::Db2),
+            "firebird" => Some(Dialect::Firebird),
+            "duckdb" => Some(Dialect::DuckDb),
+            "clickhouse" => Some(Dialect::ClickHouse),
+            _ => None,
+        }
+    }
+}
+
+/// The error returned when the [`Options`] are not usable by the parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionsError {
+    /// The statement delimiter is an empty string.
+    EmptyDelimiter,
+
+    /// The statement delimiter only consists of whitespace.
+    WhitespaceDelimiter(String),
+
+    /// The statement delimiter contains a newline character while no line-based mode is enabled
+    /// ([`Options::go_batch_separator`] or [`Options::split_on_blank_lines`]).
+    NewlineInDelimiter(String),
+
+    /// The statement delimiter starts with a quote or a comment opener (`'`, `"`, `` ` ``, `--`, `/*`, ...).
+    DelimiterCollision(String),
+}
+
+impl std::fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionsError::EmptyDelimiter => write!(f, "the statement delimiter cannot be empty"),
+            OptionsError::WhitespaceDelimiter(delimiter) => {
+                write!(f, "the statement delimiter {:?} cannot only consist of whitespace", delimiter)
+            }
+            OptionsError::NewlineInDelimiter(delimiter) => {
+                write!(f, "the statement delimiter {:?} cannot contain a newline character", delimiter)
+            }
+            OptionsError::DelimiterCollision(delimiter) => {
+                write!(f, "the statement delimiter {:?} cannot start with a quote or a comment opener", delimiter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+impl Options {
+    /// Create a builder of options, starting from the default options.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{Dialect, Options};
+    /// let options = Options::builder().statement_delimiter("//").dialect(Dialect::MySql).build().unwrap();
+    /// assert_eq!(options.statement_delimiter, "//");
+    /// assert!(options.interpret_delimiter_command);
+    /// ```
+    pub fn builder() -> OptionsBuilder {
+        OptionsBuilder { options: Options::default() }
+    }
+
+    /// Create the options of the given dialect.
+    ///
+    /// Individual options can still be overridden after the creation of the options.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{Dialect, Options};
+    /// let mut options = Options::for_dialect(Dialect::PostgreSql);
+    /// options.hash_comments = true;
+    /// assert!(options.psql_meta_commands);
+    /// assert!(options.hash_comments);
+    /// ```
+    pub fn for_dialect(dialect: Dialect) -> Options {
+        let mut options = Options { dialect, ..Default::default() };
+        match dialect {
+            Dialect::Generic => {}
+            Dialect::PostgreSql => {
+                options.hash_comments = false;
+                options.psql_meta_commands = true;
+                options.copy_from_stdin_data = true;
+                options.array_subscripts = true;
+                options.begin_atomic_blocks = true;
+            }
+            Dialect::MySql => {
+                options.require_space_after_double_dash = true;
+                options.at_variables = true;
+                options.interpret_delimiter_command = true;
+                options.backslash_g_terminators = true;
+                options.calls_are_queries = true;
+            }
+            Dialect::SqlServer => {
+                options.hash_comments = false;
+                options.dollar_quoted_strings = false;
+                options.go_batch_separator = true;
+                options.at_variables = true;
+                options.calls_are_queries = true;
+            }
+            Dialect::Oracle => {
+                options.hash_comments = false;
+                options.dollar_quoted_strings = false;
+                options.slash_terminator = true;
+                options.sqlplus_script_includes = true;
+                options.substitution_variables = true;
+            }
+            Dialect::Sqlite => {
+                options.hash_comments = false;
+                options.dollar_quoted_strings = false;
+                options.sqlite_dot_commands = true;
+                options.routine_body_blocks = true;
+            }
+            Dialect::Snowflake => {
+                options.hash_comments = false;
+                options.stage_references = true;
+                options.snowsql_bang_commands = true;
+            }
+            Dialect::BigQuery => {
+                options.dollar_quoted_strings = false;
+                options.triple_quoted_strings = true;
+            }
+            Dialect::Informix => {
+                options.hash_comments = false;
+                options.brace_comments = true;
+            }
+            Dialect::Db2 => {
+                options.hash_comments = false;
+                options.interpret_terminator_directive = true;
+                options.begin_atomic_blocks = true;
+            }
+            Dialect::Firebird => {
+                options.hash_comments = false;
+                options.interpret_set_term = true;
+            }
+            Dialect::DuckDb => {
+                options.hash_comments = false;
+                options.from_first_queries = true;
+            }
+            Dialect::ClickHouse => {
+                options.from_first_queries = true;
+            }
+        }
+        options
+    }
+
+    /// Create the default options with the given statement delimiter.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{Options, OptionsError};
+    /// assert_eq!(Options::new("//").unwrap().statement_delimiter, "//");
+    /// assert_eq!(Options::new("").unwrap_err(), OptionsError::EmptyDelimiter);
+    /// ```
+    pub fn new(delimiter: &str) -> Result<Options, OptionsError> {
+        let options = Options { statement_delimiter: delimiter.to_string(), ..Default::default() };
+        options.validate()?;
+        Ok(options)
+    }
+
+    /// Check if the options are usable by the parser.
+    pub fn validate(&self) -> Result<(), OptionsError> {
+        let delimiter = &self.statement_delimiter;
+        if delimiter.is_empty() {
+            return Err(OptionsError::EmptyDelimiter);
+        }
+        if delimiter.trim().is_empty() {
+            return Err(OptionsError::WhitespaceDelimiter(delimiter.clone()));
+        }
+        if delimiter.contains(['\n', '\r']) && !self.go_batch_separator && !self.split_on_blank_lines {
+            return Err(OptionsError::NewlineInDelimiter(delimiter.clone()));
+        }
+        if delimiter.starts_with(['\'', '"', '`'])
+            || delimiter.starts_with("--")
+            || delimiter.starts_with("/*")
+            || (delimiter.starts_with('#') && self.hash_comments)
+            || (delimiter.starts_with('{') && self.brace_comments)
+        {
+            return Err(OptionsError::DelimiterCollision(delimiter.clone()));
+        }
+        Ok(())
+    }
+
+    // Apply a directive comment to the options (see `Options::honor_directive_comments`).
+    //
+    // Unknown directives, options or values are ignored. A directive making the options unusable by the parser (see
+    // `Options::validate`, ex: `delimiter=--` or `hash_comments=true` with a `#` delimiter) is ignored as a whole.
+    pub(crate) fn apply_directive_comment(&mut self, comment: &str) {
+        let directive = comment.trim_start_matches(['-', '#']).trim();
+        let mut options = self.clone();
+        if let Some(settings) = directive.strip_prefix("loose-sqlparse:") {
+            for (name, value) in settings.split_whitespace().filter_map(|setting| setting.split_once('=')) {
+                options.apply_directive(name, value);
+            }
+        } else if let Some(dialect) = directive.strip_prefix("dialect:") {
+            options.apply_directive("dialect", dialect.trim());
+        } else {
+            return;
+        }
+        if options.validate().is_ok() {
+            *self = options;
+        }
+    }
+
+    // Apply a single `name=value` setting of a directive comment.
+    fn apply_directive(&mut self, name: &str, value: &str) {
+        match name {
+            "dialect" => self.apply_dialect(value),
+            "delimiter" | "statement_delimiter" => self.statement_delimiter = value.to_string(),
+            _ => {
+                if let (Some(option), Ok(value)) = (self.bool_option_mut(name), value.parse::<bool>()) {
+                    *option = value;
+                }
+            }
+        }
+    }
+
+    // Reset the options to the default options of the given dialect.
+    fn apply_dialect(&mut self, dialect: &str) {
+        if let Some(dialect) = Dialect::from_name(dialect) {
+            *self = Options { honor_directive_comments: true, ..Options::for_dialect(dialect) };
+        }
+    }
+
+    // Get a mutable reference to a boolean option from its name.
+    fn bool_option_mut(&mut self, name: &str) -> Option<&mut bool> {
+        match name {
+            "delimiter_case_insensitive" => Some(&mut self.delimiter_case_insensitive),
+            "delimiter_requires_own_line" => Some(&mut self.delimiter_requires_own_line),
+            "include_delimiter_token" => Some(&mut self.include_delimiter_token),
+            "skip_empty_statements" => Some(&mut self.skip_empty_statements),
+            "triple_quoted_strings" => Some(&mut self.triple_quoted_strings),
+            "dollar_quoted_strings" => Some(&mut self.dollar_quoted_strings),
+            "stage_references" => Some(&mut self.stage_references),
+            "at_variables" => Some(&mut self.at_variables),
+            "pyformat_placeholders" => Some(&mut self.pyformat_placeholders),
+            "template_placeholders" => Some(&mut self.template_placeholders),
+            "hive_substitutions" => Some(&mut self.hive_substitutions),
+            "brace_comments" => Some(&mut self.brace_comments),
+            "array_subscripts" => Some(&mut self.array_subscripts),
+            "jdbc_escapes" => Some(&mut self.jdbc_escapes),
+            "case_fragments" => Some(&mut self.case_fragments),
+            "require_space_after_double_dash" => Some(&mut self.require_space_after_double_dash),
+            "greedy_operators" => Some(&mut self.greedy_operators),
+            "hash_comments" => Some(&mut self.hash_comments),
+            "go_batch_separator" => Some(&mut self.go_batch_separator),
+            "slash_terminator" => Some(&mut self.slash_terminator),
+            "routine_body_blocks" => Some(&mut self.routine_body_blocks),
+            "stored_program_blocks" => Some(&mut self.stored_program_blocks),
+            "begin_atomic_blocks" => Some(&mut self.begin_atomic_blocks),
+            "interpret_delimiter_command" => Some(&mut self.interpret_delimiter_command),
+            "backslash_g_terminators" => Some(&mut self.backslash_g_terminators),
+            "psql_meta_commands" => Some(&mut self.psql_meta_commands),
+            "sqlite_dot_commands" => Some(&mut self.sqlite_dot_commands),
+            "sqlplus_script_includes" => Some(&mut self.sqlplus_script_includes),
+            "substitution_variables" => Some(&mut self.substitution_variables),
+            "interpret_terminator_directive" => Some(&mut self.interpret_terminator_directive),
+            "interpret_set_term" => Some(&mut self.interpret_set_term),
+            "split_on_blank_lines" => Some(&mut self.split_on_blank_lines),
+            "copy_from_stdin_data" => Some(&mut self.copy_from_stdin_data),
+            "snowsql_bang_commands" => Some(&mut self.snowsql_bang_commands),
+            "honor_directive_comments" => Some(&mut self.honor_directive_comments),
+            "unicode_line_breaks" => Some(&mut self.unicode_line_breaks),
+            "calls_are_queries" => Some(&mut self.calls_are_queries),
+            "from_first_queries" => Some(&mut self.from_first_queries),
+            _ => None,
+        }
+    }
+}
+
+/// A builder of [`Options`] (see [`Options::builder`]).
+#[derive(Debug, Clone)]
+pub struct OptionsBuilder {
+    options: Options,
+}
+
+// Generate the setters of the boolean options of the builder.
+macro_rules! bool_setters {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("See [`Options::", stringify!($name), "`].")]
+            pub fn $name(mut self, value: bool) -> Self {
+                self.options.$name = value;
+                self
+            }
+        )*
+    };
+}
+
+impl OptionsBuilder {
+    /// Reset the options to the presets of the given dialect (see [`Options::for_dialect`]).
+    ///
+    /// The statement delimiter is kept, all other options set before are replaced by the presets of the dialect.
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        let statement_delimiter = std::mem::take(&mut self.options.statement_delimiter);
+        self.options = Options { statement_delimiter, ..Options::for_dialect(dialect) };
+        self
+    }
+
+    /// See [`Options::statement_delimiter`].
+    pub fn statement_delimiter(mut self, delimiter: &str) -> Self {
+        self.options.statement_delimiter = delimiter.to_string();
+        self
+    }
+
+    /// See [`Options::additional_operators`].
+    pub fn additional_operators(mut self, operators: &[&str]) -> Self {
+        self.options.additional_operators = operators.iter().map(|op| op.to_string()).collect();
+        self
+    }
+
+    /// See [`Options::tab_width`].
+    pub fn tab_width(mut self, tab_width: Option<usize>) -> Self {
+        self.options.tab_width = tab_width;
+        self
+    }
+
+    bool_setters!(
+        delimiter_case_insensitive,
+        delimiter_requires_own_line,
+        include_delimiter_token,
+        skip_empty_statements,
+        triple_quoted_strings,
+        dollar_quoted_strings,
+        stage_references,
+        at_variables,
+        pyformat_placeholders,
+        template_placeholders,
+        hive_substitutions,
+        brace_comments,
+        array_subscripts,
+        jdbc_escapes,
+        case_fragments,
+        require_space_after_double_dash,
+        greedy_operators,
+        hash_comments,
+        go_batch_separator,
+        slash_terminator,
+        routine_body_blocks,
+        stored_program_blocks,
+        begin_atomic_blocks,
+        interpret_delimiter_command,
+        backslash_g_terminators,
+        psql_meta_commands,
+        sqlite_dot_commands,
+        sqlplus_script_includes,
+        substitution_variables,
+        interpret_terminator_directive,
+        interpret_set_term,
+        split_on_blank_lines,
+        copy_from_stdin_data,
+        snowsql_bang_commands,
+        honor_directive_comments,
+        unicode_line_breaks,
+        calls_are_queries,
+        from_first_queries,
+    );
+
+    /// Build the options.
+    ///
+    /// Returns an error if the options are not usable by the parser (see [`Options::validate`]).
+    pub fn build(self) -> Result<Options, OptionsError> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_dialect() {
+        assert_eq!(Options::for_dialect(Dialect::Generic).dialect, Dialect::Generic);
+        let options = Options::for_dialect(Dialect::MySql);
+        assert_eq!(options.dialect, Dialect::MySql);
+        assert!(options.interpret_delimiter_command);
+        assert!(options.at_variables);
+        assert!(!Options::for_dialect(Dialect::SqlServer).hash_comments);
+        assert!(!Options::for_dialect(Dialect::Snowflake).at_variables);
+        assert!(Options::for_dialect(Dialect::PostgreSql).array_subscripts);
+        assert_eq!(Dialect::from_name("TSQL"), Some(Dialect::SqlServer));
+        assert_eq!(Dialect::from_name("DuckDB"), Some(Dialect::DuckDb));
+        assert!(Options::for_dialect(Dialect::ClickHouse).from_first_queries);
+        assert_eq!(Dialect::from_name("cobol"), None);
+    }
+
+    #[test]
+    fn test_builder() {
+        let options = Options::builder().build().unwrap();
+        assert_eq!(options.statement_delimiter, ";");
+        assert_eq!(options.dialect, Dialect::Generic);
+
+        let options = Options::builder()
+            .statement_delimiter("GO")
+            .dialect(Dialect::SqlServer)
+            .go_batch_separator(false)
+            .delimiter_case_insensitive(true)
+            .additional_operators(&["<=>"])
+            .tab_width(Some(4))
+            .build()
+            .unwrap();
+        assert_eq!(options.statement_delimiter, "GO");
+        assert_eq!(options.dialect, Dialect::SqlServer);
+        assert!(!options.go_batch_separator);
+        assert!(!options.hash_comments);
+        assert!(options.delimiter_case_insensitive);
+        assert_eq!(options.additional_operators, ["<=>"]);
+        assert_eq!(options.tab_width, Some(4));
+
+        assert_eq!(Options::builder().statement_delimiter("").build().unwrap_err(), OptionsError::EmptyDelimiter);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Options::default().validate().is_ok());
+        assert!(Options::new("GO").is_ok());
+        assert!(Options::new("$$").is_ok());
+        assert_eq!(Options::new("").unwrap_err(), OptionsError::EmptyDelimiter);
+        assert_eq!(Options::new(" \t").unwrap_err(), OptionsError::WhitespaceDelimiter(" \t".to_string()));
+        assert_eq!(Options::new("GO\n").unwrap_err(), OptionsError::NewlineInDelimiter("GO\n".to_string()));
+        assert!(Options { statement_delimiter: "GO\n".to_string(), split_on_blank_lines: true, ..Default::default() }
+            .validate()
+            .is_ok());
+        for delimiter in ["'", "\"", "`", "--", "/*", "#"] {
+            assert_eq!(Options::new(delimiter).unwrap_err(), OptionsError::DelimiterCollision(delimiter.to_string()));
+        }
+        assert!(Options { statement_delimiter: "#".to_string(), hash_comments: false, ..Default::default() }
+            .validate()
+            .is_ok());
+        assert_eq!(
+            OptionsError::DelimiterCollision("--".to_string()).to_string(),
+            "the statement delimiter \"--\" cannot start with a quote or a comment opener"
+        );
     }
 }
@@ -0,0 +1,766 @@
+use crate::statement::line_range;
+use crate::tokenizer::{
+    is_double_quoted_string_introducer, is_raw_string_introducer, QuoteEscaping, Tokenizer, TokenizerState,
+};
+use crate::{Options, Position, Statement};
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serialize")]
+use serde::Serialize;
+
+/// The boundaries of a statement, returned by [`crate::split`].
+///
+/// The span covers the same input as [`Statement::sql`], with the same positions as [`Statement::start`] and
+/// [`Statement::end`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct StatementSpan<'s> {
+    // The SQL of the statement (see `Statement::sql`).
+    sql: &'s str,
+
+    // The start position of the statement.
+    start: Position,
+
+    // The end position of the statement.
+    end: Position,
+}
+
+impl<'s> StatementSpan<'s> {
+    /// The SQL of the statement.
+    pub fn sql(&self) -> &'s str {
+        self.sql
+    }
+
+    /// The start position of the statement.
+    pub fn start(&self) -> &Position {
+        &self.start
+    }
+
+    /// The end position of the statement.
+    pub fn end(&self) -> &Position {
+        &self.end
+    }
+
+    /// The range of the lines covered by the statement (see [`Statement::line_range`]).
+    pub fn line_range(&self) -> RangeInclusive<usize> {
+        line_range(&self.start, &self.end)
+    }
+}
+
+impl<'s> From<Statement<'s>> for StatementSpan<'s> {
+    fn from(statement: Statement<'s>) -> Self {
+        let (start, end) = (statement.start().clone(), statement.end().clone());
+        Self { sql: &statement.input[start.offset..end.offset], start, end }
+    }
+}
+
+// Split the input into statement spans.
+//
+// The input is scanned without building the tokens when the options are not changing the boundaries of the statements
+// depending on the tokens (client commands, blocks, delimiter changes...), otherwise it is tokenized.
+pub(crate) enum Splitter<'s> {
+    Scanner(Scanner<'s>),
+    Tokenizer(Tokenizer<'s>),
+}
+
+impl<'s> Splitter<'s> {
+    pub(crate) fn new(input: &'s str, options: Options) -> Self {
+        if Scanner::supports(input, &options) {
+            Splitter::Scanner(Scanner::new(input, options))
+        } else {
+            Splitter::Tokenizer(Tokenizer::new(input, options))
+        }
+    }
+}
+
+impl<'s> Iterator for Splitter<'s> {
+    type Item = StatementSpan<'s>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Splitter::Scanner(scanner) => scanner.next(),
+            Splitter::Tokenizer(tokenizer) => tokenizer.next().map(StatementSpan::from),
+        }
+    }
+
+    fn count(self) -> usize {
+        match self {
+            Splitter::Scanner(scanner) => scanner.count(),
+            Splitter::Tokenizer(tokenizer) => tokenizer.count(),
+        }
+    }
+}
+
+// Get the statement at the given offset: the first statement (not skipped) ending after the offset, its delimiter
+// included.
+//
+// Only the statement found is tokenized when the options are allowing to scan the input.
+pub(crate) fn statement_at(input: &str, offset: usize, options: Options) -> Option<Statement<'_>> {
+    if offset >= input.len() {
+        return None;
+    }
+    if !Scanner::supports(input, &options) {
+        let mut tokenizer = Tokenizer::new(input, options);
+        return std::iter::from_fn(|| tokenizer.next().map(|statement| (statement, tokenizer.position().offset)))
+            .find_map(|(statement, end_offset)| (end_offset > offset).then_some(statement));
+    }
+    let mut scanner = Scanner::new(input, options.clone());
+    loop {
+        let (start, _, empty) = scanner.scan_statement()?;
+        if scanner.next_offset > offset && (!options.skip_empty_statements || !empty) {
+            let start = scanner.position_at(start, false);
+            return Tokenizer::resume(input, TokenizerState { start, ..TokenizerState::new(options) }).next_statement();
+        }
+    }
+}
+
+// A scanner finding the boundaries of the statements without building the tokens.
+//
+// The scanner follows the same rules as the tokenizer for everything that may hide a statement delimiter (strings,
+// quoted identifiers, comments, dollar-quoted strings, parameter markers, numeric constants, operators) and for the
+// brackets that are extending the end of a statement.
+pub(crate) struct Scanner<'s> {
+    input: &'s str,
+    input_iter: std::str::Chars<'s>,
+    options: Options,
+
+    // The offset of the last character read from the input (same as `Tokenizer::offset`).
+    offset: usize,
+
+    // The offset of the next character to be read from the input.
+    next_offset: usize,
+
+    // The closing brackets expected by the brackets opened in the current statement.
+    closing_chars: Vec<char>,
+
+    // The offset, line and column (number of characters before the offset) of the last position computed.
+    position: (usize, usize, usize),
+}
+
+impl<'s> Scanner<'s> {
+    fn new(input: &'s str, options: Options) -> Self {
+        Self {
+            input,
+            input_iter: input.chars(),
+            options,
+            offset: 0,
+            next_offset: 0,
+            closing_chars: Vec::new(),
+            position: (0, 1, 0),
+        }
+    }
+
+    // Check if the boundaries of the statements found by the scanner are the same as the ones found by the tokenizer.
+    //
+    // The statement delimiter must not contain a character that may be part of another token, because the scanner
+    // only knows the extent of the tokens hiding a delimiter.
+    fn supports(input: &str, options: &Options) -> bool {
+        let is_safe_delimiter_char =
+            |c: char| !c.is_alphanumeric() && !c.is_whitespace() && !"'\"`$.@:?#%&|<>=!~*/+-^{}[]()_,\\".contains(c);
+        options.statement_delimiter.chars().all(is_safe_delimiter_char)
+            && !options.delimiter_requires_own_line
+            && !options.triple_quoted_strings
+            && !options.stage_references
+            && !options.at_variables
+            && !options.pyformat_placeholders
+            && !options.template_placeholders
+            && !options.hive_substitutions
+            && !options.brace_comments
+            && !options.array_subscripts
+            && options.additional_operators.is_empty()
+            && !options.greedy_operators
+            && !options.go_batch_separator
+            && !options.slash_terminator
+            && !options.routine_body_blocks
+            && !options.stored_program_blocks
+            && !(options.begin_atomic_blocks && contains_ignore_ascii_case(input, "ATOMIC"))
+            && !options.interpret_delimiter_command
+            && !options.backslash_g_terminators
+            && !options.psql_meta_commands
+            && !options.sqlite_dot_commands
+            && !options.sqlplus_script_includes
+            && !options.substitution_variables
+            && !options.interpret_terminator_directive
+            && !options.interpret_set_term
+            && !options.split_on_blank_lines
+            && !options.copy_from_stdin_data
+            && !options.snowsql_bang_commands
+            && !options.honor_directive_comments
+            && options.tab_width.is_none()
+            && !options.unicode_line_breaks
+    }
+
+    // Extract the next character from the input (same as `Tokenizer::get_next_char`).
+    #[inline]
+    fn get_next_char(&mut self) -> Option<char> {
+        let next_char = self.input_iter.next();
+        if let Some(c) = &next_char {
+            self.offset = self.next_offset;
+            self.next_offset += c.len_utf8();
+        }
+        next_char
+    }
+
+    fn forward_iter(&mut self, n: usize) {
+        for _ in 0..n {
+            self.get_next_char();
+        }
+    }
+
+    #[inline]
+    fn check_delimiter(&self, delimiter: &str) -> bool {
+        self.input[self.offset..].starts_with(delimiter)
+    }
+
+    // The offset following a token ending with the character preceding `next_char`.
+    #[inline]
+    fn end_offset(&self, next_char: Option<char>) -> usize {
+        if next_char.is_some() {
+            self.offset
+        } else {
+            self.next_offset
+        }
+    }
+
+    // Get the position at the given offset, which must not be before the last position computed.
+    //
+    // The column of a start position is the one of the character at the offset, the column of an end position is the
+    // one of the character preceding the offset (a carriage return followed by a line feed does not have a column).
+    fn position_at(&mut self, offset: usize, is_end: bool) -> Position {
+        let (from, mut line, mut column) = self.position;
+        let bytes = self.input.as_bytes();
+        for i in from..offset {
+            match bytes[i] {
+                b'\n' => (line, column) = (line + 1, 0),
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {}
+                b'\r' => (line, column) = (line + 1, 0),
+                // Continuation bytes of multi-byte characters.
+                b if b & 0xC0 == 0x80 => {}
+                _ => column += 1,
+            }
+        }
+        self.position = (offset, line, column);
+        Position { line, column: if is_end { column } else { column + 1 }, offset }
+    }
+
+    // Scan the next statement, including the empty ones.
+    //
+    // Returns the start and end offsets of the statement, and whether the statement is empty (see
+    // `Statement::is_empty`).
+    fn scan_statement(&mut self) -> Option<(usize, usize, bool)> {
+        let mut start = None;
+        let mut end = 0;
+        let mut empty = true;
+        let delimiter_start_char =
+            self.options.statement_delimiter.chars().next().expect("delimiter must not be empty");
+        self.closing_chars.clear();
+        let mut next_char = self.get_next_char();
+        while let Some(c) = next_char {
+            if c == delimiter_start_char && self.check_delimiter(&self.options.statement_delimiter) {
+                //
+                // Delimiter.
+                //
+                let delimiter_offset = self.offset;
+                self.forward_iter(self.options.statement_delimiter.chars().count() - 1);
+                let start = start.unwrap_or(delimiter_offset);
+                return Some(if self.options.include_delimiter_token {
+                    (start, self.next_offset, empty)
+                } else if !self.closing_chars.is_empty() {
+                    // The unclosed fragment ends with the delimiter.
+                    (start, delimiter_offset, empty)
+                } else {
+                    (start, end.max(start), empty)
+                });
+            } else if c.is_whitespace() {
+                next_char = self.get_next_char();
+                continue;
+            }
+            start.get_or_insert(self.offset);
+            if (c == '#' && self.options.hash_comments && !self.check_delimiter("#>"))
+                || (c == '-' && self.check_delimiter("--") && self.is_double_dash_comment())
+            {
+                //
+                // Single-line comment.
+                //
+                end = self.skip_single_line_comment();
+            } else if c == '/' && self.check_delimiter("/*") {
+                //
+                // Multi-line comment (a versioned comment is not empty).
+                //
+                empty &= !self.check_delimiter("/*!");
+                end = self.skip_multi_line_comment();
+            } else {
+                empty = false;
+                match c {
+                    '\'' | '"' | '`' => {
+                        (next_char, end) = self.skip_quoted_identifier_or_constant(c, QuoteEscaping::Doubled);
+                        continue;
+                    }
+                    'U' | 'u'
+                        if self.input[self.offset + 1..].starts_with("&\"")
+                            || self.input[self.offset + 1..].starts_with("&'") =>
+                    {
+                        self.forward_iter(2);
+                        let quote_char = self.input[self.offset..].chars().next().unwrap();
+                        (next_char, end) = self.skip_quoted_identifier_or_constant(quote_char, QuoteEscaping::Doubled);
+                        continue;
+                    }
+                    '$' => {
+                        (next_char, end) = self.skip_dollar_sign();
+                        continue;
+                    }
+                    ':' | '?' | '@' if !self.check_delimiter(":=") && !self.is_json_operator(c) => {
+                        let token_start = self.offset;
+                        next_char = self.get_next_char();
+                        while next_char.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                            next_char = self.get_next_char();
+                        }
+                        if c != ':' || next_char != Some(':') || token_start + 1 != self.offset {
+                            end = self.end_offset(next_char);
+                            continue;
+                        }
+                        // The type casting operator `::` (consuming `next_char`).
+                    }
+                    '(' => self.closing_chars.push(')'),
+                    '{' if self.options.jdbc_escapes => self.closing_chars.push('}'),
+                    ')' | '}' if c == ')' || self.options.jdbc_escapes => {
+                        // A closing bracket closes all the brackets opened since the matching one (or none if unmatched).
+                        while self.closing_chars.pop().is_some_and(|closing_char| closing_char != c) {}
+                    }
+                    '.' => {
+                        let token_start = self.offset;
+                        next_char = self.get_next_char();
+                        (next_char, end) = if next_char.is_some_and(|c| c.is_ascii_digit()) {
+                            self.skip_numeric_constant("_0123456789.eE+-", token_start)
+                        } else {
+                            (next_char, self.end_offset(next_char))
+                        };
+                        continue;
+                    }
+                    c if c.is_numeric() => {
+                        (next_char, end) = self.skip_numeric_constants(c);
+                        continue;
+                    }
+                    c if c.is_alphabetic() || c == '_' => {
+                        (next_char, end) = self.skip_identifier_or_keyword();
+                        continue;
+                    }
+                    // The operators are not captured: none of them has a character starting another token after its
+                    // first one (`::`, `:=`, `?|`, `?&` and `@>` are already excluded from the parameter markers).
+                    _ => {}
+                }
+                end = self.next_offset;
+            }
+            next_char = self.get_next_char();
+        }
+        // The end of the input was reached without finding the delimiter.
+        start.map(|start| (start, if self.closing_chars.is_empty() { end } else { self.next_offset }, empty))
+    }
+
+    // Check if the `--` at the current position starts a single-line comment (see `Tokenizer::is_double_dash_comment`).
+    fn is_double_dash_comment(&self) -> bool {
+        !self.options.require_space_after_double_dash
+            || self.input[self.offset + 2..].chars().next().is_none_or(|c| c.is_whitespace())
+    }
+
+    // Check if the input at the current position is a `?|`, `?&` or `@>` JSON operator.
+    fn is_json_operator(&self, c: char) -> bool {
+        (c == '?'
+            && ((self.check_delimiter("?|") && !self.check_delimiter("?||"))
+                || (self.check_delimiter("?&") && !self.check_delimiter("?&&"))))
+            || (c == '@' && self.check_delimiter("@>"))
+    }
+
+    // Skip a single-line comment (see `Tokenizer::capture_single_line_comment`), returns the end offset of the comment
+    // (the line feed is not part of the comment).
+    fn skip_single_line_comment(&mut self) -> usize {
+        while let Some(c) = self.get_next_char() {
+            if c == '\n' {
+                return self.offset;
+            }
+        }
+        self.next_offset
+    }
+
+    // Skip a (possibly nested) multi-line comment (see `Tokenizer::capture_multi_line_comment`), returns the end offset
+    // of the comment.
+    fn skip_multi_line_comment(&mut self) -> usize {
+        let mut nested_level = 1;
+        let mut next_char = self.get_next_char();
+        while let Some(c) = next_char {
+            if c == '*' {
+                next_char = self.get_next_char();
+                if next_char == Some('/') {
+                    nested_level -= 1;
+                    if nested_level == 0 {
+                        break;
+                    }
+                } else {
+                    continue;
+                }
+            } else if c == '/' {
+                next_char = self.get_next_char();
+                if next_char == Some('*') {
+                    nested_level += 1;
+                } else {
+                    continue;
+                }
+            }
+            next_char = self.get_next_char();
+        }
+        self.next_offset
+    }
+
+    // Skip a quoted identifier or constant (see `Tokenizer::capture_quoted_identifier_or_constant`), returns the next
+    // character to be processed and the end offset of the token.
+    fn skip_quoted_identifier_or_constant(
+        &mut self,
+        quote_char: char,
+        escaping: QuoteEscaping,
+    ) -> (Option<char>, usize) {
+        let mut next_char = self.get_next_char();
+        while let Some(c) = next_char {
+            if c == '\\' && escaping != QuoteEscaping::Doubled {
+                self.get_next_char();
+            } else if c == quote_char {
+                next_char = self.get_next_char();
+                if next_char != Some(quote_char) || escaping == QuoteEscaping::Backslash {
+                    return (next_char, self.end_offset(next_char));
+                }
+            }
+            next_char = self.get_next_char();
+        }
+        (None, self.next_offset)
+    }
+
+    // Skip a token ending with the given delimiter (see `Tokenizer::capture_delimited_token`), returns the next
+    // character to be processed and the end offset of the token.
+    fn skip_delimited_token(&mut self, delimiter: &str) -> (Option<char>, usize) {
+        let delimiter_start_char = delimiter.chars().next().expect("delimiter must not be empty");
+        while let Some(c) = self.get_next_char() {
+            if c == delimiter_start_char && self.check_delimiter(delimiter) {
+                let end_offset = self.offset + delimiter.len();
+                self.forward_iter(delimiter.chars().count() - 1);
+                return (self.get_next_char(), end_offset);
+            }
+        }
+        (None, self.next_offset)
+    }
+
+    // Skip a dollar-quoted string (`$tag$...$tag$`), a parameter marker (`$1`, `$id`) or a money constant (`$12.34`).
+    fn skip_dollar_sign(&mut self) -> (Option<char>, usize) {
+        let token_start = self.offset;
+        let mut next_char = self.get_next_char();
+        if next_char.is_some_and(|c| c.is_ascii_digit()) {
+            return self.skip_numeric_constant("_0123456789.eE+-", token_start);
+        }
+        while next_char.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+            next_char = self.get_next_char();
+        }
+        if next_char == Some('$') && self.options.dollar_quoted_strings {
+            let input = self.input;
+            self.skip_delimited_token(&input[token_start..self.next_offset])
+        } else {
+            (next_char, self.end_offset(next_char))
+        }
+    }
+
+    // Skip the numeric constants starting with the given character (see `Tokenizer::capture_fragment`).
+    fn skip_numeric_constants(&mut self, c: char) -> (Option<char>, usize) {
+        let token_start = self.offset;
+        if c != '0' {
+            return self.skip_numeric_constant("_0123456789.eE+-", token_start);
+        }
+        match self.get_next_char() {
+            Some('x' | 'X') => self.skip_numeric_constant("_0123456789abcdefABCDEF", token_start),
+            Some('o' | 'O' | 'b' | 'B') => self.skip_numeric_constant("_0123456789", token_start),
+            Some('.' | '0'..='9') => self.skip_numeric_constant("_0123456789.eE+-", token_start),
+            next_char => (next_char, self.end_offset(next_char)),
+        }
+    }
+
+    // Skip a numeric constant (see `Tokenizer::capture_numeric_constant`), returns the next character to be processed
+    // and the end offset of the constant.
+    fn skip_numeric_constant(&mut self, allowed_chars: &str, token_start: usize) -> (Option<char>, usize) {
+        let mut decimal_point_allowed = !self.input[token_start..self.next_offset].contains('.');
+        let mut next_char = self.get_next_char();
+        while let Some(c) = next_char {
+            if !allowed_chars.contains(c) {
+                break;
+            } else if c == '.' {
+                if !decimal_point_allowed {
+                    break;
+                }
+                decimal_point_allowed = false;
+            } else if (c == 'e' || c == 'E') && allowed_chars.contains('+') {
+                decimal_point_allowed = false;
+                next_char = self.get_next_char();
+                if !next_char.is_some_and(|c| c == '+' || c == '-' || c.is_ascii_digit()) {
+                    break;
+                }
+            } else if c == '+' || c == '-' {
+                break;
+            }
+            next_char = self.get_next_char();
+        }
+        if allowed_chars.contains('+') && matches!(next_char, Some('f' | 'F' | 'd' | 'D')) {
+            let last_char = self.input[token_start..self.offset].chars().last();
+            let following_char = self.input[self.next_offset..].chars().next();
+            if matches!(last_char, Some('0'..='9' | '.'))
+                && !matches!(following_char, Some(c) if c.is_alphanumeric() || c == '_' || c == '$')
+            {
+                next_char = self.get_next_char();
+            }
+        }
+        (next_char, self.end_offset(next_char))
+    }
+
+    // Skip an identifier or a keyword, including the constant following it if it's an introducer (`E'...'`, `r"..."`),
+    // returns the next character to be processed and the end offset of the token.
+    fn skip_identifier_or_keyword(&mut self) -> (Option<char>, usize) {
+        let token_start = self.offset;
+        let mut next_char = self.get_next_char();
+        while next_char.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+            next_char = self.get_next_char();
+        }
+        let introducer = &self.input[token_start..self.offset];
+        match next_char {
+            Some('\'') if introducer == "X" || introducer == "x" => self.skip_delimited_token("'"),
+            Some('\'') if introducer == "E" || introducer == "e" => {
+                self.skip_quoted_identifier_or_constant('\'', QuoteEscaping::DoubledOrBackslash)
+            }
+            Some('\'') if is_raw_string_introducer(introducer) => {
+                self.skip_quoted_identifier_or_constant('\'', QuoteEscaping::Backslash)
+            }
+            Some('\'') => self.skip_quoted_identifier_or_constant('\'', QuoteEscaping::Doubled),
+            Some('"') if is_raw_string_introducer(introducer) => {
+                self.skip_quoted_identifier_or_constant('"', QuoteEscaping::Backslash)
+            }
+            Some('"') if is_double_quoted_string_introducer(introducer) => {
+                self.skip_quoted_identifier_or_constant('"', QuoteEscaping::Doubled)
+            }
+            _ => (next_char, self.end_offset(next_char)),
+        }
+    }
+}
+
+impl<'s> Iterator for Scanner<'s> {
+    type Item = StatementSpan<'s>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (start, end, empty) = self.scan_statement()?;
+            if !self.options.skip_empty_statements || !empty {
+                let start = self.position_at(start, false);
+                // A statement only containing a delimiter not included ends where it starts (see `Statement::end`).
+                let end = if end == start.offset { start.clone() } else { self.position_at(end, true) };
+                return Some(StatementSpan { sql: &self.input[start.offset..end.offset], start, end });
+            }
+        }
+    }
+
+    // Counting the statements does not need their positions.
+    fn count(mut self) -> usize {
+        let mut count = 0;
+        while let Some((_, _, empty)) = self.scan_statement() {
+            if !self.options.skip_empty_statements || !empty {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+// Check if the input contains the given ASCII word, ignoring the ASCII case.
+fn contains_ignore_ascii_case(input: &str, word: &str) -> bool {
+    input.as_bytes().windows(word.len()).any(|window| window.eq_ignore_ascii_case(word.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_statements, loose_sqlparse_with_options, split, Dialect};
+
+    // The dialect presets.
+    const DIALECTS: [Dialect; 13] = [
+        Dialect::Generic,
+        Dialect::PostgreSql,
+        Dialect::MySql,
+        Dialect::SqlServer,
+        Dialect::Oracle,
+        Dialect::Sqlite,
+        Dialect::Snowflake,
+        Dialect::BigQuery,
+        Dialect::Informix,
+        Dialect::Db2,
+        Dialect::Firebird,
+        Dialect::DuckDb,
+        Dialect::ClickHouse,
+    ];
+
+    // The option sets using the scanner.
+    fn scanner_options() -> Vec<Options> {
+        let options = vec![
+            Options::default(),
+            Options { hash_comments: false, dollar_quoted_strings: false, ..Options::default() },
+            Options { require_space_after_double_dash: true, ..Options::default() },
+            Options { include_delimiter_token: false, ..Options::default() },
+            Options { include_delimiter_token: false, skip_empty_statements: true, ..Options::default() },
+            Options { jdbc_escapes: true, case_fragments: true, begin_atomic_blocks: true, ..Options::default() },
+            Options { statement_delimiter: ";;".to_string(), ..Options::default() },
+            Options { statement_delimiter: "§".to_string(), include_delimiter_token: false, ..Options::default() },
+        ];
+        assert!(options.iter().all(|options| Scanner::supports("", options)));
+        options
+    }
+
+    fn assert_same_spans(input: &str, options: &Options) {
+        let expected: Vec<_> =
+            loose_sqlparse_with_options(input, options.clone()).unwrap().map(StatementSpan::from).collect();
+        assert_eq!(split(input, options).unwrap().collect::<Vec<_>>(), expected, "input: {input:?}, {options:?}");
+    }
+
+    #[test]
+    fn test_split() {
+        let inputs = [
+            "",
+            " \n ",
+            "SELECT 1; SELECT 2;\nSELECT 'a;b' -- c;\n, \"d;e\"; /* f; */ SELECT (1;",
+            "SELECT 'héllo'; SELECT 2;\r\n\tSELECT naïve, \"日本\" -- コメント\n/* ü */ FROM t;é;\nSELECT (ñ + 1)",
+            "é;ü;SELECT $$ü;$$, $tag$;$ta$tag$, $é, $1;2, :ñ, 'ü'::text;😀",
+            "SELECT 1 # ü\n;;\n;SELECT 'unterminated ü",
+            "SELECT E'\\';', r'\\';', X'\\';', b\"\\\";\", U&'\\';', `a;b`, 'a'';b';",
+            "SELECT 1e-3;2, 0x1F;3, .5e+2d;4, 0b1;/* /* nested; */ ; */ 5--6;\r7 #> '{a}';8 #- -- c;\n9 /*/;",
+            "SELECT ((1; (2}; {fn a(;} ?|b; ?||c; @>d; :=e; --+ hint;\n/*+ hint; */;/*! versioned */;-- only\n;",
+            "SELECT (1, (2)\n\n;\r\nSELECT ( \r;SELECT (\n",
+            "CREATE FUNCTION f() BEGIN ATOMIC SELECT 1; SELECT 2; END; SELECT CASE WHEN 1 THEN 2 END;;;",
+        ];
+        let options =
+            scanner_options().into_iter().chain(DIALECTS.iter().map(|dialect| Options::for_dialect(*dialect)));
+        for options in options.collect::<Vec<_>>() {
+            for input in inputs {
+                assert_same_spans(input, &options);
+            }
+        }
+    }
+
+    // Inputs made of random pieces that may start, end or hide tokens.
+    fn random_inputs(count: usize) -> Vec<String> {
+        let pieces = [
+            "SELECT", " ", "\n", "\r\n", "\r", "\t", ";", ";;", "§", "'", "\"", "`", "$$", "$a$", "$1", "$", "--", "#",
+            "#>", "#-", "/*", "*/", "/", "*", "--+", "/*+", "/*!", "E'", "r'", "X'", "b\"", "U&'", "\\", "(", ")", "{",
+            "}", "1e", "-", "+", "0x", "0", ".5", ".", "d", ":a", "::", ":=", ":", "?", "?|", "@", "@>", "é", "a$",
+            "ATOMIC", "BEGIN", "END", "CASE",
+        ];
+        let mut seed: u64 = 42;
+        let mut random = |n: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % n
+        };
+        (0..count)
+            .map(|_| {
+                let len = random(20);
+                (0..len).map(|_| pieces[random(pieces.len())]).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_random_inputs() {
+        // The option sets and the dialect presets using the scanner.
+        let options: Vec<_> = scanner_options()
+            .into_iter()
+            .chain(DIALECTS.iter().map(|dialect| Options::for_dialect(*dialect)))
+            .filter(|options| Scanner::supports("", options))
+            .collect();
+        assert!(options.iter().any(|options| options.dialect == Dialect::DuckDb));
+        for input in random_inputs(2000) {
+            for options in &options {
+                assert_same_spans(&input, options);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_statements() {
+        let dialects = [Dialect::Generic, Dialect::PostgreSql, Dialect::MySql, Dialect::Oracle, Dialect::Firebird];
+        let options: Vec<_> = scanner_options()
+            .into_iter()
+            .chain(dialects.iter().map(|dialect| Options::for_dialect(*dialect)))
+            .flat_map(|options| [Options { skip_empty_statements: true, ..options.clone() }, options])
+            .collect();
+        for input in random_inputs(500) {
+            for options in &options {
+                let expected = loose_sqlparse_with_options(&input, options.clone()).unwrap().count();
+                assert_eq!(count_statements(&input, options), expected, "input: {input:?}, {options:?}");
+            }
+        }
+        // The input is only scanned for the options supported by the scanner.
+        let input = "SELECT 1; SELECT 2";
+        for dialect in [Dialect::Generic, Dialect::DuckDb, Dialect::ClickHouse] {
+            assert!(matches!(Splitter::new(input, Options::for_dialect(dialect)), Splitter::Scanner(_)), "{dialect:?}");
+        }
+        for dialect in [Dialect::PostgreSql, Dialect::MySql, Dialect::SqlServer, Dialect::Db2, Dialect::BigQuery] {
+            assert!(
+                matches!(Splitter::new(input, Options::for_dialect(dialect)), Splitter::Tokenizer(_)),
+                "{dialect:?}"
+            );
+        }
+        let options = Options { begin_atomic_blocks: true, ..Options::default() };
+        assert!(matches!(Splitter::new(input, options.clone()), Splitter::Scanner(_)));
+        assert!(matches!(Splitter::new("BEGIN ATOMIC SELECT 1; END", options), Splitter::Tokenizer(_)));
+
+        let options = Options::default();
+        assert_eq!(count_statements("", &options), 0);
+        assert_eq!(count_statements("SELECT 1; SELECT 2", &options), 2);
+        assert_eq!(count_statements("SELECT 1; -- c\n;;SELECT ';", &options), 4);
+        let options = Options { skip_empty_statements: true, ..options };
+        assert_eq!(count_statements("SELECT 1; -- c\n;;SELECT ';", &options), 2);
+        assert_eq!(count_statements("SELECT 1;", &Options { statement_delimiter: String::new(), ..options }), 0);
+    }
+
+    #[test]
+    fn test_statement_at() {
+        let inputs = [
+            "SELECT 1;\n\n  SELECT 'a;b' -- c;\n;;  -- d\n\n;SELECT (1;\r\nSELECT 2",
+            "SELECT $$ é; $$; /* e */ ;\nSELECT 'unterminated ü",
+            "SELECT 1\nGO\n\\c db\nSELECT 2;\nBEGIN NULL; END;\n/\n",
+        ];
+        let options: Vec<_> = scanner_options()
+            .into_iter()
+            .chain([Dialect::SqlServer, Dialect::PostgreSql, Dialect::Oracle].map(Options::for_dialect))
+            .flat_map(|options| [Options { skip_empty_statements: true, ..options.clone() }, options])
+            .collect();
+        for (input, options) in inputs.iter().flat_map(|input| options.iter().map(move |options| (input, options))) {
+            let mut statements = loose_sqlparse_with_options(input, options.clone()).unwrap();
+            let mut expected = Vec::new();
+            while let Some(statement) = statements.next() {
+                expected.push((format!("{:?}", statement), statements.position().offset));
+            }
+            for offset in 0..=input.len() + 1 {
+                let statement =
+                    statement_at(input, offset, options.clone()).map(|statement| format!("{:?}", statement));
+                let expected = expected.iter().find(|(_, end_offset)| *end_offset > offset);
+                assert_eq!(statement.as_ref(), expected.map(|(statement, _)| statement), "{input:?} at {offset}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_positions() {
+        let input = "SELECT 1;\r\n  SELECT 'é\n' ; \n-- c\n;SELECT (\n";
+        let options = Options { include_delimiter_token: false, ..Options::default() };
+        let spans: Vec<_> = split(input, &options).unwrap().collect();
+        assert_eq!(
+            spans.iter().map(|span| span.sql()).collect::<Vec<_>>(),
+            ["SELECT 1", "SELECT 'é\n'", "-- c", "SELECT (\n"]
+        );
+        assert_eq!(spans[1].start(), &Position::new(2, 3, 13));
+        assert_eq!(spans[1].end(), &Position::new(3, 1, 25));
+        assert_eq!(spans[2].end(), &Position::new(4, 4, 33));
+        assert_eq!(spans[3].end(), &Position::new(6, 0, 44));
+        let options = Options { skip_empty_statements: true, ..options };
+        assert_eq!(split(input, &options).unwrap().count(), 3);
+        assert!(split(input, &Options { statement_delimiter: String::new(), ..options }).is_err());
+    }
+}
@@ -1,8 +1,347 @@
-use crate::{tokens::Tokens, Position, TokenValue};
+use crate::{tokens::Tokens, Position, Token, TokenValue};
+use std::borrow::Cow;
+use std::ops::{Range, RangeInclusive};
 
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 
+/// The style of the parameter markers used by a statement (see [`Statement::bind_style`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindStyle {
+    /// Question mark parameters: `?`, `?1` (JDBC, ODBC, MySQL, SQLite).
+    QuestionMark,
+
+    /// Dollar parameters: `$1`, `$name` (PostgreSQL).
+    Dollar,
+
+    /// Colon parameters: `:name`, `:1` (Oracle, SQLite).
+    Colon,
+
+    /// At sign parameters: `@name` (SQL Server, SQLite).
+    At,
+
+    /// Python DB-API parameters: `%s`, `%(name)s` (see [`crate::Options::pyformat_placeholders`]).
+    Percent,
+
+    /// Template parameters: `#{name}`, `${name}` (see [`crate::Options::template_placeholders`]).
+    Template,
+
+    /// More than one style is used by the statement (ex: `?` and `:name`).
+    Mixed,
+}
+
+impl BindStyle {
+    // Get the style of a parameter marker.
+    fn of(marker: &str) -> Self {
+        if marker.starts_with("#{") || marker.starts_with("${") {
+            BindStyle::Template
+        } else if marker.starts_with('$') {
+            BindStyle::Dollar
+        } else if marker.starts_with(':') {
+            BindStyle::Colon
+        } else if marker.starts_with('@') {
+            BindStyle::At
+        } else if marker.starts_with('%') {
+            BindStyle::Percent
+        } else {
+            BindStyle::QuestionMark
+        }
+    }
+}
+
+/// The type of a statement, as classified from its leading keywords (see [`Statement::statement_type`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatementType {
+    /// `SELECT ...`, including `WITH ... SELECT ...` and set operations (`(SELECT 1) UNION SELECT 2`).
+    Select,
+
+    /// `INSERT ...`, `REPLACE ...` (MySQL), `UPSERT ...` (CockroachDB).
+    Insert,
+
+    /// `UPDATE ...`
+    Update,
+
+    /// `DELETE ...`
+    Delete,
+
+    /// `MERGE ...`
+    Merge,
+
+    /// `VALUES ...`
+    Values,
+
+    /// `TRUNCATE ...`
+    Truncate,
+
+    /// `CREATE [OR REPLACE] [TEMPORARY | UNIQUE | ...] <object> ...`
+    Create(ObjectType),
+
+    /// `ALTER <object> ...`
+    Alter(ObjectType),
+
+    /// `DROP <object> ...`
+    Drop(ObjectType),
+
+    /// `COMMENT ON ...`
+    Comment,
+
+    /// `RENAME ...` (MySQL: `RENAME TABLE`, `RENAME USER`)
+    Rename,
+
+    /// `BEGIN ...`, `START TRANSACTION ...`
+    Begin,
+
+    /// `COMMIT ...`, `END ...` (PostgreSQL)
+    Commit,
+
+    /// `ROLLBACK ...`, including `ROLLBACK TO SAVEPOINT ...`
+    Rollback,
+
+    /// `SAVEPOINT ...`
+    Savepoint,
+
+    /// `RELEASE [SAVEPOINT] ...`
+    Release,
+
+    /// `SET ...`
+    Set,
+
+    /// `SHOW ...`
+    Show,
+
+    /// `EXPLAIN ...`
+    Explain,
+
+    /// `DESCRIBE ...`, `DESC ...`
+    Describe,
+
+    /// `GRANT ...`
+    Grant,
+
+    /// `REVOKE ...`
+    Revoke,
+
+    /// `USE ...`
+    Use,
+
+    /// `CALL ...`, including the JDBC call escape sequences (`{call ...}`)
+    Call,
+
+    /// `EXEC ...`, `EXECUTE ...`
+    Execute,
+
+    /// Any other statement, including empty statements and client commands.
+    Unknown,
+}
+
+/// The type of the object created, altered or dropped by a DDL statement (see [`StatementType`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ObjectType {
+    /// `TABLE`, including temporary and external tables.
+    Table,
+
+    /// `VIEW`, including recursive views.
+    View,
+
+    /// `MATERIALIZED VIEW`
+    MaterializedView,
+
+    /// `INDEX`, including unique and (non) clustered indexes.
+    Index,
+
+    /// `SEQUENCE`
+    Sequence,
+
+    /// `SCHEMA`
+    Schema,
+
+    /// `DATABASE`
+    Database,
+
+    /// `FUNCTION`
+    Function,
+
+    /// `PROCEDURE`, `PROC` (SQL Server)
+    Procedure,
+
+    /// `TRIGGER`
+    Trigger,
+
+    /// `TYPE`
+    Type,
+
+    /// `ROLE`
+    Role,
+
+    /// `USER`
+    User,
+
+    /// `EXTENSION` (PostgreSQL)
+    Extension,
+
+    /// Any other object (ex: `CREATE DOMAIN`, `ALTER SYSTEM`).
+    Other,
+}
+
+impl StatementType {
+    /// Returns whether the statement is a Data Definition Language statement (`CREATE`, `ALTER`, `DROP`, `TRUNCATE`,
+    /// `COMMENT`, `RENAME`).
+    pub fn is_ddl(&self) -> bool {
+        matches!(
+            self,
+            StatementType::Create(_)
+                | StatementType::Alter(_)
+                | StatementType::Drop(_)
+                | StatementType::Truncate
+                | StatementType::Comment
+                | StatementType::Rename
+        )
+    }
+
+    /// Returns whether the statement is a Data Manipulation Language statement (`SELECT`, `INSERT`, `UPDATE`, `DELETE`,
+    /// `MERGE`).
+    pub fn is_dml(&self) -> bool {
+        matches!(
+            self,
+            StatementType::Select
+                | StatementType::Insert
+                | StatementType::Update
+                | StatementType::Delete
+                | StatementType::Merge
+        )
+    }
+
+    /// Returns whether the statement is a Data Control Language statement (`GRANT`, `REVOKE`).
+    pub fn is_dcl(&self) -> bool {
+        matches!(self, StatementType::Grant | StatementType::Revoke)
+    }
+
+    /// Returns whether the statement is a Transaction Control Language statement (`BEGIN`, `START TRANSACTION`,
+    /// `COMMIT`, `ROLLBACK`, `SAVEPOINT`, `RELEASE`).
+    pub fn is_tcl(&self) -> bool {
+        matches!(
+            self,
+            StatementType::Begin
+                | StatementType::Commit
+                | StatementType::Rollback
+                | StatementType::Savepoint
+                | StatementType::Release
+        )
+    }
+
+    // Classify a statement from its top level words (uppercase).
+    fn of(words: &[&str]) -> Self {
+        match words {
+            ["WITH", rest @ ..] => {
+                // Skip the CTEs (their body is in a fragment) up to the main statement.
+                match rest.iter().position(|w| matches!(*w, "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "MERGE")) {
+                    Some(index) => StatementType::of(&rest[index..]),
+                    None => StatementType::Unknown,
+                }
+            }
+            ["SELECT", ..] => StatementType::Select,
+            ["INSERT" | "REPLACE" | "UPSERT", ..] => StatementType::Insert,
+            ["UPDATE", ..] => StatementType::Update,
+            ["DELETE", ..] => StatementType::Delete,
+            ["MERGE", ..] => StatementType::Merge,
+            ["VALUES", ..] => StatementType::Values,
+            ["TRUNCATE", ..] => StatementType::Truncate,
+            ["CREATE", rest @ ..] => StatementType::Create(ObjectType::of(rest)),
+            ["ALTER", rest @ ..] => StatementType::Alter(ObjectType::of(rest)),
+            ["DROP", rest @ ..] => StatementType::Drop(ObjectType::of(rest)),
+            ["COMMENT", "ON", ..] => StatementType::Comment,
+            ["RENAME", ..] => StatementType::Rename,
+            ["BEGIN", ..] | ["START", "TRANSACTION", ..] => StatementType::Begin,
+            ["COMMIT" | "END", ..] => StatementType::Commit,
+            ["ROLLBACK", ..] => StatementType::Rollback,
+            ["SAVEPOINT", ..] => StatementType::Savepoint,
+            ["RELEASE", ..] => StatementType::Release,
+            ["SET", ..] => StatementType::Set,
+            ["SHOW", ..] => StatementType::Show,
+            ["EXPLAIN", ..] => StatementType::Explain,
+            ["DESCRIBE" | "DESC", ..] => StatementType::Describe,
+            ["GRANT", ..] => StatementType::Grant,
+            ["REVOKE", ..] => StatementType::Revoke,
+            ["USE", ..] => StatementType::Use,
+            ["CALL", ..] => StatementType::Call,
+            ["EXEC" | "EXECUTE", ..] => StatementType::Execute,
+            _ => StatementType::Unknown,
+        }
+    }
+}
+
+impl ObjectType {
+    // Get the type of the object from the words following `CREATE`, `ALTER` or `DROP` (uppercase).
+    fn of(words: &[&str]) -> Self {
+        let mut words = words.iter().skip_while(|w| {
+            matches!(
+                **w,
+                "OR" | "REPLACE"
+                    | "ALTER"
+                    | "TEMPORARY"
+                    | "TEMP"
+                    | "GLOBAL"
+                    | "LOCAL"
+                    | "UNLOGGED"
+                    | "EXTERNAL"
+                    | "TRANSIENT"
+                    | "VOLATILE"
+                    | "RECURSIVE"
+                    | "UNIQUE"
+                    | "CLUSTERED"
+                    | "NONCLUSTERED"
+            )
+        });
+        match words.next().copied() {
+            Some("TABLE") => ObjectType::Table,
+            Some("VIEW") => ObjectType::View,
+            Some("MATERIALIZED") => ObjectType::MaterializedView,
+            Some("INDEX") => ObjectType::Index,
+            Some("SEQUENCE") => ObjectType::Sequence,
+            Some("SCHEMA") => ObjectType::Schema,
+            Some("DATABASE") => ObjectType::Database,
+            Some("FUNCTION") => ObjectType::Function,
+            Some("PROCEDURE" | "PROC") => ObjectType::Procedure,
+            Some("TRIGGER") => ObjectType::Trigger,
+            Some("TYPE") => ObjectType::Type,
+            Some("ROLE") => ObjectType::Role,
+            Some("USER") => ObjectType::User,
+            Some("EXTENSION") => ObjectType::Extension,
+            _ => ObjectType::Other,
+        }
+    }
+}
+
+/// A problem found in the input and recovered by the parser (see [`Statement::diagnostics`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Diagnostic {
+    /// The kind of problem.
+    pub kind: DiagnosticKind,
+
+    /// The start position of the construct having the problem (ex: the opening quote of an unterminated string).
+    pub position: Position,
+}
+
+/// The kind of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum DiagnosticKind {
+    /// A string constant without its closing quote (`'...`, `E'...`, `'''...`).
+    UnterminatedString,
+
+    /// A quoted identifier without its closing quote (`"...`, `` `... ``).
+    UnterminatedQuotedIdentifier,
+
+    /// A dollar-quoted string without its closing tag (`$$...`, `$tag$...`).
+    UnterminatedDollarQuotedString,
+
+    /// A multi-line comment without its end (`/* ...`, `{ ...`).
+    UnterminatedComment,
+}
+
 // A SQL statement.
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
@@ -12,83 +351,640 @@ pub struct Statement<'s> {
 
     // All tokens found in the statement.
     pub(crate) tokens: Tokens<'s>,
+
+    // The statement delimiter when it's not included in the tokens (see `Options::include_delimiter_token`).
+    #[cfg_attr(feature = "serialize", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) delimiter: Option<Token<'s>>,
+
+    // The problems recovered by the parser while capturing the statement.
+    #[cfg_attr(feature = "serialize", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) diagnostics: Vec<Diagnostic>,
+
+    // Whether `CALL ...` statements are queries, from the options in effect when the statement was parsed (see
+    // `Options::calls_are_queries`).
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub(crate) calls_are_queries: bool,
+
+    // Whether `FROM ...` statements are queries, from the options in effect when the statement was parsed (see
+    // `Options::from_first_queries`).
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub(crate) from_first_queries: bool,
 }
 
 impl Statement<'_> {
     /// The SQL statement.
     pub fn sql(&self) -> &str {
-        return &self.input[self.start().offset..self.end().offset];
+        &self.input[self.start().offset..self.end().offset]
     }
 
     /// The start position of the statement.
     pub fn start(&self) -> &Position {
-        &self.tokens[0].start
+        match self.tokens.first() {
+            Some(token) => &token.start,
+            // The statement only contains a delimiter which is not included in the tokens.
+            None => &self.delimiter_token().expect("a statement without tokens must have a delimiter").start,
+        }
     }
 
     /// The column where the statement starts.
     pub fn end(&self) -> &Position {
-        &self.tokens[self.tokens.len() - 1].end
+        match self.tokens.last() {
+            Some(token) => &token.end,
+            // The statement only contains a delimiter which is not included in the tokens.
+            None => self.start(),
+        }
+    }
+
+    /// The byte range of the statement in the input (see [`Statement::sql`]).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let input = "SELECT 1;\nSELECT 2;";
+    /// let stmt = loose_sqlparse(input).nth(1).unwrap();
+    /// assert_eq!(stmt.range(), 10..19);
+    /// assert_eq!(&input[stmt.range()], "SELECT 2;");
+    /// ```
+    pub fn range(&self) -> Range<usize> {
+        self.start().offset..self.end().offset
+    }
+
+    /// Returns whether the byte at the given offset is part of the statement.
+    ///
+    /// The start offset of the statement is included and its end offset is excluded (see [`crate::Position`]), so
+    /// the offset following the statement is not part of it.
+    pub fn contains_offset(&self, offset: usize) -> bool {
+        self.range().contains(&offset)
+    }
+
+    /// Returns whether the character at the given line and column is part of the statement.
+    ///
+    /// The line and column of the start and end positions of the statement are both the ones of characters of the
+    /// statement (see [`crate::Position`]), so both are included. This is consistent with
+    /// [`Statement::contains_offset`]: the character following the statement is not part of it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT 1;\nSELECT\n  2;").nth(1).unwrap();
+    /// assert!(stmt.contains_position(2, 1));
+    /// assert!(stmt.contains_position(3, 4));
+    /// assert!(!stmt.contains_position(3, 5));
+    /// assert!(!stmt.contains_position(1, 9));
+    /// ```
+    pub fn contains_position(&self, line: usize, column: usize) -> bool {
+        let (start, end) = (self.start(), self.end());
+        !self.range().is_empty()
+            && (start.line, start.column) <= (line, column)
+            && (line, column) <= (end.line, end.column)
     }
 
     pub fn tokens(&self) -> &Tokens<'_> {
         &self.tokens
     }
 
+    /// The statement delimiter terminating the statement (ex: `;`, `\G`), if any.
+    ///
+    /// The last statement of the input may not be terminated by a delimiter.
+    pub fn terminator(&self) -> Option<&str> {
+        self.delimiter_token().map(|token| token.value.as_ref())
+    }
+
+    /// The token of the statement delimiter terminating the statement, if any.
+    ///
+    /// The token is returned even if it's not included in the tokens of the statement (see
+    /// [`crate::Options::include_delimiter_token`]).
+    pub fn delimiter_token(&self) -> Option<&Token<'_>> {
+        self.tokens.last().filter(|token| token.is_statement_delimiter()).or(self.delimiter.as_ref())
+    }
+
+    /// Returns whether the statement is terminated by a statement delimiter.
+    ///
+    /// Only the last statement of the input may not be terminated (cut off by the end of the input).
+    pub fn is_terminated(&self) -> bool {
+        self.delimiter_token().is_some()
+    }
+
+    /// Returns whether the statement is complete: terminated by a statement delimiter (or a client command), with all
+    /// its strings, quoted identifiers, comments and brackets closed.
+    ///
+    /// See [`crate::is_complete`] to check if an input typed in an interactive shell is complete.
+    pub fn is_complete(&self) -> bool {
+        (self.is_terminated() || self.is_client_command())
+            && self.diagnostics.is_empty()
+            && !has_unclosed_bracket(&self.tokens)
+    }
+
+    /// The range of the lines covered by the statement (1-based).
+    ///
+    /// A line break ending the statement (ex: an unterminated string) does not extend the range to the following line.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("\nSELECT 1,\n  2;").next().unwrap();
+    /// assert_eq!(stmt.line_range(), 2..=3);
+    /// ```
+    pub fn line_range(&self) -> RangeInclusive<usize> {
+        line_range(self.start(), self.end())
+    }
+
+    /// The problems recovered by the parser while capturing the statement, in the order they were found.
+    ///
+    /// The parser never fails, an unterminated string, quoted identifier or comment is captured up to the end of the
+    /// input and reported here.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, DiagnosticKind};
+    /// let stmt = loose_sqlparse("SELECT 1;\nSELECT 'oops").nth(1).unwrap();
+    /// assert_eq!(stmt.diagnostics()[0].kind, DiagnosticKind::UnterminatedString);
+    /// assert_eq!((stmt.diagnostics()[0].position.line, stmt.diagnostics()[0].position.column), (2, 8));
+    /// ```
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     /// The list of keywords found in the statement at the top level.
     /// Keywords found on CTEs or sub queries are not included in this list.
+    ///
+    /// Only the words of the built-in keyword table are returned (see [`Token::is_keyword`]), identifiers such as table
+    /// or column names are not keywords. A non-reserved keyword (ex: `NAME`, `RETURNING`) used as an identifier is not
+    /// returned either: it's part of a qualified name, a list or an expression (`t.name`, `name, x`, `returning = 1`),
+    /// or it's following a keyword introducing a name (`SELECT name`, `ORDER BY date`, `INSERT INTO name`).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse(r#"SELECT name, "from" FROM users WHERE id IN (SELECT 1)"#).next().unwrap();
+    /// assert_eq!(stmt.keywords(), ["SELECT", "FROM", "WHERE", "IN"]);
+    /// ```
     pub fn keywords(&self) -> Vec<&str> {
-        self.tokens
+        self.keyword_tokens().into_iter().map(|token| token.value.as_ref()).collect()
+    }
+
+    /// The tokens of the keywords found in the statement at the top level (see [`Statement::keywords`]).
+    pub fn keyword_tokens(&self) -> Vec<&Token<'_>> {
+        let tokens: Vec<_> = self.tokens.iter().filter(|t| is_significant(t)).collect();
+        tokens
             .iter()
-            .filter(|token| match token.value {
-                TokenValue::IdentifierOrKeyword(value) => value.chars().all(|c| c.is_ascii_alphabetic()),
-                _ => false,
+            .enumerate()
+            .filter(|&(index, token)| {
+                token.is_reserved_keyword()
+                    || (token.is_keyword()
+                        && !is_identifier_position(
+                            index.checked_sub(1).map(|i| tokens[i]),
+                            tokens.get(index + 1).copied(),
+                        ))
+            })
+            .map(|(_, token)| *token)
+            .collect()
+    }
+
+    /// The parameter markers (`?`, `$1`, `:name`, `@name`) found in the statement, including the ones found in nested
+    /// fragments, in order of appearance.
+    ///
+    /// A parameter used several times (ex: `:a = :a`) is returned for each occurrence.
+    pub fn parameter_markers(&self) -> Vec<&Token<'_>> {
+        fn collect<'t, 's>(tokens: &'t Tokens<'s>, markers: &mut Vec<&'t Token<'s>>) {
+            for token in tokens.iter() {
+                if let Some(children) = token.children() {
+                    collect(children, markers);
+                } else if token.is_parameter_marker() || token.is_placeholder() {
+                    markers.push(token);
+                }
+            }
+        }
+        let mut markers = Vec::new();
+        collect(&self.tokens, &mut markers);
+        markers
+    }
+
+    /// The question mark placeholders (`?`, `?NNN`) found in the statement with their index, including the ones found
+    /// in nested fragments, in order of appearance.
+    ///
+    /// A numbered placeholder (`?2`) has the index given by its number, a `?` has the index following the largest index
+    /// already assigned (SQLite), which is its position when the statement only uses `?`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT * FROM t WHERE a = ? AND b = ?2").next().unwrap();
+    /// let placeholders: Vec<_> = stmt.placeholders().into_iter().map(|(i, token)| (i, token.value.as_ref())).collect();
+    /// assert_eq!(placeholders, [(1, "?"), (2, "?2")]);
+    /// ```
+    pub fn placeholders(&self) -> Vec<(usize, &Token<'_>)> {
+        let mut largest_index = 0;
+        self.parameter_markers()
+            .into_iter()
+            .filter(|marker| marker.is_placeholder())
+            .map(|marker| {
+                let index = marker.value.as_ref()[1..].parse().unwrap_or(largest_index + 1);
+                largest_index = largest_index.max(index);
+                (index, marker)
             })
-            .map(|token| token.value.as_ref())
             .collect()
     }
 
+    /// The style of the parameter markers used by the statement.
+    ///
+    /// Returns `None` if the statement has no parameter markers and [`BindStyle::Mixed`] if it's using more than one
+    /// style, which is usually a mistake. Question marks found in string constants or comments are not parameters.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, BindStyle};
+    /// let stmt = loose_sqlparse("SELECT * FROM t WHERE a = ? AND b = '?'").next().unwrap();
+    /// assert_eq!(stmt.bind_style(), Some(BindStyle::QuestionMark));
+    /// let stmt = loose_sqlparse("SELECT * FROM t WHERE a = ? AND b = :b").next().unwrap();
+    /// assert_eq!(stmt.bind_style(), Some(BindStyle::Mixed));
+    /// ```
+    pub fn bind_style(&self) -> Option<BindStyle> {
+        let mut styles = self.parameter_markers().into_iter().map(|marker| BindStyle::of(marker.value.as_ref()));
+        let style = styles.next()?;
+        if styles.all(|other| other == style) {
+            Some(style)
+        } else {
+            Some(BindStyle::Mixed)
+        }
+    }
+
+    /// The substitution variables (Oracle SQL*Plus: `&name`, `&&name`, Hive: `${name}`) found in the statement,
+    /// including the ones found in nested fragments.
+    ///
+    /// Each variable is returned with its name (without the leading `&`, `&&` or `${`, the optional terminating period
+    /// and the closing `}`) and its start position. Substitution variables are only recognized if
+    /// [`crate::Options::substitution_variables`] or [`crate::Options::hive_substitutions`] is enabled.
+    pub fn substitution_variables(&self) -> Vec<(&str, &Position)> {
+        fn collect<'t>(tokens: &'t Tokens<'_>, variables: &mut Vec<(&'t str, &'t Position)>) {
+            for token in tokens.iter() {
+                if let Some(children) = token.children() {
+                    collect(children, variables);
+                } else if let TokenValue::SubstitutionVariable(value) = token.value {
+                    let name = match value.strip_prefix("${") {
+                        Some(name) => name.strip_suffix('}').unwrap_or(name),
+                        None => value.trim_start_matches('&').trim_end_matches('.'),
+                    };
+                    variables.push((name, &token.start));
+                }
+            }
+        }
+        let mut variables = Vec::new();
+        collect(&self.tokens, &mut variables);
+        variables
+    }
+
     /// Returns whether the statement is empty.
     ///
-    /// An empty statement is a statement that contains nothing else that comments or whitespace.
+    /// An empty statement is a statement that contains nothing else that comments (including optimizer hints) or
+    /// whitespace.
     pub fn is_empty(&self) -> bool {
-        self.tokens.iter().all(|t| t.is_comment() || t.is_statement_delimiter())
+        self.tokens.iter().all(|t| t.is_comment() || t.is_hint() || t.is_statement_delimiter())
+    }
+
+    /// Returns whether the statement is a client command (psql: `\connect mydb`, SQLite: `.mode csv`, SQL*Plus:
+    /// `@script.sql`, SnowSQL: `!source file.sql`) rather than SQL.
+    ///
+    /// Client commands are only recognized if enabled by the options (see [`TokenValue::ClientCommand`]).
+    pub fn is_client_command(&self) -> bool {
+        self.tokens.iter().any(|t| t.is_client_command())
+    }
+
+    /// Returns whether the statement is the inline data of a `COPY ... FROM STDIN` statement (PostgreSQL).
+    ///
+    /// The inline data is only captured if [`crate::Options::copy_from_stdin_data`] is enabled.
+    pub fn is_copy_data(&self) -> bool {
+        self.tokens.iter().any(|t| t.is_copy_data())
     }
 
     /// Returns whether the statement is a query or a command.
     ///
     /// The following SQL statements are considered queries:
-    /// - SELECT ... (excluding SELECT INTO)
+    /// - SELECT ... (excluding SELECT ... INTO a table, a file or variables: `INTO t`, `INTO OUTFILE`, `INTO @var`)
     /// - SHOW ...
     /// - DESCRIBE ...
     /// - EXPLAIN ...
-    /// - WITH ... SELECT ...
     /// - VALUES ...
+    /// - TABLE ...
+    /// - FETCH ... (excluding FETCH ... INTO)
     /// - LIST ...
     /// - PRAGMA ...
-    /// - INSERT|UPDATE|DELETE ... RETURNING ...
+    /// - INSERT|UPDATE|DELETE|MERGE ... RETURNING ...
+    /// - WITH ... followed by one of the above (ex: `WITH ... SELECT ...`, `WITH ... INSERT ... RETURNING ...`), the
+    ///   `RETURNING` clauses of the CTEs are ignored
+    /// - {call ...} and {? = call ...} (JDBC escape sequences, see [`crate::Options::jdbc_escapes`])
+    ///
+    /// Depending on the options used to parse the statement, the following statements are also queries:
+    /// - `CALL ...`, `EXEC ...` and `EXECUTE ...` if [`crate::Options::calls_are_queries`] is enabled (MySQL, SQL Server),
+    /// - `FROM ...` if [`crate::Options::from_first_queries`] is enabled (DuckDB, ClickHouse).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, loose_sqlparse_with_options, Dialect, Options};
+    /// assert!(!loose_sqlparse("FROM t SELECT a").next().unwrap().is_query());
+    /// let options = Options::for_dialect(Dialect::DuckDb);
+    /// assert!(loose_sqlparse_with_options("FROM t SELECT a", options).unwrap().next().unwrap().is_query());
+    /// ```
     pub fn is_query(&self) -> bool {
-        let keywords = self.keywords();
-        if keywords.is_empty() {
+        // 0. The statement is a JDBC call escape sequence, the stored procedure may return rows.
+        if self.is_call_escape() {
+            return true;
+        }
+        // The first word is looked up separately as the statement may start with a word that is not a keyword.
+        let Some(first) = self.first_word() else {
             return false;
+        };
+        let keywords: Vec<String> = self.keywords().iter().map(|k| k.to_ascii_uppercase()).collect();
+        // 1. The statement starts with a WITH clause, the CTE bodies are fragments and their keywords are not at the top
+        //    level: the main statement starts with the first SELECT or DML keyword following WITH.
+        let (main, clauses) = match first.as_ref() {
+            "WITH" => match keywords
+                .iter()
+                .position(|k| matches!(k.as_str(), "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "VALUES"))
+            {
+                Some(index) => (keywords[index].as_str(), &keywords[index + 1..]),
+                None => return false,
+            },
+            first => (first, &keywords[..]),
+        };
+        // 2. The statement starts with a keyword that is unambiguously a query.
+        (matches!(main, "SHOW" | "DESCRIBE" | "EXPLAIN" | "VALUES" | "TABLE" | "LIST" | "PRAGMA"))
+        // 3. The statement is an INSERT, UPDATE, DELETE or MERGE with a RETURNING clause.
+            || (matches!(main, "INSERT" | "UPDATE" | "DELETE" | "MERGE") && clauses.iter().any(|k| k == "RETURNING"))
+        // 4. The statement is a SELECT or a FETCH (except SELECT ... INTO and FETCH ... INTO).
+            || (matches!(main, "SELECT" | "FETCH") && !clauses.iter().any(|k| k == "INTO"))
+        // 5. The statement is calling a stored procedure or executing a prepared statement, which may return rows.
+            || (self.calls_are_queries && matches!(main, "CALL" | "EXEC" | "EXECUTE"))
+        // 6. The statement starts with a FROM clause (DuckDB, ClickHouse).
+            || (self.from_first_queries && main == "FROM")
+    }
+
+    /// The type of the statement, classified from its first keywords.
+    ///
+    /// Comments, leading parentheses (ex: `(SELECT 1) UNION SELECT 2`) and CTEs (ex: `WITH t AS (...) INSERT ...`) are
+    /// skipped. The classification is case-insensitive and returns [`StatementType::Unknown`] for empty statements or
+    /// statements that are not recognized.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::{loose_sqlparse, ObjectType, StatementType};
+    /// let mut statements = loose_sqlparse("with t as (select 1) insert into u select * from t; CREATE OR REPLACE VIEW v AS SELECT 1");
+    /// assert_eq!(statements.next().unwrap().statement_type(), StatementType::Insert);
+    /// assert_eq!(statements.next().unwrap().statement_type(), StatementType::Create(ObjectType::View));
+    /// ```
+    pub fn statement_type(&self) -> StatementType {
+        if self.is_call_escape() {
+            return StatementType::Call;
         }
-        // 1. The statement starts with a keyword that is unambiguously a query.
-        (matches!(keywords[0].to_uppercase().as_str(),
-            "SHOW" | "DESCRIBE" | "EXPLAIN" | "VALUES" | "LIST" | "PRAGMA"))
-        // 2. The statement starts with a WITH clause followed by a SELECT or RETURNING.
-            || (keywords[0].to_uppercase() == "WITH"
-                && keywords.iter().any(|&k| matches!(k.to_uppercase().as_str(), "SELECT" | "RETURNING")))
-        // 3. The statement is an INSERT, UPDATE, or DELETE with a RETURNING clause.
-            || (matches!(keywords[0].to_uppercase().as_str(), "INSERT" | "UPDATE" | "DELETE")
-                && keywords.iter().any(|&k| k.to_uppercase().as_str() == "RETURNING"))
-        // 4. The statement is a SELECT (except SELECT ... INTO).
-            || (keywords[0].to_uppercase() == "SELECT"
-                && !keywords.iter().any(|&k| k.to_uppercase().as_str() == "INTO"))
+        let words: Vec<String> = self.leading_words().iter().map(|word| word.to_ascii_uppercase()).collect();
+        StatementType::of(&words.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    /// Returns whether the statement is a Data Definition Language statement (see [`StatementType::is_ddl`]).
+    pub fn is_ddl(&self) -> bool {
+        self.statement_type().is_ddl()
+    }
+
+    /// Returns whether the statement is a Data Manipulation Language statement (see [`StatementType::is_dml`]).
+    ///
+    /// CTEs are skipped, so `WITH t AS (...) INSERT ...` is a DML statement.
+    pub fn is_dml(&self) -> bool {
+        self.statement_type().is_dml()
+    }
+
+    /// Returns whether the statement is a Data Control Language statement (see [`StatementType::is_dcl`]).
+    pub fn is_dcl(&self) -> bool {
+        self.statement_type().is_dcl()
+    }
+
+    /// Returns whether the statement is a Transaction Control Language statement (see [`StatementType::is_tcl`]).
+    ///
+    /// None of the four predicates is true for an unrecognized statement ([`StatementType::Unknown`]).
+    pub fn is_tcl(&self) -> bool {
+        self.statement_type().is_tcl()
+    }
+
+    /// The first token of the statement that is not a comment, an optimizer hint or a statement delimiter.
+    ///
+    /// If the statement starts with parentheses (ex: `(SELECT 1) UNION SELECT 2`), the first significant token of the
+    /// parenthesized fragment is returned (`SELECT`).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("/* c */ ((SELECT 1)) UNION SELECT 2").next().unwrap();
+    /// assert_eq!(stmt.first_significant_token().unwrap().value.as_ref(), "SELECT");
+    /// assert!(loose_sqlparse("-- comment").next().unwrap().first_significant_token().is_none());
+    /// ```
+    pub fn first_significant_token(&self) -> Option<&Token<'_>> {
+        self.leading_tokens().iter().find(|t| is_significant(t))
     }
+
+    /// The first keyword of the statement in uppercase (ex: `SELECT` for `/*+ hint */ (select 1)`).
+    ///
+    /// Returns `None` if the first significant token (see [`Statement::first_significant_token`]) is not a keyword
+    /// (see [`Token::is_keyword`]), ex: a quoted identifier, a constant or an identifier such as `foo` in `foo bar`.
+    pub fn first_keyword(&self) -> Option<Cow<'_, str>> {
+        self.first_significant_token().filter(|token| token.is_keyword())?;
+        self.first_word()
+    }
+
+    // Get the first word of the statement in uppercase if the first significant token is an unquoted identifier or
+    // keyword (ex: `EXEC`, which is not in the keyword table).
+    fn first_word(&self) -> Option<Cow<'_, str>> {
+        match self.first_significant_token()?.value {
+            TokenValue::IdentifierOrKeyword(value) if value.bytes().any(|b| b.is_ascii_lowercase()) => {
+                Some(Cow::Owned(value.to_ascii_uppercase()))
+            }
+            TokenValue::IdentifierOrKeyword(value) => Some(Cow::Borrowed(value)),
+            _ => None,
+        }
+    }
+
+    // Get the tokens of the statement skipping the leading parentheses (ex: the tokens of the innermost fragment for
+    // `((SELECT 1)) UNION ...`).
+    fn leading_tokens(&self) -> &Tokens<'_> {
+        let mut tokens = &self.tokens;
+        loop {
+            let mut significant = tokens.iter().filter(|t| is_significant(t));
+            match (significant.next(), significant.next().and_then(|t| t.children())) {
+                (Some(open), Some(children)) if open.value.as_ref() == "(" => tokens = children,
+                _ => return tokens,
+            }
+        }
+    }
+
+    // Get the unquoted words (identifiers or keywords) found at the top level of the statement, skipping the leading
+    // parentheses (ex: `SELECT` and `UNION` for `((SELECT 1)) UNION ...`).
+    fn leading_words(&self) -> Vec<&str> {
+        self.leading_tokens()
+            .iter()
+            .filter_map(|token| match token.value {
+                TokenValue::IdentifierOrKeyword(value) => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns whether executing the statement may modify the database (data, schema or privileges) or write files.
+    ///
+    /// The following statements have side effects:
+    /// - INSERT, UPDATE, DELETE and MERGE, including in a CTE (ex: `WITH d AS (DELETE ...) SELECT * FROM d`)
+    /// - DDL and DCL statements (see [`Statement::is_ddl`] and [`Statement::is_dcl`])
+    /// - SELECT ... INTO a table (`SELECT * INTO t FROM u`) or a file (MySQL: `INTO OUTFILE`, `INTO DUMPFILE`), but not
+    ///   into variables (`INTO @var`)
+    /// - EXPLAIN ANALYZE of one of the above, which executes the explained statement
+    /// - CALL, EXECUTE and the statements which are not recognized (ex: `VACUUM`, `COPY`)
+    ///
+    /// Queries, transaction control, `SET` and `USE` statements have no side effects, nor have empty statements.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("EXPLAIN ANALYZE DELETE FROM t").next().unwrap();
+    /// assert!(stmt.is_query());
+    /// assert!(stmt.has_side_effects());
+    /// assert!(!loose_sqlparse("EXPLAIN DELETE FROM t").next().unwrap().has_side_effects());
+    /// ```
+    pub fn has_side_effects(&self) -> bool {
+        let tokens = self.leading_tokens();
+        let words: Vec<String> = self.leading_words().iter().map(|word| word.to_ascii_uppercase()).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let start = match words.first() {
+            None => return false,
+            // EXPLAIN only executes the explained statement with the ANALYZE option.
+            Some(&"EXPLAIN") => match words.iter().position(|w| {
+                matches!(
+                    *w,
+                    "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "WITH" | "VALUES" | "TABLE" | "CREATE"
+                )
+            }) {
+                Some(index) if is_explain_analyze(tokens, &words[..index]) => index,
+                _ => return false,
+            },
+            Some(_) => 0,
+        };
+        match StatementType::of(&words[start..]) {
+            StatementType::Select | StatementType::Values => {
+                tokens.iter().filter_map(|t| t.children()).any(is_data_modifying)
+                    || select_into_target(tokens).is_some_and(|target| {
+                        !target.is_variable() && !target.is_parameter_marker() && !target.is_placeholder()
+                    })
+            }
+            StatementType::Show
+            | StatementType::Explain
+            | StatementType::Describe
+            | StatementType::Begin
+            | StatementType::Commit
+            | StatementType::Rollback
+            | StatementType::Savepoint
+            | StatementType::Release
+            | StatementType::Set
+            | StatementType::Use => false,
+            StatementType::Unknown => !matches!(words[start], "TABLE" | "FETCH" | "LIST" | "PRAGMA"),
+            _ => true,
+        }
+    }
+
+    // Check if the statement is a JDBC call escape sequence (`{call ...}`, `{? = call ...}`).
+    fn is_call_escape(&self) -> bool {
+        let mut tokens = self.tokens.iter().filter(|t| !t.is_comment() && !t.is_hint());
+        tokens.next().is_some_and(|t| t.value.as_ref() == "{")
+            && tokens.next().and_then(|t| t.children()).is_some_and(|children| {
+                children.iter().any(|t| t.is_identifier_or_keyword() && t.value.as_ref().eq_ignore_ascii_case("CALL"))
+            })
+    }
+}
+
+// Get the range of the lines covered by a span.
+//
+// An end position with a column 0 follows a line break (ex: an unterminated string ending with a line break), its line
+// is not covered.
+pub(crate) fn line_range(start: &Position, end: &Position) -> RangeInclusive<usize> {
+    let end_line = if end.column == 0 { end.line - 1 } else { end.line };
+    start.line..=end_line.max(start.line)
+}
+
+// Check if a token is significant (i.e. not a comment, an optimizer hint or a statement delimiter).
+fn is_significant(token: &Token) -> bool {
+    !token.is_comment() && !token.is_hint() && !token.is_statement_delimiter()
+}
+
+// Check if a non-reserved keyword is used as an identifier (ex: a column named `name` or `returning`), from the
+// significant tokens preceding and following it.
+fn is_identifier_position(previous: Option<&Token>, next: Option<&Token>) -> bool {
+    // A function call (`REPLACE(a, 'b', 'c')`) or a typed literal (`DATE '2024-01-01'`).
+    if next.is_some_and(|t| t.is_parenthesis() || t.value.as_ref().starts_with('\'')) {
+        return false;
+    }
+    // A qualified name, a list or an expression (`t.name`, `name, x`, `returning = 1`), `*` is a wildcard.
+    let is_separator = |t: &Token| t.is_dot() || t.is_comma() || (t.is_operator() && t.value.as_ref() != "*");
+    previous.is_some_and(is_separator)
+        || next.is_some_and(is_separator)
+        || previous.is_some_and(|t| {
+            t.is_reserved_keyword()
+                && ["SELECT", "DISTINCT", "FROM", "JOIN", "WHERE", "BY", "INTO", "UPDATE", "HAVING", "AS"]
+                    .iter()
+                    .any(|k| t.value.as_ref().eq_ignore_ascii_case(k))
+        })
+}
+
+// Check if a fragment is a data-modifying statement (ex: the body of the CTE `WITH d AS (DELETE ...) ...`).
+fn is_data_modifying(tokens: &Tokens) -> bool {
+    tokens.iter().find(|t| is_significant(t)).is_some_and(|t| {
+        t.is_identifier_or_keyword()
+            && ["INSERT", "UPDATE", "DELETE", "MERGE"].iter().any(|k| t.value.as_ref().eq_ignore_ascii_case(k))
+    })
+}
+
+// Get the token following the `INTO` keyword of a `SELECT ... INTO` statement (ex: the table name, `OUTFILE` or the
+// `@var` variable).
+fn select_into_target<'t, 's>(tokens: &'t Tokens<'s>) -> Option<&'t Token<'s>> {
+    let mut tokens = tokens.iter().filter(|t| is_significant(t));
+    tokens.find(|t| t.is_identifier_or_keyword() && t.value.as_ref().eq_ignore_ascii_case("INTO"))?;
+    tokens.next()
+}
+
+// Check if an EXPLAIN statement is executing the explained statement (PostgreSQL, MySQL: `EXPLAIN ANALYZE ...`,
+// PostgreSQL: `EXPLAIN (ANALYZE, BUFFERS) ...`), the `EXPLAIN` options are the words preceding the statement.
+fn is_explain_analyze(tokens: &Tokens, options: &[&str]) -> bool {
+    let is_analyze = |word: &str| word.eq_ignore_ascii_case("ANALYZE") || word.eq_ignore_ascii_case("ANALYSE");
+    if options.iter().any(|word| is_analyze(word)) {
+        return true;
+    }
+    let mut tokens = tokens.iter().filter(|t| is_significant(t)).skip(1);
+    match (tokens.next(), tokens.next().and_then(|t| t.children())) {
+        (Some(open), Some(children)) if open.value.as_ref() == "(" => {
+            let children: Vec<_> = children.iter().filter(|t| is_significant(t)).collect();
+            children.iter().enumerate().any(|(index, t)| {
+                t.is_identifier_or_keyword()
+                    && is_analyze(t.value.as_ref())
+                    && !children.get(index + 1).is_some_and(|next| {
+                        ["FALSE", "OFF", "0"].iter().any(|value| next.value.as_ref().eq_ignore_ascii_case(value))
+                    })
+            })
+        }
+        _ => false,
+    }
+}
+
+// Check if a bracket opening a fragment is not closed (the fragment is not followed by the closing bracket), including
+// the nested fragments.
+fn has_unclosed_bracket(tokens: &Tokens) -> bool {
+    tokens.iter().enumerate().any(|(index, token)| {
+        token.children().is_some_and(|children| {
+            has_unclosed_bracket(children)
+                || (index > 0
+                    && tokens[index - 1].is_punctuation()
+                    && !tokens.get(index + 1).is_some_and(|token| token.is_punctuation()))
+        })
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::loose_sqlparse;
+    use crate::{loose_sqlparse, loose_sqlparse_with_options, BindStyle, Dialect, ObjectType, Options, StatementType};
+    use std::borrow::Cow;
 
     #[test]
     fn test_statement_is_empty() {
@@ -98,5 +994,405 @@ mod tests {
         assert!(statements[1].is_empty());
         assert!(statements[2].is_empty());
         assert!(!statements[3].is_empty());
+
+        let statements: Vec<_> = loose_sqlparse("/*!40101 SET NAMES utf8 */;\n/* comment */;").collect();
+        assert!(!statements[0].is_empty());
+        assert!(statements[1].is_empty());
+
+        let statements: Vec<_> = loose_sqlparse("/*+ INDEX(t idx) */;").collect();
+        assert!(statements[0].is_empty());
+    }
+
+    #[test]
+    fn test_statement_type() {
+        let cases = [
+            ("SELECT 1", StatementType::Select),
+            ("select * from t", StatementType::Select),
+            ("/* c */ -- c\n SELECT 1", StatementType::Select),
+            ("/*+ hint */ SELECT 1", StatementType::Select),
+            ("(SELECT 1) UNION SELECT 2", StatementType::Select),
+            ("((select 1)) union all (select 2)", StatementType::Select),
+            ("WITH t AS (SELECT 1) SELECT * FROM t", StatementType::Select),
+            ("WITH RECURSIVE t(n) AS (SELECT 1 UNION SELECT n + 1 FROM t) SELECT * FROM t", StatementType::Select),
+            ("WITH t AS (SELECT 1) INSERT INTO u SELECT * FROM t", StatementType::Insert),
+            ("with t as (delete from q returning *) insert into u select * from t", StatementType::Insert),
+            ("WITH t AS MATERIALIZED (SELECT 1) UPDATE u SET a = 1", StatementType::Update),
+            ("WITH t AS (SELECT 1) DELETE FROM u", StatementType::Delete),
+            ("INSERT INTO t VALUES (1)", StatementType::Insert),
+            ("REPLACE INTO t VALUES (1)", StatementType::Insert),
+            ("UPDATE t SET a = 1", StatementType::Update),
+            ("DELETE FROM t", StatementType::Delete),
+            ("MERGE INTO t USING s ON t.id = s.id WHEN MATCHED THEN DELETE", StatementType::Merge),
+            ("VALUES (1), (2)", StatementType::Values),
+            ("TRUNCATE TABLE t", StatementType::Truncate),
+            ("CREATE TABLE t (a INT)", StatementType::Create(ObjectType::Table)),
+            ("create temporary table t (a int)", StatementType::Create(ObjectType::Table)),
+            ("CREATE OR REPLACE VIEW v AS SELECT 1", StatementType::Create(ObjectType::View)),
+            ("CREATE MATERIALIZED VIEW v AS SELECT 1", StatementType::Create(ObjectType::MaterializedView)),
+            ("CREATE UNIQUE INDEX i ON t (a)", StatementType::Create(ObjectType::Index)),
+            ("CREATE OR ALTER PROCEDURE p AS SELECT 1", StatementType::Create(ObjectType::Procedure)),
+            (
+                "CREATE FUNCTION f() RETURNS int AS $$ SELECT 1 $$ LANGUAGE sql",
+                StatementType::Create(ObjectType::Function),
+            ),
+            ("CREATE SCHEMA s", StatementType::Create(ObjectType::Schema)),
+            ("CREATE DOMAIN d AS int", StatementType::Create(ObjectType::Other)),
+            ("ALTER TABLE t ADD COLUMN b INT", StatementType::Alter(ObjectType::Table)),
+            ("ALTER SEQUENCE s RESTART", StatementType::Alter(ObjectType::Sequence)),
+            ("DROP TABLE IF EXISTS t", StatementType::Drop(ObjectType::Table)),
+            ("DROP INDEX i", StatementType::Drop(ObjectType::Index)),
+            ("drop database d", StatementType::Drop(ObjectType::Database)),
+            ("BEGIN", StatementType::Begin),
+            ("START TRANSACTION", StatementType::Begin),
+            ("COMMIT", StatementType::Commit),
+            ("END", StatementType::Commit),
+            ("ROLLBACK TO SAVEPOINT s", StatementType::Rollback),
+            ("SAVEPOINT s", StatementType::Savepoint),
+            ("RELEASE SAVEPOINT s", StatementType::Release),
+            ("SET search_path = public", StatementType::Set),
+            ("SHOW TABLES", StatementType::Show),
+            ("EXPLAIN SELECT 1", StatementType::Explain),
+            ("DESCRIBE t", StatementType::Describe),
+            ("GRANT SELECT ON t TO u", StatementType::Grant),
+            ("REVOKE SELECT ON t FROM u", StatementType::Revoke),
+            ("USE mydb", StatementType::Use),
+            ("CALL p(1)", StatementType::Call),
+            ("EXEC p 1", StatementType::Execute),
+            ("VACUUM", StatementType::Unknown),
+            ("WITH t AS (SELECT 1)", StatementType::Unknown),
+            ("/* comment only */", StatementType::Unknown),
+            ("'SELECT'", StatementType::Unknown),
+        ];
+        for (sql, expected) in cases {
+            let statement = loose_sqlparse(sql).next().unwrap();
+            assert_eq!(statement.statement_type(), expected, "{sql}");
+        }
+        let options = Options { jdbc_escapes: true, ..Options::default() };
+        let statement = loose_sqlparse_with_options("{? = call f(?)}", options).unwrap().next().unwrap();
+        assert_eq!(statement.statement_type(), StatementType::Call);
+    }
+
+    #[test]
+    fn test_statement_categories() {
+        let cases = [
+            ("CREATE TABLE t (a INT)", "ddl"),
+            ("/* c */ alter table t add b int", "ddl"),
+            ("DROP VIEW v", "ddl"),
+            ("TRUNCATE t", "ddl"),
+            ("COMMENT ON TABLE t IS 'c'", "ddl"),
+            ("RENAME TABLE t TO u", "ddl"),
+            ("SELECT 1", "dml"),
+            ("(SELECT 1) UNION SELECT 2", "dml"),
+            ("WITH t AS (SELECT 1) INSERT INTO u SELECT * FROM t", "dml"),
+            ("UPDATE t SET a = 1", "dml"),
+            ("DELETE FROM t", "dml"),
+            ("MERGE INTO t USING s ON t.a = s.a WHEN MATCHED THEN DELETE", "dml"),
+            ("GRANT ALL ON t TO u", "dcl"),
+            ("REVOKE ALL ON t FROM u", "dcl"),
+            ("BEGIN", "tcl"),
+            ("start transaction", "tcl"),
+            ("COMMIT", "tcl"),
+            ("ROLLBACK", "tcl"),
+            ("SAVEPOINT s", "tcl"),
+            ("RELEASE SAVEPOINT s", "tcl"),
+            ("VACUUM", ""),
+            ("SET a = 1", ""),
+            ("-- comment", ""),
+        ];
+        for (sql, expected) in cases {
+            let statement = loose_sqlparse(sql).next().unwrap();
+            assert_eq!(statement.is_ddl(), expected == "ddl", "{sql}");
+            assert_eq!(statement.is_dml(), expected == "dml", "{sql}");
+            assert_eq!(statement.is_dcl(), expected == "dcl", "{sql}");
+            assert_eq!(statement.is_tcl(), expected == "tcl", "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_statement_first_keyword() {
+        let statements: Vec<_> =
+            loose_sqlparse("/*+ INDEX(t idx) */ select 1; -- c\n/* c */ (( Select 1)) UNION SELECT 2; /* c */; 'a'")
+                .collect();
+        assert_eq!(statements[0].first_significant_token().unwrap().value.as_ref(), "select");
+        assert_eq!(statements[0].first_keyword().as_deref(), Some("SELECT"));
+        assert_eq!(statements[1].first_significant_token().unwrap().start.offset, 46);
+        assert_eq!(statements[1].first_keyword().as_deref(), Some("SELECT"));
+        assert!(statements[2].first_significant_token().is_none());
+        assert!(statements[2].first_keyword().is_none());
+        assert!(statements[3].first_significant_token().unwrap().is_quoted_identifier_or_constant());
+        assert!(statements[3].first_keyword().is_none());
+        assert!(matches!(loose_sqlparse("UPDATE t").next().unwrap().first_keyword(), Some(Cow::Borrowed("UPDATE"))));
+        // Identifiers are not keywords.
+        assert!(loose_sqlparse("foo bar").next().unwrap().first_keyword().is_none());
+        assert!(loose_sqlparse("EXEC p").next().unwrap().first_keyword().is_none());
+        assert_eq!(loose_sqlparse("explain select 1").next().unwrap().first_keyword().as_deref(), Some("EXPLAIN"));
+    }
+
+    #[test]
+    fn test_statement_has_side_effects() {
+        let mysql = Options::for_dialect(Dialect::MySql);
+        let cases = [
+            // (sql, is_query, has_side_effects)
+            ("SELECT 1", true, false),
+            ("SELECT * INTO t FROM u", false, true),
+            ("SELECT a INTO OUTFILE '/tmp/x' FROM t", false, true),
+            ("SELECT a FROM t INTO DUMPFILE '/tmp/x'", false, true),
+            ("SELECT a INTO @a FROM t", false, false),
+            ("SELECT a FROM t INTO @a, @b", false, false),
+            ("SELECT a FROM t WHERE b IN (SELECT c INTO d FROM e)", true, false),
+            ("WITH d AS (DELETE FROM q RETURNING *) SELECT * FROM d", true, true),
+            ("WITH t AS (SELECT 1) SELECT * FROM t", true, false),
+            ("INSERT INTO t VALUES (1)", false, true),
+            ("DELETE FROM t RETURNING *", true, true),
+            ("CREATE TABLE t (a INT)", false, true),
+            ("GRANT SELECT ON t TO u", false, true),
+            ("EXPLAIN SELECT 1", true, false),
+            ("EXPLAIN INSERT INTO t VALUES (1)", true, false),
+            ("EXPLAIN ANALYZE SELECT 1", true, false),
+            ("explain analyze insert into t values (1)", true, true),
+            ("EXPLAIN ANALYZE VERBOSE UPDATE t SET a = 1", true, true),
+            ("EXPLAIN (ANALYZE, BUFFERS) DELETE FROM t", true, true),
+            ("EXPLAIN (ANALYZE false) DELETE FROM t", true, false),
+            ("EXPLAIN (FORMAT JSON) DELETE FROM t", true, false),
+            ("EXPLAIN ANALYZE SELECT * INTO t FROM u", true, true),
+            ("TABLE t", true, false),
+            ("FETCH ALL FROM c", true, false),
+            ("SHOW TABLES", true, false),
+            ("BEGIN", false, false),
+            ("SET a = 1", false, false),
+            ("CALL p()", true, true),
+            ("VACUUM", false, true),
+            ("-- comment", false, false),
+        ];
+        for (sql, is_query, has_side_effects) in cases {
+            let statement = loose_sqlparse_with_options(sql, mysql.clone()).unwrap().next().unwrap();
+            assert_eq!(statement.is_query(), is_query, "{sql}");
+            assert_eq!(statement.has_side_effects(), has_side_effects, "{sql}");
+        }
+
+        // `@a` is a parameter marker when `at_variables` is disabled.
+        assert!(!loose_sqlparse("SELECT a INTO @a FROM t").next().unwrap().has_side_effects());
+        assert!(!loose_sqlparse("SELECT a INTO :a FROM t").next().unwrap().has_side_effects());
+    }
+
+    #[test]
+    fn test_statement_terminator() {
+        let statements: Vec<_> = loose_sqlparse("SELECT 1; SELECT 2").collect();
+        assert_eq!(statements[0].terminator(), Some(";"));
+        assert_eq!(statements[1].terminator(), None);
+    }
+
+    #[test]
+    fn test_statement_parameter_markers() {
+        let statements: Vec<_> =
+            loose_sqlparse("SELECT :a::int FROM t WHERE a = :a AND b IN (:b_1, :2) AND c = 'a :c'").collect();
+        let markers = statements[0].parameter_markers();
+        assert_eq!(markers.iter().map(|t| t.value.as_ref()).collect::<Vec<_>>(), [":a", ":a", ":b_1", ":2"]);
+        assert_eq!(markers[1].start.offset, 32);
+        assert!(loose_sqlparse("SELECT a::int").next().unwrap().parameter_markers().is_empty());
+
+        // The question mark placeholders and their indices.
+        let placeholders = |sql| {
+            let statement = loose_sqlparse(sql).next().unwrap();
+            statement.placeholders().iter().map(|(index, token)| (*index, token.to_string())).collect::<Vec<_>>()
+        };
+        let expected = |pairs: &[(usize, &str)]| pairs.iter().map(|(i, v)| (*i, v.to_string())).collect::<Vec<_>>();
+        assert_eq!(placeholders("SELECT * FROM t WHERE a = ? AND b = ?2"), expected(&[(1, "?"), (2, "?2")]));
+        assert_eq!(placeholders("SELECT ?, f(?, '?'), :a, ?"), expected(&[(1, "?"), (2, "?"), (3, "?")]));
+        assert_eq!(placeholders("SELECT ?5, ?, ?1, ?"), expected(&[(5, "?5"), (6, "?"), (1, "?1"), (7, "?")]));
+        assert_eq!(placeholders("SELECT a ?| b, ?"), expected(&[(1, "?")]));
+        assert!(placeholders("SELECT :a, $1").is_empty());
+    }
+
+    #[test]
+    fn test_statement_contains() {
+        let input = "SELECT 1; -- c\nSELECT 'é\n';\n;";
+        let statements: Vec<_> = loose_sqlparse(input).collect();
+        assert_eq!(statements[0].range(), 0..9);
+        assert_eq!(statements[1].range(), 10..28);
+        assert_eq!(statements[2].range(), 29..30);
+        // (offset, index of the statement containing the offset)
+        let cases = [
+            (0, Some(0)),
+            (8, Some(0)),
+            (9, None), // The whitespace between two statements.
+            (10, Some(1)),
+            (14, Some(1)),
+            (23, Some(1)),
+            (24, Some(1)), // The second byte of `é`.
+            (27, Some(1)),
+            (28, None), // The line feed between two statements.
+            (29, Some(2)),
+            (30, None), // The end of the input.
+        ];
+        for (offset, expected) in cases {
+            assert_eq!(statements.iter().position(|statement| statement.contains_offset(offset)), expected, "{offset}");
+        }
+
+        // Positions.
+        assert!(statements[0].contains_position(1, 1));
+        assert!(statements[0].contains_position(1, 9));
+        assert!(!statements[0].contains_position(1, 10));
+        assert!(statements[1].contains_position(1, 11));
+        assert!(statements[1].contains_position(2, 1));
+        assert!(statements[1].contains_position(3, 2));
+        assert!(!statements[1].contains_position(3, 3));
+        assert!(!statements[1].contains_position(1, 10));
+        assert!(statements[2].contains_position(4, 1));
+
+        // An empty statement (without its delimiter) does not contain anything.
+        let options = Options { include_delimiter_token: false, ..Options::default() };
+        let statement = loose_sqlparse_with_options(";", options).unwrap().next().unwrap();
+        assert_eq!(statement.range(), 0..0);
+        assert!(!statement.contains_offset(0));
+        assert!(!statement.contains_position(1, 1));
+    }
+
+    #[test]
+    fn test_statement_is_query() {
+        assert!(loose_sqlparse("SELECT 1").next().unwrap().is_query());
+        assert!(!loose_sqlparse("SELECT 1 INTO x").next().unwrap().is_query());
+        assert!(!loose_sqlparse("{call my_proc(?)}").next().unwrap().is_query());
+        let options = || Options { jdbc_escapes: true, ..Default::default() };
+        let statements: Vec<_> =
+            loose_sqlparse_with_options("{call my_proc(?)};{? = CALL f};{fn now()}", options()).unwrap().collect();
+        assert!(statements[0].is_query());
+        assert!(statements[1].is_query());
+        assert!(!statements[2].is_query());
+
+        // Identifiers named after keywords don't change the classification.
+        assert!(loose_sqlparse(r#"SELECT "into", count("into") FROM t"#).next().unwrap().is_query());
+        assert!(loose_sqlparse("SELECT a FROM t WHERE b IN (SELECT c INTO d FROM e)").next().unwrap().is_query());
+        assert!(!loose_sqlparse(r#"INSERT INTO t ("returning") VALUES (1)"#).next().unwrap().is_query());
+        assert!(!loose_sqlparse("VACUUM ANALYZE t").next().unwrap().is_query());
+        assert!(!loose_sqlparse("UPDATE t SET returning = 1").next().unwrap().is_query());
+        assert!(!loose_sqlparse("DELETE FROM t WHERE returning > 1").next().unwrap().is_query());
+        assert!(loose_sqlparse("UPDATE t SET a = 1 RETURNING id, name").next().unwrap().is_query());
+
+        // The rules apply to the main statement following the CTEs.
+        let is_query = |sql| loose_sqlparse(sql).next().unwrap().is_query();
+        assert!(is_query("WITH t AS (SELECT 1) SELECT * FROM t"));
+        assert!(is_query("with recursive t(n) as (select 1 union select n + 1 from t) select * from t"));
+        assert!(is_query("WITH t AS (SELECT 1) INSERT INTO u SELECT * FROM t RETURNING id"));
+        assert!(is_query("WITH t AS (SELECT 1) UPDATE u SET a = 1 RETURNING *"));
+        assert!(!is_query("WITH moved AS (DELETE FROM q RETURNING *) INSERT INTO archive SELECT * FROM moved"));
+        assert!(!is_query("WITH t AS (SELECT 1) DELETE FROM u WHERE a IN (SELECT * FROM t)"));
+        assert!(!is_query("WITH t AS (SELECT 1) SELECT * INTO u FROM t"));
+        assert!(!is_query("WITH t AS (SELECT 1)"));
+
+        // TABLE and FETCH.
+        assert!(is_query("TABLE my_table"));
+        assert!(is_query("fetch all from c"));
+        assert!(is_query("FETCH NEXT FROM c"));
+        assert!(!is_query("FETCH NEXT FROM c INTO @a"));
+        assert!(!is_query("MOVE NEXT FROM c"));
+
+        // CALL, EXEC and EXECUTE.
+        let is_query_with =
+            |sql, options: Options| loose_sqlparse_with_options(sql, options).unwrap().next().unwrap().is_query();
+        for sql in ["CALL p(1)", "exec p 1", "EXECUTE stmt(1)"] {
+            assert!(!is_query(sql), "{sql}");
+            assert!(is_query_with(sql, Options { calls_are_queries: true, ..Options::default() }), "{sql}");
+            assert!(is_query_with(sql, Options::for_dialect(Dialect::MySql)), "{sql}");
+            assert!(is_query_with(sql, Options::for_dialect(Dialect::SqlServer)), "{sql}");
+        }
+        let trigger = "CREATE TRIGGER t BEFORE INSERT ON u FOR EACH ROW EXECUTE FUNCTION f()";
+        assert!(!is_query_with(trigger, Options::for_dialect(Dialect::MySql)));
+        // The options in effect when the statement is parsed are used.
+        let options = Options { honor_directive_comments: true, ..Options::default() };
+        let statements: Vec<_> =
+            loose_sqlparse_with_options("CALL p(); -- loose-sqlparse: calls_are_queries=true\nCALL p()", options)
+                .unwrap()
+                .collect();
+        assert!(!statements[0].is_query());
+        assert!(statements[1].is_query());
+
+        // FROM-first syntax.
+        for sql in ["FROM t SELECT a", "from t", "(FROM t) UNION (FROM u)"] {
+            assert!(!is_query(sql), "{sql}");
+            assert!(is_query_with(sql, Options { from_first_queries: true, ..Options::default() }), "{sql}");
+            assert!(is_query_with(sql, Options::for_dialect(Dialect::DuckDb)), "{sql}");
+            assert!(is_query_with(sql, Options::for_dialect(Dialect::ClickHouse)), "{sql}");
+        }
+        assert!(!is_query_with("DELETE FROM t", Options::for_dialect(Dialect::DuckDb)));
+    }
+
+    #[test]
+    fn test_statement_bind_style() {
+        let bind_style = |sql| loose_sqlparse(sql).next().unwrap().bind_style();
+        assert_eq!(bind_style("SELECT 1"), None);
+        assert_eq!(bind_style("SELECT '?' /* ? */ -- ?"), None);
+        assert_eq!(bind_style("SELECT ?, ?2, f(?)"), Some(BindStyle::QuestionMark));
+        assert_eq!(bind_style("SELECT $1::int, $2"), Some(BindStyle::Dollar));
+        assert_eq!(bind_style("SELECT :a, :1"), Some(BindStyle::Colon));
+        assert_eq!(bind_style("SELECT @a"), Some(BindStyle::At));
+        assert_eq!(bind_style("SELECT ? FROM t WHERE a = :a"), Some(BindStyle::Mixed));
+        assert_eq!(bind_style("SELECT $1, (SELECT ?)"), Some(BindStyle::Mixed));
+        let options = Options { pyformat_placeholders: true, template_placeholders: true, ..Default::default() };
+        let statements: Vec<_> =
+            loose_sqlparse_with_options("SELECT %s, %(a)s;SELECT #{a}, ${b}", options).unwrap().collect();
+        assert_eq!(statements[0].bind_style(), Some(BindStyle::Percent));
+        assert_eq!(statements[1].bind_style(), Some(BindStyle::Template));
+    }
+
+    #[test]
+    fn test_statement_substitution_variables() {
+        let options = Options { substitution_variables: true, ..Default::default() };
+        let statements: Vec<_> =
+            loose_sqlparse_with_options("SELECT &&col FROM &owner..t WHERE id IN (&id)", options).unwrap().collect();
+        let variables = statements[0].substitution_variables();
+        assert_eq!(variables.iter().map(|(name, _)| *name).collect::<Vec<_>>(), ["col", "owner", "id"]);
+        assert_eq!(variables[2].1.offset, 41);
+        assert!(loose_sqlparse("SELECT &&col").next().unwrap().substitution_variables().is_empty());
+        let options = Options { hive_substitutions: true, ..Default::default() };
+        let statements: Vec<_> = loose_sqlparse_with_options("SELECT ${hivevar:a}, ${b", options).unwrap().collect();
+        let variables = statements[0].substitution_variables();
+        assert_eq!(variables.iter().map(|(name, _)| *name).collect::<Vec<_>>(), ["hivevar:a", "b"]);
+    }
+
+    #[test]
+    fn test_statement_delimiter_token() {
+        let options = || Options { statement_delimiter: "$$".to_string(), ..Default::default() };
+        let statements: Vec<_> = loose_sqlparse_with_options("SELECT 1$$\n$$SELECT 2", options()).unwrap().collect();
+        assert!(statements[0].is_terminated());
+        let delimiter = statements[0].delimiter_token().unwrap();
+        assert_eq!(delimiter.value.as_ref(), "$$");
+        assert_eq!((delimiter.start.offset, delimiter.end.offset), (8, 10));
+        assert!(statements[1].is_terminated());
+        assert_eq!(statements[1].delimiter_token().unwrap().start.offset, 11);
+        assert!(!statements[2].is_terminated());
+        assert!(statements[2].delimiter_token().is_none());
+
+        let options = Options { include_delimiter_token: false, ..Default::default() };
+        let statements: Vec<_> = loose_sqlparse_with_options(";", options).unwrap().collect();
+        assert!(statements[0].is_terminated());
+        assert!(statements[0].delimiter_token().unwrap().is_statement_delimiter());
+    }
+
+    #[test]
+    fn test_statement_keywords() {
+        let statements: Vec<_> = loose_sqlparse("SELECT N'text' FROM DUAL").collect();
+        assert_eq!(statements[0].keywords(), ["SELECT", "FROM"]);
+        assert_eq!(statements[0].tokens()[1].start.offset, 7);
+        let statements: Vec<_> = loose_sqlparse("SELECT _utf8mb4'text' FROM DUAL").collect();
+        assert_eq!(statements[0].keywords(), ["SELECT", "FROM"]);
+
+        // Identifiers are not keywords, even if they are quoted keywords or named after non-reserved keywords.
+        let statement =
+            loose_sqlparse(r#"select name, "select", `from` FROM users u WHERE (a) in (1)"#).next().unwrap();
+        assert_eq!(statement.keywords(), ["select", "FROM", "WHERE", "in"]);
+        let tokens = statement.keyword_tokens();
+        assert_eq!(tokens.len(), 4);
+        assert_eq!((tokens[1].start.offset, tokens[1].end.offset), (30, 34));
+        let keywords = |sql| loose_sqlparse(sql).next().unwrap().keywords().join(" ");
+        assert_eq!(keywords("SELECT name FROM users"), "SELECT FROM");
+        assert_eq!(keywords("SELECT t.date, name AS comment FROM t ORDER BY date"), "SELECT AS FROM ORDER BY");
+        assert_eq!(keywords("UPDATE t SET returning = 1 WHERE name > 1"), "UPDATE SET WHERE");
+        assert_eq!(keywords("DELETE FROM t RETURNING *"), "DELETE FROM RETURNING");
+        assert_eq!(
+            keywords("CREATE OR REPLACE VIEW v AS SELECT DATE '2024-01-01'"),
+            "CREATE OR REPLACE VIEW AS SELECT DATE"
+        );
+        assert_eq!(keywords("SHOW TABLES"), "SHOW");
     }
 }
@@ -0,0 +1,426 @@
+use crate::tokenizer::{Tokenizer, TokenizerState};
+use crate::{Diagnostic, Options, OptionsError, Position, Statement};
+use std::collections::VecDeque;
+use std::io::{BufRead, Error, ErrorKind};
+
+#[cfg(feature = "serialize")]
+use serde::Serialize;
+
+// The default minimum number of bytes read from the reader before looking for complete statements.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A statement owning its SQL, returned by the [`StreamingParser`].
+///
+/// The positions are absolute, they are the same as the positions of the [`Statement`] returned when parsing the whole
+/// input at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct OwnedStatement {
+    // The SQL of the statement (see `Statement::sql`).
+    sql: String,
+
+    // The start position of the statement.
+    start: Position,
+
+    // The end position of the statement.
+    end: Position,
+
+    // The statement delimiter terminating the statement, if any.
+    #[cfg_attr(feature = "serialize", serde(skip_serializing_if = "Option::is_none"))]
+    terminator: Option<String>,
+
+    // Whether the statement is empty (see `Statement::is_empty`).
+    empty: bool,
+
+    // Whether the statement is the inline data of a `COPY ... FROM STDIN` statement.
+    copy_data: bool,
+
+    // The problems recovered by the parser while capturing the statement.
+    #[cfg_attr(feature = "serialize", serde(skip_serializing_if = "Vec::is_empty"))]
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl OwnedStatement {
+    /// The SQL statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The start position of the statement.
+    pub fn start(&self) -> &Position {
+        &self.start
+    }
+
+    /// The end position of the statement.
+    pub fn end(&self) -> &Position {
+        &self.end
+    }
+
+    /// The statement delimiter terminating the statement (ex: `;`, `\G`), if any.
+    pub fn terminator(&self) -> Option<&str> {
+        self.terminator.as_deref()
+    }
+
+    /// Returns whether the statement is terminated by a statement delimiter.
+    pub fn is_terminated(&self) -> bool {
+        self.terminator.is_some()
+    }
+
+    /// Returns whether the statement is empty (see [`Statement::is_empty`]).
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+
+    /// Returns whether the statement is the inline data of a `COPY ... FROM STDIN` statement (see
+    /// [`Statement::is_copy_data`]).
+    pub fn is_copy_data(&self) -> bool {
+        self.copy_data
+    }
+
+    /// The problems recovered by the parser while capturing the statement (see [`Statement::diagnostics`]).
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    // Create an owned statement from a statement found at the given offset of the whole input.
+    fn new(statement: &Statement, base_offset: usize) -> Self {
+        let shift = |position: &Position| Position { offset: position.offset + base_offset, ..position.clone() };
+        OwnedStatement {
+            sql: statement.sql().to_string(),
+            start: shift(statement.start()),
+            end: shift(statement.end()),
+            terminator: statement.terminator().map(str::to_string),
+            empty: statement.is_empty(),
+            copy_data: statement.is_copy_data(),
+            diagnostics: statement
+                .diagnostics()
+                .iter()
+                .map(|diagnostic| Diagnostic { kind: diagnostic.kind, position: shift(&diagnostic.position) })
+                .collect(),
+        }
+    }
+}
+
+impl From<&Statement<'_>> for OwnedStatement {
+    fn from(statement: &Statement<'_>) -> Self {
+        OwnedStatement::new(statement, 0)
+    }
+}
+
+/// A parser reading the SQL from a [`BufRead`] and returning the statements as soon as they are complete.
+///
+/// Only the statement being read (and the text preceding it on its first line, up to its last non-whitespace
+/// character) is kept in memory, allowing to split inputs too large to be loaded at once (ex: a `pg_dump` file). A statement is returned once the next one is started, or
+/// when the end of the input is reached.
+///
+/// The iterator returns an error if the reader fails or if the input is not valid UTF-8, the parsing can be resumed
+/// after an [`ErrorKind::Interrupted`] error only.
+///
+/// # Examples
+/// ```rust
+/// use loose_sqlparser::{Options, StreamingParser};
+/// let reader = std::io::Cursor::new("SELECT 1;\nSELECT 2;");
+/// let statements: Vec<_> =
+///     StreamingParser::new(reader, Options::default()).unwrap().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(statements[1].sql(), "SELECT 2;");
+/// assert_eq!((statements[1].start().line, statements[1].start().offset), (2, 10));
+/// ```
+pub struct StreamingParser<R: BufRead> {
+    // The reader of the input.
+    reader: R,
+
+    // The text read but not yet returned as statements, starting with the line of the next statement.
+    buffer: String,
+
+    // The bytes read following the buffer which are not a complete UTF-8 character yet.
+    incomplete_char: Vec<u8>,
+
+    // The offset of the buffer in the whole input.
+    base_offset: usize,
+
+    // The state of the tokenizer at the start of the next statement, its offset is relative to the buffer.
+    state: TokenizerState,
+
+    // The statements found but not yet returned.
+    statements: VecDeque<OwnedStatement>,
+
+    // The minimum number of bytes read before looking for complete statements.
+    chunk_size: usize,
+
+    // Whether the end of the input was reached.
+    eof: bool,
+}
+
+impl<R: BufRead> StreamingParser<R> {
+    /// Create a streaming parser reading the SQL from the given reader.
+    ///
+    /// Returns an error if the options are not usable by the parser (see [`Options::validate`]).
+    pub fn new(reader: R, options: Options) -> Result<Self, OptionsError> {
+        options.validate()?;
+        Ok(StreamingParser {
+            reader,
+            buffer: String::new(),
+            incomplete_char: Vec::new(),
+            base_offset: 0,
+            state: TokenizerState::new(options),
+            statements: VecDeque::new(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            eof: false,
+        })
+    }
+
+    /// Set the minimum number of bytes read before looking for complete statements (64 KiB by default).
+    ///
+    /// The parser reads at least as many bytes as already buffered, so a statement larger than the chunk size is not
+    /// parsed again for each chunk.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    // Read the next chunk of the input into the buffer.
+    fn read_chunk(&mut self) -> Result<(), Error> {
+        let target_len = self.buffer.len() + self.chunk_size.max(self.buffer.len());
+        while self.buffer.len() < target_len {
+            let data = self.reader.fill_buf()?;
+            if data.is_empty() {
+                self.eof = true;
+                if !self.incomplete_char.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "stream did not end with a complete UTF-8 character",
+                    ));
+                }
+                break;
+            }
+            let len = data.len();
+            self.incomplete_char.extend_from_slice(data);
+            self.reader.consume(len);
+            let valid_len = match std::str::from_utf8(&self.incomplete_char) {
+                Ok(text) => text.len(),
+                // An incomplete character at the end of the data is completed by the next read.
+                Err(err) if err.error_len().is_none() => err.valid_up_to(),
+                Err(err) => return Err(Error::new(ErrorKind::InvalidData, err)),
+            };
+            self.buffer.push_str(std::str::from_utf8(&self.incomplete_char[..valid_len]).expect("valid UTF-8"));
+            self.incomplete_char.drain(..valid_len);
+        }
+        Ok(())
+    }
+
+    // Find the complete statements of the buffer and remove them from the buffer.
+    //
+    // The last statement of the buffer is only complete if the end of the input is reached, otherwise it's kept in the
+    // buffer to be parsed again with the next chunk.
+    fn split_statements(&mut self) {
+        let mut tokenizer = Tokenizer::resume(&self.buffer, self.state.clone());
+        // The last statement found and the state of the tokenizer before it.
+        let mut last_statement: Option<(Statement, TokenizerState)> = None;
+        loop {
+            let state = tokenizer.state();
+            let Some(statement) = tokenizer.next_statement() else {
+                break;
+            };
+            if let Some((previous, _)) = last_statement.replace((statement, state.clone())) {
+                push_statement(&mut self.statements, &previous, &state.options, self.base_offset);
+            }
+        }
+        let next_state = match last_statement {
+            Some((statement, _)) if self.eof => {
+                let state = tokenizer.state();
+                push_statement(&mut self.statements, &statement, &state.options, self.base_offset);
+                state
+            }
+            Some((_, state)) => state,
+            None => tokenizer.state(),
+        };
+        // Some constructs are only recognized at the beginning of a line: the text preceding the next statement on its
+        // line is kept from its last non-whitespace character, or from the line feed if it's only whitespace.
+        let line_start = self.buffer[..next_state.start.offset].rfind('\n').unwrap_or(0);
+        let drain_len = match self.buffer[line_start..next_state.start.offset].rfind(|c: char| !c.is_whitespace()) {
+            Some(index) => line_start + index,
+            None => line_start,
+        };
+        self.buffer.drain(..drain_len);
+        self.base_offset += drain_len;
+        self.state = TokenizerState {
+            start: Position { offset: next_state.start.offset - drain_len, ..next_state.start },
+            ..next_state
+        };
+    }
+}
+
+// Add a complete statement to the statements to be returned, skipping the empty ones if requested by the options
+// active at the end of the statement.
+fn push_statement(
+    statements: &mut VecDeque<OwnedStatement>,
+    statement: &Statement,
+    options: &Options,
+    base_offset: usize,
+) {
+    if !options.skip_empty_statements || !statement.is_empty() {
+        statements.push_back(OwnedStatement::new(statement, base_offset));
+    }
+}
+
+impl<R: BufRead> Iterator for StreamingParser<R> {
+    type Item = Result<OwnedStatement, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(statement) = self.statements.pop_front() {
+                return Some(Ok(statement));
+            }
+            if self.eof {
+                return None;
+            }
+            if let Err(err) = self.read_chunk() {
+                // Only an interrupted read can be retried.
+                self.eof = err.kind() != ErrorKind::Interrupted;
+                return Some(Err(err));
+            }
+            self.split_statements();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{loose_sqlparse_with_options, DiagnosticKind, Dialect};
+    use std::io::{BufReader, Cursor, Read};
+
+    // A reader returning the input one byte at a time.
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.0.split_first() {
+                Some((byte, rest)) if !buf.is_empty() => {
+                    buf[0] = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn parse_stream(input: &str, options: Options, chunk_size: usize) -> Vec<OwnedStatement> {
+        let reader = BufReader::with_capacity(1, ByteReader(input.as_bytes()));
+        let parser = StreamingParser::new(reader, options).unwrap().chunk_size(chunk_size);
+        parser.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn test_streaming_parser() {
+        let inputs = [
+            "SELECT 1; SELECT 2;\nSELECT 'a;b' -- c;\n, \"d;e\"; /* f; */ SELECT (1;",
+            "SELECT 'héllo'; SELECT 2;\r\n\tSELECT naïve, \"日本\" -- コメント\n/* ü */ FROM t;é;\nSELECT (ñ + 1)",
+            "é;ü;SELECT $$ü;$$, $tag$;$ta$tag$, $é, :ñ, 'ü'::text;😀",
+            "SELECT 1 # ü\n;;\n;SELECT 'unterminated ü",
+            "SELECT 1\nGO\nSELECT 2\nGO 2\n/\nSELECT 3\n/\nBEGIN\n  NULL;\nEND;\n/\n",
+            "DELIMITER //\nCREATE PROCEDURE p() BEGIN SELECT 1; END//\nDELIMITER ;\nSELECT 2; SELECT 3\\G",
+            "\\c db\nCOPY t FROM STDIN;\n1\ta;b\n\\.\nSELECT 1;\n.mode csv\nSET TERM ^ ;\nSELECT 1^\nSET TERM ; ^",
+            "-- loose-sqlparse: delimiter=// skip_empty_statements=true\nSELECT 1; SELECT 2//\n//\nSELECT 3//",
+            "CREATE TRIGGER t AFTER INSERT ON a BEGIN\n  DELETE FROM b;\nEND; SELECT CASE WHEN 1 THEN 2 END;",
+        ];
+        let dialects = [
+            Dialect::Generic,
+            Dialect::PostgreSql,
+            Dialect::MySql,
+            Dialect::SqlServer,
+            Dialect::Oracle,
+            Dialect::Sqlite,
+            Dialect::Snowflake,
+            Dialect::BigQuery,
+            Dialect::Informix,
+            Dialect::Db2,
+            Dialect::Firebird,
+            Dialect::DuckDb,
+            Dialect::ClickHouse,
+        ];
+        for (input, dialect) in inputs.iter().flat_map(|input| dialects.iter().map(move |dialect| (input, dialect))) {
+            let options =
+                Options { honor_directive_comments: true, tab_width: Some(4), ..Options::for_dialect(*dialect) };
+            let expected: Vec<_> = loose_sqlparse_with_options(input, options.clone())
+                .unwrap()
+                .map(|s| OwnedStatement::from(&s))
+                .collect();
+            for chunk_size in [1, 2, 3, 5, 8, 13, 1024] {
+                assert_eq!(
+                    parse_stream(input, options.clone(), chunk_size),
+                    expected,
+                    "input: {input:?}, dialect: {dialect:?}, chunk size: {chunk_size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_parser_large_statements() {
+        // Statements larger than the chunk size, the positions are kept global across the chunks.
+        let input =
+            format!("SELECT '{}';\nSELECT 2;\n{}", "x\n".repeat(1000), "INSERT INTO t VALUES (1);\n".repeat(100));
+        let statements = parse_stream(&input, Options::default(), 16);
+        assert_eq!(statements.len(), 102);
+        assert_eq!(statements[0].sql().len(), 2010);
+        assert_eq!((statements[0].end().line, statements[0].end().column), (1001, 2));
+        assert_eq!(
+            (statements[1].start().line, statements[1].start().column, statements[1].start().offset),
+            (1002, 1, 2011)
+        );
+        assert_eq!(statements[101].sql(), "INSERT INTO t VALUES (1);");
+        assert_eq!(statements[101].start().line, 1102);
+        assert_eq!(&input[statements[101].start().offset..statements[101].end().offset], statements[101].sql());
+
+        // The default chunk size.
+        let parser = StreamingParser::new(Cursor::new(input.as_bytes()), Options::default()).unwrap();
+        assert_eq!(parser.map(Result::unwrap).count(), 102);
+    }
+
+    #[test]
+    fn test_streaming_parser_long_line() {
+        // Many statements on a single line, the buffer only keeps the statements not returned yet.
+        let input = format!("{}\n   SELECT 2;", "SELECT 1; ".repeat(100_000));
+        let reader = BufReader::with_capacity(256, Cursor::new(input.as_bytes()));
+        let mut parser = StreamingParser::new(reader, Options::default()).unwrap().chunk_size(1024);
+        let mut statements = Vec::new();
+        while let Some(statement) = parser.next() {
+            assert!(parser.buffer.len() < 4096, "buffer length: {}", parser.buffer.len());
+            statements.push(statement.unwrap());
+        }
+        assert_eq!(statements.len(), 100_001);
+        assert_eq!((statements[99_999].start().column, statements[99_999].start().offset), (999_991, 999_990));
+        assert_eq!(&input[statements[99_999].start().offset..statements[99_999].end().offset], "SELECT 1;");
+        assert_eq!(statements[100_000].sql(), "SELECT 2;");
+        assert_eq!((statements[100_000].start().line, statements[100_000].start().column), (2, 4));
+
+        // Line-based constructs are still recognized after a line drained from the buffer.
+        let input = format!("{}\nGO\nSELECT 2\n  GO\nSELECT 3", "SELECT 1 ".repeat(1000));
+        let statements = parse_stream(&input, Options::for_dialect(Dialect::SqlServer), 16);
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[1].sql(), "SELECT 2\n  GO");
+        let input = format!("{}\nSELECT 2 GO SELECT 3", "SELECT 1 ".repeat(1000));
+        assert_eq!(parse_stream(&input, Options::for_dialect(Dialect::SqlServer), 16).len(), 1);
+    }
+
+    #[test]
+    fn test_streaming_parser_errors() {
+        let parser = StreamingParser::new(Cursor::new(b"SELECT 1;\nSELECT '\xff';"), Options::default()).unwrap();
+        let results: Vec<_> = parser.collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].as_ref().unwrap_err().kind(), ErrorKind::InvalidData);
+
+        let parser = StreamingParser::new(Cursor::new(b"SELECT 1;\xc3"), Options::default()).unwrap();
+        assert_eq!(parser.last().unwrap().unwrap_err().kind(), ErrorKind::InvalidData);
+
+        let options = Options { statement_delimiter: String::new(), ..Default::default() };
+        assert!(StreamingParser::new(Cursor::new(b""), options).is_err());
+
+        let statements = parse_stream("SELECT 1; SELECT 'a", Options::default(), 1);
+        assert_eq!(statements[1].diagnostics()[0].kind, DiagnosticKind::UnterminatedString);
+        assert_eq!(statements[1].diagnostics()[0].position.offset, 17);
+        assert!(parse_stream("", Options::default(), 1).is_empty());
+    }
+}
@@ -1,13 +1,62 @@
-use crate::{Options, Position, Statement};
+use crate::{Diagnostic, DiagnosticKind, Options, Position, Statement};
 use crate::{Token, TokenValue, Tokens};
 
 // The list of all operators supported by the tokenizer.
 // The tokenizer will try to match the longest operator possible, so that list must be sorted by the length descending.
-const OPERATORS: [&str; 24] = [
-    "!~*", "!=", ">=", "<=", "<>", "||", "<<", ">>", "::", "~*", "!~", "+", "-", "*", "/", "=", ">", "<", "!", "%",
-    "~", "&", "|", "^",
+//
+// Note: `#` is only reachable when `Options::hash_comments` is disabled (PostgreSQL), `#>` and `#>>` are taking
+// precedence over `#` comments.
+// Note: `?|` and `?&` are only reachable when not followed by the same character (`?||` is a parameter marker followed
+// by the concatenation operator).
+// Note: `<@` is ignored when `Options::at_variables` is enabled (`a<@b` is `a`, `<`, `@b`).
+// Note: multi-character operators must be listed before their prefixes (maximal munch).
+const OPERATORS: [&str; 37] = [
+    "!~*", "#>>", "->>", "!=", ">=", "<=", "<>", "||", "&&", "<<", ">>", "::", ":=", "=>", "**", "~*", "!~", "#>",
+    "->", "@>", "<@", "?|", "?&", "+", "-", "*", "/", "=", ">", "<", "!", "%", "~", "&", "|", "^", "#",
 ];
 
+// The escaping rules of a quoted identifier or constant, shared with the scanner of the splitter.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum QuoteEscaping {
+    // The quote is escaped by repeating it ('O''Reilly').
+    Doubled,
+
+    // The quote is escaped either by repeating it or by a backslash (E'O\'Reilly'), a backslash escapes any character.
+    DoubledOrBackslash,
+
+    // The quote can only be escaped by a backslash which is kept as is in the value (r'O\'Reilly').
+    Backslash,
+}
+
+// The state of the tokenizer between two statements, allowing to resume the tokenization on another input holding the
+// rest of the text (see `StreamingParser`).
+#[derive(Debug, Clone)]
+pub(crate) struct TokenizerState {
+    // The options, they can be changed by directive comments.
+    pub(crate) options: Options,
+
+    // The active statement delimiter.
+    pub(crate) statement_delimiter: String,
+
+    // Whether the next statement is the inline data of a `COPY ... FROM STDIN` statement.
+    pub(crate) copy_data_pending: bool,
+
+    // The start position of the next statement, the offset is relative to the input of the tokenizer.
+    pub(crate) start: Position,
+}
+
+impl TokenizerState {
+    // The state of the tokenizer at the beginning of the input.
+    pub(crate) fn new(options: Options) -> Self {
+        TokenizerState {
+            statement_delimiter: options.statement_delimiter.clone(),
+            options,
+            copy_data_pending: false,
+            start: Position { line: 1, column: 1, offset: 0 },
+        }
+    }
+}
+
 pub(crate) struct Tokenizer<'s> {
     // The input to be tokenized (the whole SQL to be tokenized).
     input: &'s str,
@@ -29,42 +78,120 @@ pub(crate) struct Tokenizer<'s> {
 
     // The tokenizer options.
     options: Options,
+
+    // The active statement delimiter (initialized from the options, can be changed by a `DELIMITER` command).
+    statement_delimiter: String,
+
+    // The nesting level of the fragment being captured (0 for the top level of a statement).
+    fragment_depth: usize,
+
+    // Whether the fragment being captured is an array subscript (`[ ... ]`).
+    in_subscript: bool,
+
+    // Whether the next statement is the inline data of a `COPY ... FROM STDIN` statement (PostgreSQL).
+    copy_data_pending: bool,
+
+    // The last offset and its column computed when `Options::tab_width` is set, used to avoid scanning the whole line
+    // for each position.
+    tab_aware_column: (usize, usize),
+
+    // The problems recovered while capturing the current statement.
+    diagnostics: Vec<Diagnostic>,
+
+    // Whether the input is split into statements, otherwise the statement delimiter (and the other terminators) are
+    // regular tokens (see `Tokenizer::tokenize`).
+    split_statements: bool,
 }
 
 impl<'s> Iterator for Tokenizer<'s> {
     type Item = Statement<'s>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let statement = self.next_statement()?;
+            if !self.options.skip_empty_statements || !statement.is_empty() {
+                return Some(statement);
+            }
+        }
+    }
+}
+
+impl<'s> Tokenizer<'s> {
+    // Get the next statement from the input, including the empty ones.
+    pub(crate) fn next_statement(&mut self) -> Option<Statement<'s>> {
         if self.next_offset >= self.input.len() {
             return None;
         }
         // The start of the next statement is where the tokenizer is currently positioned.
         let next = &self.input[self.next_offset..];
         let mut input_iter = next.chars();
-        self.get_next_statement(input_iter.by_ref(), &self.options.statement_delimiter.clone())
+        if self.copy_data_pending {
+            self.copy_data_pending = false;
+            return self.get_copy_data(input_iter.by_ref());
+        }
+        self.get_next_statement(input_iter.by_ref(), &self.statement_delimiter.clone())
     }
-}
 
-impl<'s> Tokenizer<'s> {
     pub(crate) fn new(input: &'s str, options: Options) -> Self {
+        Self::resume(input, TokenizerState::new(options))
+    }
+
+    // Create a tokenizer starting at the position and with the state given.
+    //
+    // The input must include the line of the start position (from the line feed preceding it) because some constructs
+    // are only recognized at the beginning of a line.
+    pub(crate) fn resume(input: &'s str, state: TokenizerState) -> Self {
+        let TokenizerState { options, statement_delimiter, copy_data_pending, start } = state;
+        let previous_char_len = input[..start.offset].chars().next_back().map_or(0, char::len_utf8);
         Tokenizer {
             input,
+            statement_delimiter,
+            fragment_depth: 0,
+            in_subscript: false,
+            copy_data_pending,
+            tab_aware_column: (start.offset, start.column),
+            diagnostics: Vec::new(),
+            split_statements: true,
             options,
-            offset: 0,
-            next_offset: 0,
-            line: 1,
-            column: 0,
-            token_start: { Position { line: 1, column: 1, offset: 0 } },
+            offset: start.offset - previous_char_len,
+            next_offset: start.offset,
+            line: start.line,
+            column: start.column - 1,
+            token_start: start,
+        }
+    }
+
+    // Get the state of the tokenizer, it should only be called between two statements.
+    pub(crate) fn state(&self) -> TokenizerState {
+        TokenizerState {
+            options: self.options.clone(),
+            statement_delimiter: self.statement_delimiter.clone(),
+            copy_data_pending: self.copy_data_pending,
+            start: self.position(),
         }
     }
 
+    // Get the position where the next statement starts, it should only be called between two statements.
+    pub(crate) fn position(&self) -> Position {
+        Position { offset: self.next_offset, ..self.token_start.clone() }
+    }
+
+    // Get the input not tokenized yet.
+    pub(crate) fn remaining(&self) -> &'s str {
+        &self.input[self.next_offset..]
+    }
+
+    pub(crate) fn options(&self) -> &Options {
+        &self.options
+    }
+
     // Extract the next character from the given iterator.
     #[inline]
     fn get_next_char(&mut self, input_iter: &mut std::str::Chars) -> Option<char> {
         let next_char = input_iter.next();
-        if next_char.is_some() {
+        if let Some(c) = &next_char {
             self.offset = self.next_offset;
-            self.next_offset += next_char.as_ref().unwrap().len_utf8();
+            self.next_offset += c.len_utf8();
             self.column += 1;
         }
         next_char
@@ -76,6 +203,372 @@ impl<'s> Tokenizer<'s> {
         self.remaining_input().starts_with(delimiter)
     }
 
+    // Check if the input at the current position starts with the statement delimiter.
+    //
+    // The comparison ignores the ASCII case if `delimiter_case_insensitive` is enabled.
+    #[inline]
+    fn check_statement_delimiter(&self, c: char, delimiter: &str) -> bool {
+        if self.options.delimiter_case_insensitive {
+            self.remaining_input().get(..delimiter.len()).is_some_and(|input| input.eq_ignore_ascii_case(delimiter))
+        } else {
+            delimiter.starts_with(c) && self.check_delimiter(delimiter)
+        }
+    }
+
+    // Check if the input at the current position starts with the statement delimiter (or any other terminator enabled
+    // by the options).
+    //
+    // Returns the length (in characters) of the statement delimiter found at the current position, if any.
+    fn match_statement_delimiter(&self, c: char, delimiter: &str) -> Option<usize> {
+        if !self.split_statements {
+            None
+        } else if self.options.go_batch_separator {
+            self.match_go_batch_separator(c)
+        } else if self.is_slash_terminator(c) {
+            Some(1)
+        } else if c == '\\'
+            && self.options.backslash_g_terminators
+            && (self.check_delimiter("\\G") || self.check_delimiter("\\g"))
+        {
+            Some(2)
+        } else if self.check_statement_delimiter(c, delimiter)
+            && self.is_at_word_boundaries(delimiter)
+            && (!self.options.delimiter_requires_own_line || self.is_alone_on_its_line(delimiter.len()))
+        {
+            Some(delimiter.chars().count())
+        } else {
+            None
+        }
+    }
+
+    // Check if the delimiter found at the current position is not part of an identifier.
+    //
+    // A delimiter starting (or ending) with an identifier character (ex: `GO`) must not be preceded (or followed) by an
+    // identifier character, otherwise `CATEGORY_ID` or `GOAL` would be split by the delimiter.
+    fn is_at_word_boundaries(&self, delimiter: &str) -> bool {
+        let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_';
+        let preceding_char = self.input[..self.offset].chars().next_back();
+        let following_char = self.input[self.offset + delimiter.len()..].chars().next();
+        (!delimiter.starts_with(is_identifier_char) || !preceding_char.is_some_and(is_identifier_char))
+            && (!delimiter.ends_with(is_identifier_char) || !following_char.is_some_and(is_identifier_char))
+    }
+
+    // Check if the input at the current position is a `?|`, `?&` or `@>` JSON operator (PostgreSQL) rather than a
+    // parameter marker or a stage reference.
+    //
+    // `?||` and `?&&` are parameter markers followed by the `||` and `&&` operators.
+    // See: https://www.postgresql.org/docs/current/functions-json.html
+    fn is_json_operator(&self, c: char) -> bool {
+        (c == '?'
+            && ((self.check_delimiter("?|") && !self.check_delimiter("?||"))
+                || (self.check_delimiter("?&") && !self.check_delimiter("?&&"))))
+            || (c == '@' && self.check_delimiter("@>"))
+    }
+
+    // Check if the input at the current position is a custom operator of 2 characters or more (PostgreSQL) and return
+    // its length.
+    //
+    // - The operator is the longest run of operator characters not containing `--`, `/*` or the statement delimiter.
+    // - The trailing `+` and `-` are not part of the operator unless it contains one of `~ ! @ # % ^ & | \` ?`.
+    // See: https://www.postgresql.org/docs/current/sql-createoperator.html
+    fn match_greedy_operator(&self, delimiter: &str) -> Option<usize> {
+        if !self.options.greedy_operators {
+            return None;
+        }
+        let input = self.remaining_input();
+        let mut len = 0;
+        for c in input.chars() {
+            let rest = &input[len..];
+            if !"+-*/<>=~!@#%^&|`?".contains(c)
+                || (c == '#' && self.options.hash_comments)
+                || rest.starts_with("--")
+                || rest.starts_with("/*")
+                || rest.starts_with(delimiter)
+            {
+                break;
+            }
+            len += 1;
+        }
+        let operator = &input[..len];
+        if !operator.contains(['~', '!', '@', '#', '%', '^', '&', '|', '`', '?']) {
+            len = operator.trim_end_matches(['+', '-']).len();
+        }
+        if len > 1 {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    // Check if the input at the current position is a `%s` or `%(name)s` placeholder (Python DB-API) and return its
+    // length.
+    // See: https://peps.python.org/pep-0249/#paramstyle
+    fn match_pyformat_placeholder(&self, c: char) -> Option<usize> {
+        if c != '%' || !self.options.pyformat_placeholders {
+            return None;
+        }
+        let input = self.remaining_input();
+        let len = if input.starts_with("%s") {
+            2
+        } else if let Some(name) = input.strip_prefix("%(").and_then(|rest| rest.split_once(")s")).map(|(name, _)| name)
+        {
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return None;
+            }
+            name.len() + 4
+        } else {
+            return None;
+        };
+        // `%s` must not be the beginning of a word (`10%step`).
+        match input[len..].chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '_' => None,
+            _ => Some(len),
+        }
+    }
+
+    // Check if the input at the current position is a `GO` batch separator (SQL Server).
+    //
+    // `GO` is case-insensitive and must be the only word on its line, optionally followed by a repeat count (`GO 5`).
+    // See: https://learn.microsoft.com/en-us/sql/t-sql/language-elements/sql-server-utilities-statements-go
+    fn match_go_batch_separator(&self, c: char) -> Option<usize> {
+        if c != 'G' && c != 'g' {
+            return None;
+        }
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        if !self.input[line_start..self.offset].trim().is_empty() {
+            return None;
+        }
+        let remaining_input = self.remaining_input();
+        if !remaining_input.get(..2).is_some_and(|go| go.eq_ignore_ascii_case("GO")) {
+            return None;
+        }
+        let line = &remaining_input[2..remaining_input.find('\n').unwrap_or(remaining_input.len())];
+        if !line.is_empty() && !line.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let count = line.trim();
+        if !count.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        Some(2 + line.trim_end().chars().count())
+    }
+
+    // Check if the input at the current position is a `DELIMITER` command (MySQL).
+    //
+    // The command must be at the beginning of a line and is followed by the new statement delimiter.
+    // Returns the offset and the value of the new statement delimiter.
+    // See: https://dev.mysql.com/doc/refman/8.4/en/stored-programs-defining.html
+    fn match_delimiter_command(&self, c: char) -> Option<(usize, &'s str)> {
+        if c != 'D' && c != 'd' {
+            return None;
+        }
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        if !self.input[line_start..self.offset].trim().is_empty() {
+            return None;
+        }
+        let line_end = self.remaining_input().find('\n').map_or(self.input.len(), |i| self.offset + i);
+        let line = &self.input[self.offset..line_end];
+        if !line.get(..9).is_some_and(|command| command.eq_ignore_ascii_case("DELIMITER"))
+            || !line[9..].starts_with(char::is_whitespace)
+        {
+            return None;
+        }
+        let delimiter = line[9..].split_whitespace().next()?;
+        let delimiter_offset = self.offset + 9 + line[9..].find(delimiter).expect("the delimiter must be in the line");
+        Some((delimiter_offset, &self.input[delimiter_offset..delimiter_offset + delimiter.len()]))
+    }
+
+    // Check if the input at the current position is a `--#SET TERMINATOR` directive (Db2 CLP).
+    //
+    // Returns the value of the new statement delimiter.
+    // See: https://www.ibm.com/docs/en/db2/11.5?topic=clp-command-line-processor-features
+    fn match_terminator_directive(&self, c: char) -> Option<&'s str> {
+        if c != '-' {
+            return None;
+        }
+        let remaining_input = &self.input[self.offset..];
+        let line = &remaining_input[..remaining_input.find('\n').unwrap_or(remaining_input.len())];
+        let mut words = line.strip_prefix("--#")?.split_whitespace();
+        if !words.next()?.eq_ignore_ascii_case("SET") || !words.next()?.eq_ignore_ascii_case("TERMINATOR") {
+            return None;
+        }
+        words.next()
+    }
+
+    // Check if the input at the current position is a `SET TERM` statement (Firebird isql).
+    //
+    // The statement must be on a single line and terminated by the active statement delimiter: `SET TERM ^ ;` or
+    // `SET TERM ^;`.
+    // Returns the value of the new statement delimiter.
+    // See: https://firebirdsql.org/file/documentation/html/en/firebirddocs/isql/firebird-isql.html#isql-set-term
+    fn match_set_term(&self, c: char) -> Option<&'s str> {
+        if c != 'S' && c != 's' {
+            return None;
+        }
+        let line_end = self.remaining_input().find('\n').map_or(self.input.len(), |i| self.offset + i);
+        let line = &self.input[self.offset..line_end];
+        if !line.get(..3).is_some_and(|set| set.eq_ignore_ascii_case("SET"))
+            || !line[3..].starts_with(char::is_whitespace)
+        {
+            return None;
+        }
+        let term = line[3..].trim_start();
+        if !term.get(..4).is_some_and(|term| term.eq_ignore_ascii_case("TERM"))
+            || !term[4..].starts_with(char::is_whitespace)
+        {
+            return None;
+        }
+        let delimiter = term[4..].split_whitespace().next()?;
+        let delimiter_offset = term[4..].find(delimiter).expect("the delimiter must be in the line") + 4;
+        if term[delimiter_offset + delimiter.len()..].trim_start().starts_with(self.statement_delimiter.as_str()) {
+            Some(delimiter)
+        } else {
+            // The active statement delimiter is glued to the new one (`SET TERM ^;`).
+            delimiter.strip_suffix(self.statement_delimiter.as_str()).filter(|delimiter| !delimiter.is_empty())
+        }
+    }
+
+    // Capture a `SET TERM` statement (Firebird isql) and change the active statement delimiter.
+    //
+    // The tokenizer is expected to be positioned on the first character of the statement.
+    //
+    // The splitter does not scan `SET TERM`, the tokenizer is used instead (see `Scanner::supports`).
+    fn capture_set_term(&mut self, input_iter: &mut std::str::Chars, delimiter: &'s str, tokens: &mut Tokens<'s>) {
+        let active_delimiter_len = self.statement_delimiter.chars().count();
+        self.forward_iter(input_iter, "SET".len() - 1);
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::IdentifierOrKeyword);
+        self.skip_whitespace(input_iter, tokens);
+        self.forward_iter(input_iter, "TERM".len());
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::IdentifierOrKeyword);
+        self.skip_whitespace(input_iter, tokens);
+        self.forward_iter(input_iter, delimiter.chars().count());
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+        self.skip_whitespace(input_iter, tokens);
+        self.forward_iter(input_iter, active_delimiter_len);
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::StatementDelimiter);
+        self.statement_delimiter = delimiter.to_string();
+    }
+
+    // Skip the whitespace following the current position (without crossing the end of the line).
+    fn skip_whitespace(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        while let Some(c) = self.input[self.next_offset..].chars().next().filter(|c| c.is_whitespace() && *c != '\n') {
+            if self.is_other_line_break(c, &self.input[self.next_offset + c.len_utf8()..]) {
+                break;
+            }
+            self.get_next_char(input_iter);
+            self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
+        }
+    }
+
+    // Capture a `DELIMITER` command (MySQL) as a statement and change the active statement delimiter.
+    //
+    // The tokenizer is expected to be positioned on the first character of the command.
+    //
+    // The splitter does not scan the `DELIMITER` command, the tokenizer is used instead (see `Scanner::supports`).
+    fn capture_delimiter_command(
+        &mut self,
+        input_iter: &mut std::str::Chars,
+        delimiter_offset: usize,
+        delimiter: &'s str,
+        tokens: &mut Tokens<'s>,
+    ) {
+        self.forward_iter(input_iter, "DELIMITER".len() - 1);
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::IdentifierOrKeyword);
+        while self.next_offset < delimiter_offset {
+            self.get_next_char(input_iter);
+            self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
+        }
+        self.forward_iter(input_iter, delimiter.chars().count());
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+        self.statement_delimiter = delimiter.to_string();
+    }
+
+    // Check if the input at the current position is a `/` terminator (Oracle SQL*Plus).
+    //
+    // The `/` must be the only non-whitespace character on its line, otherwise it's a division operator.
+    // See: https://docs.oracle.com/en/database/oracle/oracle-database/23/sqpug/SLASH.html
+    fn is_slash_terminator(&self, c: char) -> bool {
+        if c != '/' || !self.options.slash_terminator {
+            return false;
+        }
+        self.is_alone_on_its_line(1)
+    }
+
+    // Check if the `len` bytes at the current position are the only non-whitespace characters of their line.
+    fn is_alone_on_its_line(&self, len: usize) -> bool {
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.remaining_input().find('\n').map_or(self.input.len(), |i| self.offset + i);
+        self.input[line_start..self.offset].trim().is_empty()
+            && self.input[self.offset + len..line_end.max(self.offset + len)].trim().is_empty()
+    }
+
+    // Check if the input at the current position is a meta-command (psql).
+    //
+    // The `\` must be the first non-whitespace character of its line at the top level of the statement.
+    // See: https://www.postgresql.org/docs/current/app-psql.html#APP-PSQL-META-COMMANDS
+    fn is_meta_command(&self, c: char) -> bool {
+        if c != '\\' || !self.options.psql_meta_commands || self.fragment_depth > 0 {
+            return false;
+        }
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        self.input[line_start..self.offset].trim().is_empty()
+    }
+
+    // Check if the input at the current position is a dot-command (SQLite CLI).
+    //
+    // The `.` must be the first non-whitespace character of its line at the start of the statement and be followed by
+    // a letter, so a decimal constant (`.5`) is not a dot-command.
+    // See: https://sqlite.org/cli.html#special_commands_to_sqlite3_dot_commands_
+    fn is_dot_command(&self, c: char, tokens: &Tokens<'s>) -> bool {
+        if c != '.' || !self.options.sqlite_dot_commands || self.fragment_depth > 0 {
+            return false;
+        }
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        self.input[line_start..self.offset].trim().is_empty()
+            && tokens.iter().all(|token| token.is_comment())
+            && self.remaining_input()[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+    }
+
+    // Check if the input at the current position is a command (SnowSQL: `!source file.sql`).
+    //
+    // The `!` must be the first non-whitespace character of its line at the start of the statement and be followed by
+    // a letter, so an operator (`!=`) is not a command.
+    // See: https://docs.snowflake.com/en/user-guide/snowsql-use#using-snowsql-commands
+    fn is_bang_command(&self, c: char, tokens: &Tokens<'s>) -> bool {
+        if c != '!' || !self.options.snowsql_bang_commands || self.fragment_depth > 0 {
+            return false;
+        }
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        self.input[line_start..self.offset].trim().is_empty()
+            && tokens.iter().all(|token| token.is_comment())
+            && self.remaining_input()[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+    }
+
+    // Check if the input at the current position is a script include directive (Oracle SQL*Plus: `@file.sql` or
+    // `@@file.sql`).
+    //
+    // The `@` must be the first non-whitespace character of its line at the start of the statement.
+    // See: https://docs.oracle.com/en/database/oracle/oracle-database/23/sqpug/AT-sign.html
+    fn is_script_include(&self, c: char, tokens: &Tokens<'s>) -> bool {
+        if c != '@' || !self.options.sqlplus_script_includes || self.fragment_depth > 0 {
+            return false;
+        }
+        let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        self.input[line_start..self.offset].trim().is_empty() && tokens.iter().all(|token| token.is_comment())
+    }
+
+    // Capture a client command (psql meta-command, SQLite dot-command, SQL*Plus script include directive, SnowSQL
+    // command) until the end of its line.
+    //
+    // The tokenizer is expected to be positioned on the first character of the command.
+    //
+    // The splitter does not scan the client commands, the tokenizer is used instead (see `Scanner::supports`).
+    fn capture_client_command(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        let line = &self.remaining_input()[..self.remaining_input().find('\n').unwrap_or(self.remaining_input().len())];
+        self.forward_iter(input_iter, line.trim_end().chars().count() - 1);
+        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::ClientCommand);
+    }
+
     // Move an iterator n characters forward.
     #[inline]
     fn forward_iter(&mut self, input_iter: &mut std::str::Chars, n: usize) {
@@ -91,15 +584,28 @@ impl<'s> Tokenizer<'s> {
         &self.input[self.offset..]
     }
 
-    // Handle the CRLF (Carriage Return + Line Feed) sequence.
+    // Check if the current character is a line break other than `\n`: a `\r` not followed by `\n` (classic Mac OS), or
+    // a Unicode line break (NEL, LS, PS) when `Options::unicode_line_breaks` is enabled.
+    //
+    // These line breaks are only changing the positions of the tokens, not the way the input is tokenized.
+    #[inline]
+    fn is_other_line_break(&self, c: char, following: &str) -> bool {
+        match c {
+            '\r' => !following.starts_with('\n'),
+            '\u{0085}' | '\u{2028}' | '\u{2029}' => self.options.unicode_line_breaks,
+            _ => false,
+        }
+    }
+
+    // Handle the line breaks and the CRLF (Carriage Return + Line Feed) sequence.
     #[inline]
     fn process_newline(&mut self, c: char) -> bool {
-        if c == '\n' {
+        if c == '\n' || self.is_other_line_break(c, &self.input[self.next_offset..]) {
             //
             // New Line.
             //
             self.line += 1;
-            self.column = 1;
+            self.column = 0;
         } else if c == '\r' {
             //
             // Carriage Return (ignored).
@@ -117,7 +623,9 @@ impl<'s> Tokenizer<'s> {
     // (`self.offset` of the tokenizer.
     #[inline]
     fn column_from_offset(&mut self, offset: usize) -> usize {
-        if offset == self.offset {
+        if let Some(tab_width) = self.options.tab_width {
+            self.tab_aware_column_from_offset(offset, tab_width)
+        } else if offset == self.offset {
             self.column
         } else {
             // Because strings can contain multi-byte characters, we need to count the number of characters between the
@@ -131,6 +639,38 @@ impl<'s> Tokenizer<'s> {
         }
     }
 
+    // Get the column number from an offset when tabs are advancing the column to the next tab stop.
+    //
+    // The column is computed from the start of the line of the `offset` (or from the last computed column if on the
+    // same line), a carriage return does not advance the column.
+    fn tab_aware_column_from_offset(&mut self, offset: usize, tab_width: usize) -> usize {
+        let (mut from, mut column) = self.tab_aware_column;
+        if from > offset {
+            from = self.input[..offset]
+                .char_indices()
+                .rev()
+                .find(|(i, c)| *c == '\n' || self.is_other_line_break(*c, &self.input[i + c.len_utf8()..]))
+                .map_or(0, |(i, c)| i + c.len_utf8());
+            column = 1;
+        }
+        for (i, c) in self.input[from..offset].char_indices() {
+            match c {
+                '\n' => column = 1,
+                c if self.is_other_line_break(c, &self.input[from + i + c.len_utf8()..]) => column = 1,
+                '\r' => {}
+                '\t' if tab_width > 0 => column += tab_width - (column - 1) % tab_width,
+                _ => column += 1,
+            }
+        }
+        self.tab_aware_column = (offset, column);
+        column
+    }
+
+    // Record a problem of the construct starting at the start position of the current token.
+    fn add_diagnostic(&mut self, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic { kind, position: self.token_start.clone() });
+    }
+
     // Add a token to a list of tokens.
     //
     // The `end_offset` designated the position of the character immediately following the token. Which means the token
@@ -181,25 +721,62 @@ impl<'s> Tokenizer<'s> {
     // Can be either `--` or `#`.
     // The `--` single-line comment is the most universally supported across different SQL dialects.
     // The `#`` single-line comment is less common and is primarily used in MySQL.
+    // The `--+` single-line comment is an optimizer hint (Oracle).
+    //
+    // Any change must be mirrored in `Scanner::skip_single_line_comment` (splitter).
     fn capture_single_line_comment(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        let value_constructor: fn(&'s str) -> TokenValue<'s> =
+            if self.remaining_input().starts_with("--+") { TokenValue::Hint } else { TokenValue::Comment };
         while let Some(c) = self.get_next_char(input_iter) {
             if c == '\n' {
                 // We found the end of the comment.
-                self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Comment);
+                self.capture_token(tokens, self.offset, self.next_offset, value_constructor);
                 self.line += 1;
-                self.column = 1;
+                self.column = 0;
+                self.token_start.line = self.line;
+                self.token_start.column = 1;
                 return;
             }
+            // The other line breaks are part of the comment.
+            self.process_newline(c);
         }
         // We reached the end of the input without finding the end of the comment.
         // Capture what we have so far...
-        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Comment);
+        self.capture_token(tokens, self.next_offset, self.next_offset, value_constructor);
+    }
+
+    // Apply the directive comment captured as the last token, if any (see `Options::honor_directive_comments`).
+    //
+    // A new statement delimiter becomes the active statement delimiter starting from the next statement.
+    fn apply_directive_comment(&mut self, tokens: &Tokens<'s>) {
+        if let Some(comment) = tokens.last().filter(|token| token.is_comment()) {
+            let statement_delimiter = self.options.statement_delimiter.clone();
+            self.options.apply_directive_comment(comment.value.as_ref());
+            if self.options.statement_delimiter != statement_delimiter {
+                self.statement_delimiter = self.options.statement_delimiter.clone();
+            }
+        }
     }
 
     // The /* ... */ multi-line comment is widely supported supported across different SQL dialects.
     // Despite most SQL dialects not supporting nested comments, PostgreSQL does...
     // See: https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-SYNTAX-COMMENTS
+    //
+    // MySQL versioned comments (/*! ... */ or /*!50613 ... */) are captured as `VersionedComment` because their
+    // content is executed by the server.
+    // See: https://dev.mysql.com/doc/refman/8.4/en/comments.html
+    //
+    // Optimizer hints (/*+ ... */) are captured as `Hint` (Oracle, MySQL).
+    //
+    // Any change must be mirrored in `Scanner::skip_multi_line_comment` (splitter).
     fn capture_multi_line_comment(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        let value_constructor: fn(&'s str) -> TokenValue<'s> = if self.remaining_input().starts_with("/*!") {
+            TokenValue::VersionedComment
+        } else if self.remaining_input().starts_with("/*+") {
+            TokenValue::Hint
+        } else {
+            TokenValue::Comment
+        };
         // The nested level of comments (starts at 1, and decreased by 1 when a `*/` is found).
         let mut nested_level = 1;
         let mut next_char = self.get_next_char(input_iter);
@@ -230,9 +807,49 @@ impl<'s> Tokenizer<'s> {
             }
             next_char = self.get_next_char(input_iter);
         }
+        if next_char.is_none() {
+            // We reached the end of the input without finding the end of the comment.
+            self.add_diagnostic(DiagnosticKind::UnterminatedComment);
+        }
+        self.capture_token(tokens, self.next_offset, self.next_offset, value_constructor);
+    }
+
+    // The { ... } comment is supported by Informix (and found in some legacy scripts), it's only recognized when the
+    // `brace_comments` option is enabled. Nested braces are balanced so `{ a { b } c }` is a single comment.
+    //
+    // The splitter does not scan the brace comments, the tokenizer is used instead (see `Scanner::supports`).
+    fn capture_brace_comment(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) {
+        let mut nested_level = 1;
+        while let Some(c) = self.get_next_char(input_iter) {
+            if c == '{' {
+                nested_level += 1;
+            } else if c == '}' {
+                nested_level -= 1;
+                if nested_level == 0 {
+                    // We found the end of the comment.
+                    break;
+                }
+            } else {
+                self.process_newline(c);
+            }
+        }
+        if nested_level > 0 {
+            // We reached the end of the input without finding the end of the comment.
+            self.add_diagnostic(DiagnosticKind::UnterminatedComment);
+        }
         self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Comment);
     }
 
+    // Check if the `--` at the current position starts a single-line comment.
+    //
+    // MySQL requires the `--` to be followed by a whitespace (or the end of the input), otherwise `5--2` is parsed as
+    // `5 - (-2)`.
+    // See: https://dev.mysql.com/doc/refman/8.4/en/comments.html
+    fn is_double_dash_comment(&self) -> bool {
+        !self.options.require_space_after_double_dash
+            || self.remaining_input()[2..].chars().next().is_none_or(|c| c.is_whitespace())
+    }
+
     // Capture a quoted identifier or a string literal.
     //
     // - Identifiers can be delimited by double quotes (ex: "Employee #") or backticks (`) in MySQL if he `ANSI_QUOTES`
@@ -240,21 +857,32 @@ impl<'s> Tokenizer<'s> {
     // - String literals can be delimited by single quotes (ex: 'O''Reilly') or double quotes (ex: "O'Reilly").
     // - The quotes can be escaped by repeating the quote character, e.g., to create an identifier named
     //   'IDENTIFIER "X"', use 'IDENTIFIER ""X""'.
+    // - Depending on the `escaping` rules, a backslash escapes the following character, including the quote character
+    //   (PostgreSQL escape string constants: E'O\'Reilly', BigQuery raw strings: r'O\'Reilly').
     //
     // Because this function has to peek the next character to check for an escaped delimiter, it returns the next
     // character to be processed by the tokenizer.
+    //
+    // Any change must be mirrored in `Scanner::skip_quoted_identifier_or_constant` (splitter).
     fn capture_quoted_identifier_or_constant(
         &mut self,
         input_iter: &mut std::str::Chars,
         quote_char: char,
+        escaping: QuoteEscaping,
         tokens: &mut Tokens<'s>,
     ) -> Option<char> {
         let mut next_char = self.get_next_char(input_iter);
         while let Some(c) = next_char {
-            if c == quote_char {
+            if c == '\\' && escaping != QuoteEscaping::Doubled {
+                // The next character is escaped, whatever it is.
+                next_char = self.get_next_char(input_iter);
+                if let Some(escaped_char) = next_char {
+                    self.process_newline(escaped_char);
+                }
+            } else if c == quote_char {
                 // Quote found, we need to check if it's an escaped quote (repeated quote).
                 next_char = self.get_next_char(input_iter);
-                if next_char.as_ref() != Some(&quote_char) {
+                if next_char.as_ref() != Some(&quote_char) || escaping == QuoteEscaping::Backslash {
                     // We found the end of the quoted token (or the end of the input).
                     // We return the next character to the tokenizer so it can be processed.
                     self.capture_token(
@@ -274,17 +902,24 @@ impl<'s> Tokenizer<'s> {
         }
         // We reached the end of the input without finding the end of the identifier, we still need to capture the last
         // token.
+        // Double quotes and backticks are delimiting identifiers unless they follow a string introducer (b"bytes").
+        let start = &self.input[self.token_start.offset..];
+        self.add_diagnostic(if quote_char == '\'' || !start.starts_with([quote_char, 'U', 'u']) {
+            DiagnosticKind::UnterminatedString
+        } else {
+            DiagnosticKind::UnterminatedQuotedIdentifier
+        });
         self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::QuotedIdentifierOrConstant);
         next_char
     }
 
+    // Any change must be mirrored in `Scanner::scan_statement` (splitter).
     fn capture_fragment(
         &mut self,
         input_iter: &mut std::str::Chars,
         delimiter: &str,
         tokens: &mut Tokens<'s>,
     ) -> Option<char> {
-        let delimiter_start_char = delimiter.chars().next().expect("delimiter must not be empty");
         let mut next_char = self.get_next_char(input_iter);
         while let Some(c) = next_char {
             if c == '\n' {
@@ -296,13 +931,91 @@ impl<'s> Tokenizer<'s> {
                 self.column = 0;
                 self.token_start.line = self.line;
                 self.token_start.column = 1;
+                if self.options.split_on_blank_lines && self.fragment_depth == 0 && !tokens.is_empty() {
+                    // The statement ends with the first blank line following it.
+                    let line_start = self.input[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+                    if self.input[line_start..self.offset].trim().is_empty() {
+                        return None;
+                    }
+                }
+            } else if self.is_other_line_break(c, &self.input[self.next_offset..]) {
+                //
+                // Other line breaks (\r alone, NEL, LS, PS).
+                //
+                self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
+                self.process_newline(c);
+                self.token_start.line = self.line;
+                self.token_start.column = 1;
             } else if c == '\r' {
                 //
                 // Carriage Return (ignored).
                 //
                 self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
                 self.column -= 1;
-            } else if c == delimiter_start_char && self.check_delimiter(delimiter) {
+            } else if self.options.interpret_delimiter_command
+                && self.fragment_depth == 0
+                && tokens.iter().all(|token| token.is_comment())
+                && self.match_delimiter_command(c).is_some()
+            {
+                //
+                // DELIMITER command (MySQL).
+                //
+                // The command is captured as a statement on its own, so we return `None` to end the statement.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                let (delimiter_offset, new_delimiter) = self.match_delimiter_command(c).unwrap();
+                self.capture_delimiter_command(input_iter, delimiter_offset, new_delimiter, tokens);
+                return None;
+            } else if self.options.interpret_terminator_directive
+                && self.fragment_depth == 0
+                && tokens.iter().all(|token| token.is_comment())
+                && self.match_terminator_directive(c).is_some()
+            {
+                //
+                // --#SET TERMINATOR directive (Db2 CLP).
+                //
+                // The directive is captured as a comment-only statement, so we return `None` to end the statement.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                let new_delimiter = self.match_terminator_directive(c).unwrap();
+                self.capture_single_line_comment(input_iter, tokens);
+                self.statement_delimiter = new_delimiter.to_string();
+                return None;
+            } else if self.options.interpret_set_term
+                && self.fragment_depth == 0
+                && tokens.iter().all(|token| token.is_comment())
+                && self.match_set_term(c).is_some()
+            {
+                //
+                // SET TERM statement (Firebird isql).
+                //
+                // The statement is captured up to its delimiter, so we return `None` to end the statement.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                let new_delimiter = self.match_set_term(c).unwrap();
+                self.capture_set_term(input_iter, new_delimiter, tokens);
+                return None;
+            } else if self.is_meta_command(c) {
+                //
+                // Meta-command (psql).
+                //
+                // The meta-command is captured as a statement on its own, so if the statement already contains some
+                // tokens we return the `\` to end the statement before the meta-command.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                if !tokens.iter().all(|token| token.is_comment()) {
+                    return next_char;
+                }
+                self.capture_client_command(input_iter, tokens);
+                return None;
+            } else if self.is_dot_command(c, tokens)
+                || self.is_script_include(c, tokens)
+                || self.is_bang_command(c, tokens)
+            {
+                //
+                // Dot-command (SQLite CLI), script include directive (Oracle SQL*Plus) or command (SnowSQL).
+                //
+                // The command is captured as a statement on its own.
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_client_command(input_iter, tokens);
+                return None;
+            } else if self.match_statement_delimiter(c, delimiter).is_some() {
                 //
                 // Delimiter.
                 //
@@ -316,52 +1029,143 @@ impl<'s> Tokenizer<'s> {
                 // Whitespace (could be \s, \t, \r, \n, etc.).
                 //
                 self.capture_token(tokens, self.offset, self.next_offset, TokenValue::Any);
-            } else if c == '#' || (c == '-' && self.check_delimiter("--")) {
+            } else if let Some(len) = self.match_greedy_operator(delimiter) {
                 //
-                // Single-line comment starting by '#' (MySQL).
-                // Single-line comment starting by '--' (most SQL dialects).
+                // A custom operator (PostgreSQL: <->, @@, ||/).
                 //
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-                self.capture_single_line_comment(input_iter, tokens);
-            } else if c == '/' && self.check_delimiter("/*") {
+                self.capture_token(tokens, self.offset + len, self.offset + len, TokenValue::Operator);
+                self.forward_iter(input_iter, len - 1);
+            } else if ((c == '#' && self.options.template_placeholders)
+                || (c == '$' && (self.options.template_placeholders || self.options.hive_substitutions)))
+                && self.remaining_input()[1..].starts_with('{')
+            {
                 //
-                // Either a multi-line comment '/* ... */' or a division operator.
+                // A template placeholder (MyBatis: #{userId}, ${tableName}).
+                // A substitution variable (Hive: ${hivevar:path}, ${env:HOME}).
                 //
+                // The placeholder ends with the matching closing brace, or the end of the input if unterminated.
+                let value_constructor = if c == '$' && self.options.hive_substitutions {
+                    TokenValue::SubstitutionVariable
+                } else {
+                    TokenValue::ParameterMarker
+                };
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-                self.capture_multi_line_comment(input_iter, tokens);
-            } else if c == '\'' || c == '"' || c == '`' {
-                //
+                self.get_next_char(input_iter);
+                let mut depth = 1;
+                next_char = self.get_next_char(input_iter);
+                while let Some(c) = next_char {
+                    if c == '{' {
+                        depth += 1;
+                    } else if c == '}' {
+                        depth -= 1;
+                        if depth == 0 {
+                            next_char = self.get_next_char(input_iter);
+                            break;
+                        }
+                    } else {
+                        self.process_newline(c);
+                    }
+                    next_char = self.get_next_char(input_iter);
+                }
+                let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                self.capture_token(tokens, end_offset, end_offset, value_constructor);
+                continue;
+            } else if (c == '#' && self.options.hash_comments && !self.check_delimiter("#>"))
+                || (c == '-' && self.check_delimiter("--") && self.is_double_dash_comment())
+            {
+                //
+                // Single-line comment starting by '#' (MySQL).
+                // Single-line comment starting by '--' (most SQL dialects).
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_single_line_comment(input_iter, tokens);
+                if self.options.honor_directive_comments {
+                    self.apply_directive_comment(tokens);
+                }
+            } else if c == '/' && self.check_delimiter("/*") {
+                //
+                // Either a multi-line comment '/* ... */' or a division operator.
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_multi_line_comment(input_iter, tokens);
+            } else if c == '{' && self.options.brace_comments {
+                //
+                // Brace comment '{ ... }' (Informix).
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_brace_comment(input_iter, tokens);
+            } else if c == '\'' || c == '"' || c == '`' {
+                //
                 // Quoted identifier or constant.
                 //
-                if c == '\'' && self.offset > self.token_start.offset {
+                if self.options.triple_quoted_strings && (c == '\'' || c == '"') {
+                    let triple_quote = if c == '\'' { "'''" } else { "\"\"\"" };
+                    if self.check_delimiter(triple_quote) {
+                        // Triple-quoted strings (BigQuery: '''...''', """...""") can span multiple lines and contain
+                        // single quotes without escaping them.
+                        self.forward_iter(input_iter, 2);
+                        next_char = self.capture_delimited_token(
+                            input_iter,
+                            triple_quote,
+                            DiagnosticKind::UnterminatedString,
+                            tokens,
+                            TokenValue::QuotedIdentifierOrConstant,
+                        );
+                        continue;
+                    }
+                }
+                if self.offset > self.token_start.offset {
                     // There is an introducer:
                     // - Escaped string constant (E'hello\\tworld').
                     // - Unicode string constant (N'こんにちは').
-                    // - Bit-String constant (B'1001', X'1FF').
+                    // - Bit-String constant (B'1001', X'1FF') or bytes constant (b'bytes', B"bytes").
                     // - String constant with a character set introducer (_latin1'hello').
+                    // - Raw string constant (r'\d+', R"\d+", rb'\x00').
                     let introducer = &self.input[self.token_start.offset..self.offset];
-                    let first_char = introducer.chars().next().unwrap();
-                    if first_char == 'B' || first_char == 'b' || first_char == 'X' || first_char == 'x' {
-                        // Escaped quotes are not allowed by Bit-String constants.
+                    if introducer == "X" || introducer == "x" {
+                        // Escaped quotes are not allowed by hexadecimal constants.
                         next_char = self.capture_delimited_token(
                             input_iter,
                             &c.to_string(),
+                            DiagnosticKind::UnterminatedString,
                             tokens,
                             TokenValue::QuotedIdentifierOrConstant,
                         );
                         continue;
+                    } else if introducer == "E" || introducer == "e" {
+                        // Escape string constants are allowing backslash escapes (E'O\'Reilly').
+                        next_char = self.capture_quoted_identifier_or_constant(
+                            input_iter,
+                            c,
+                            QuoteEscaping::DoubledOrBackslash,
+                            tokens,
+                        );
+                        continue;
+                    } else if is_raw_string_introducer(introducer) {
+                        // Backslashes are kept as is in raw strings but still prevent the quote from ending the string.
+                        next_char =
+                            self.capture_quoted_identifier_or_constant(input_iter, c, QuoteEscaping::Backslash, tokens);
+                        continue;
                     }
                 }
-                next_char = self.capture_quoted_identifier_or_constant(input_iter, c, tokens);
+                next_char = self.capture_quoted_identifier_or_constant(input_iter, c, QuoteEscaping::Doubled, tokens);
                 continue;
-            } else if (c == 'U' || c == 'u') && self.remaining_input().starts_with("U&\"") {
+            } else if (c == 'U' || c == 'u')
+                && (self.remaining_input()[1..].starts_with("&\"") || self.remaining_input()[1..].starts_with("&'"))
+            {
                 //
-                // Escaped Unicode quoted identifier (PostgreSQL: U&"d\0061t\+000061").
+                // Escaped Unicode quoted identifier (U&"d\0061t\+000061") or string constant (U&'d\0061t\+000061').
                 //
                 // A Unicode escape string constant starts with U& (upper or lower case letter U followed by ampersand)
                 // immediately before the opening quote, without any spaces in between, for example U&"foo".
+                // The escape character can be changed with an optional `UESCAPE 'x'` clause following the constant, it
+                // is captured as separate tokens. Quotes are still escaped by doubling them whatever the escape
+                // character is.
                 self.forward_iter(input_iter, 2);
-                next_char = self.capture_quoted_identifier_or_constant(input_iter, '"', tokens);
+                let quote_char = self.input[self.offset..].chars().next().unwrap();
+                next_char =
+                    self.capture_quoted_identifier_or_constant(input_iter, quote_char, QuoteEscaping::Doubled, tokens);
                 continue;
             } else if c == '$' {
                 //
@@ -370,39 +1174,145 @@ impl<'s> Tokenizer<'s> {
                 // Before starting to identify the dollar-quoted delimiter we need to capture the current token.
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
 
+                next_char = self.get_next_char(input_iter);
+                if next_char.is_some_and(|c| c.is_ascii_digit()) {
+                    // A dollar-quoted tag cannot start with a digit, so this is either a positional parameter ($1) or
+                    // a money constant (T-SQL: $12.34, $1e3).
+                    next_char =
+                        self.capture_numeric_constant(input_iter, "_0123456789.eE+-", tokens, |value: &'s str| {
+                            if value[1..].chars().all(|c| c.is_ascii_digit()) {
+                                TokenValue::ParameterMarker(value)
+                            } else {
+                                TokenValue::NumericConstant(value)
+                            }
+                        });
+                    continue;
+                }
+
                 // A dollar-quoted delimiter consists of a dollar sign ($), an optional “tag” of zero or more
                 // characters and another dollar sign.
                 // - The tag is case-sensitive, so $TAG$...$TAG$ is different from $tag$...$tag$.
                 // - The tag consists of letters (A-Z, a-z), digits (0-9), and underscores (_).
-                next_char = self.get_next_char(input_iter);
                 while next_char.is_some()
                     && (next_char.as_ref().unwrap().is_ascii_alphanumeric() || next_char.as_ref() == Some(&'_'))
                 {
                     next_char = self.get_next_char(input_iter);
                 }
-                if next_char.as_ref() == Some(&'$') {
+                if next_char.as_ref() == Some(&'$') && self.options.dollar_quoted_strings {
                     // We found the end of the dollar-quoted delimiter.
                     let delimiter = &self.input[self.token_start.offset..self.next_offset];
                     next_char = self.capture_delimited_token(
                         input_iter,
                         delimiter,
+                        DiagnosticKind::UnterminatedDollarQuotedString,
                         tokens,
                         TokenValue::QuotedIdentifierOrConstant,
                     );
                 } else {
-                    // We've found a parameter marker (`$1`, `$id`)
+                    // We've found a parameter marker (`$1`, `$id`) or a placeholder (`?`, `?2`)
+                    let value_constructor =
+                        if c == '?' { TokenValue::Placeholder } else { TokenValue::ParameterMarker };
                     self.capture_token(
                         tokens,
                         if next_char.is_some() { self.offset } else { self.next_offset },
-                        self.next_offset,
-                        TokenValue::ParameterMarker,
+                        if next_char.is_some() { self.offset } else { self.next_offset },
+                        value_constructor,
                     );
                 }
                 continue;
-            } else if c == ':' || c == '?' || c == '@' {
+            } else if c == '@' && self.options.stage_references && !self.is_json_operator(c) {
+                //
+                // A stage reference (Snowflake: @my_stage/path/file.csv, @~/file.csv, @%table).
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                next_char = self.get_next_char(input_iter);
+                while let Some(c) = next_char {
+                    if !(c.is_alphanumeric() || "_$./~%-".contains(c))
+                        || self.match_statement_delimiter(c, delimiter).is_some()
+                    {
+                        break;
+                    }
+                    next_char = self.get_next_char(input_iter);
+                }
+                let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                self.capture_token(tokens, end_offset, end_offset, TokenValue::StageReference);
+                continue;
+            } else if c == '&'
+                && self.options.substitution_variables
+                && self.remaining_input().trim_start_matches('&').starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && !self.check_delimiter("&&&")
+            {
+                //
+                // A substitution variable (Oracle SQL*Plus: &name, &&name, &name.suffix).
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                if self.check_delimiter("&&") {
+                    self.get_next_char(input_iter);
+                }
+                next_char = self.get_next_char(input_iter);
+                while next_char.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '#') {
+                    next_char = self.get_next_char(input_iter);
+                }
+                if next_char.as_ref() == Some(&'.') {
+                    // The period terminating the variable name is part of the variable.
+                    next_char = self.get_next_char(input_iter);
+                }
+                let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                self.capture_token(tokens, end_offset, end_offset, TokenValue::SubstitutionVariable);
+                continue;
+            } else if c == '@'
+                && self.options.at_variables
+                && self.remaining_input()[1..].starts_with(|c: char| c.is_alphanumeric() || "_@'\"`".contains(c))
+            {
+                //
+                // A variable (MySQL: @rownum, @'my var', @`my var`, @@session.sql_mode, SQL Server: @id, @@ROWCOUNT).
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                if self.check_delimiter("@@") {
+                    self.get_next_char(input_iter);
+                }
+                next_char = self.get_next_char(input_iter);
+                if let Some(quote_char @ ('\'' | '"' | '`')) = next_char {
+                    // A quoted variable name, the quotes are escaped by repeating them.
+                    next_char = self.get_next_char(input_iter);
+                    while let Some(c) = next_char {
+                        if c == quote_char {
+                            next_char = self.get_next_char(input_iter);
+                            if next_char.as_ref() != Some(&quote_char) {
+                                break;
+                            }
+                        } else {
+                            self.process_newline(c);
+                        }
+                        next_char = self.get_next_char(input_iter);
+                    }
+                } else {
+                    while next_char.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.') {
+                        next_char = self.get_next_char(input_iter);
+                    }
+                }
+                let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                self.capture_token(tokens, end_offset, end_offset, TokenValue::Variable);
+                continue;
+            } else if let Some(len) = self.match_pyformat_placeholder(c) {
+                //
+                // A Python DB-API placeholder (psycopg: %s, %(name)s).
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.forward_iter(input_iter, len - 1);
+                next_char = self.get_next_char(input_iter);
+                let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                self.capture_token(tokens, end_offset, end_offset, TokenValue::ParameterMarker);
+                continue;
+            } else if (c == ':' || c == '?' || c == '@')
+                && !(c == ':' && self.in_subscript)
+                && !self.check_delimiter(":=")
+                && !self.is_json_operator(c)
+            {
                 //
                 // A Parameter Marker
                 //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
                 next_char = self.get_next_char(input_iter);
                 while next_char.is_some()
                     && (next_char.as_ref().unwrap().is_ascii_alphanumeric() || next_char.as_ref() == Some(&'_'))
@@ -413,38 +1323,67 @@ impl<'s> Tokenizer<'s> {
                     // Special case for the PostgreSQL type casting operator `::` (consuming next_char).
                     self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Operator);
                 } else {
-                    // We've found a parameter marker (`$1`, `$id`)
+                    // We've found a parameter marker (`$1`, `$id`) or a placeholder (`?`, `?2`)
+                    let value_constructor =
+                        if c == '?' { TokenValue::Placeholder } else { TokenValue::ParameterMarker };
                     self.capture_token(
                         tokens,
                         if next_char.is_some() { self.offset } else { self.next_offset },
-                        self.next_offset,
-                        TokenValue::ParameterMarker,
+                        if next_char.is_some() { self.offset } else { self.next_offset },
+                        value_constructor,
                     );
                     continue;
                 }
-            } else if c == '(' {
+            } else if c == '(' || (c == '{' && self.options.jdbc_escapes) || (c == '[' && self.options.array_subscripts)
+            {
                 //
                 // Start of a parentheses block.
+                // Start of a JDBC escape sequence (ex: {fn CONCAT(a, b)}, {call my_proc(?)}).
+                // Start of an array subscript (ex: arr[1], arr[2:3]).
                 //
+                let closing_char = match c {
+                    '(' => ')',
+                    '{' => '}',
+                    _ => ']',
+                };
                 // Capture the previous token if any.
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
                 // Capture the parentheses as a token.
-                self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Punctuation);
                 let mut nested_tokens = Tokens::new();
+                let in_subscript = std::mem::replace(&mut self.in_subscript, c == '[');
+                let fragment_start = self.token_start.clone();
+                self.fragment_depth += 1;
                 next_char = self.capture_fragment(input_iter, delimiter, &mut nested_tokens);
-                self.add_token(TokenValue::Fragment(nested_tokens), self.offset, self.offset, tokens);
+                self.fragment_depth -= 1;
+                self.in_subscript = in_subscript;
+                if c == '[' && next_char.as_ref() != Some(&closing_char) {
+                    // An unbalanced `[` is not starting an array subscript, its tokens are kept flat.
+                    let bracket = tokens.last_mut().expect("the opening bracket was captured");
+                    if let TokenValue::Punctuation(value) = bracket.value {
+                        bracket.value = TokenValue::Any(value);
+                    }
+                    tokens.append(&mut nested_tokens);
+                } else {
+                    // The fragment starts right after the opening parenthesis and ends before the closing one (or the
+                    // statement delimiter, or the end of the input if unclosed).
+                    self.token_start = fragment_start;
+                    let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                    self.add_token(TokenValue::Fragment(nested_tokens), end_offset, end_offset, tokens);
+                }
                 // We cannot assume the next character is the end of the parentheses block because we could have
                 // reached the end of the input or the statement delimiter.
-                if next_char.as_ref() == Some(&')') {
+                if next_char.as_ref() == Some(&closing_char) {
                     // Capturing the end parenthesis.
-                    self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                    self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Punctuation);
                 } else {
                     // End of the input or statement delimiter found.
                     return next_char;
                 }
-            } else if c == ')' {
+            } else if c == ')' || (c == '}' && self.options.jdbc_escapes) || (c == ']' && self.options.array_subscripts)
+            {
                 //
-                // End of a parentheses block.
+                // End of a parentheses block (or of a JDBC escape sequence, or of an array subscript).
                 //
                 // Capture the last token before the end parenthesis.
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
@@ -460,10 +1399,16 @@ impl<'s> Tokenizer<'s> {
                 next_char = self.get_next_char(input_iter);
                 if next_char.is_some() && next_char.as_ref().unwrap().is_ascii_digit() {
                     // The dot is part of a numeric constant.
-                    next_char = self.capture_numeric_constant(input_iter, "_0123456789.eE+-", tokens);
+                    next_char = self.capture_numeric_constant(
+                        input_iter,
+                        "_0123456789.eE+-",
+                        tokens,
+                        TokenValue::NumericConstant,
+                    );
                 } else {
                     // The dot is not part of a numeric constant, we need to capture it as a token.
-                    self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                    let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
+                    self.capture_token(tokens, end_offset, end_offset, TokenValue::Punctuation);
                 }
                 continue; // `next_char` need to be processed by the tokenizer...
             } else if c.is_numeric() {
@@ -476,27 +1421,54 @@ impl<'s> Tokenizer<'s> {
                     next_char = self.get_next_char(input_iter);
                     if next_char.as_ref() == Some(&'x') || next_char.as_ref() == Some(&'X') {
                         // hexadecimal constant.
-                        next_char = self.capture_numeric_constant(input_iter, "_0123456789abcdefABCDEF", tokens);
+                        next_char = self.capture_numeric_constant(
+                            input_iter,
+                            "_0123456789abcdefABCDEF",
+                            tokens,
+                            TokenValue::NumericConstant,
+                        );
                     } else if next_char.as_ref() == Some(&'o') || next_char.as_ref() == Some(&'O') {
                         // Octal constant.
-                        next_char = self.capture_numeric_constant(input_iter, "_01234567", tokens);
+                        // Invalid digits (`8` and `9`) are captured as part of the constant, so `0o78` is not split.
+                        next_char = self.capture_numeric_constant(
+                            input_iter,
+                            "_0123456789",
+                            tokens,
+                            TokenValue::NumericConstant,
+                        );
                     } else if next_char.as_ref() == Some(&'b') || next_char.as_ref() == Some(&'B') {
                         // Binary constant.
-                        next_char = self.capture_numeric_constant(input_iter, "_01", tokens);
+                        // Invalid digits (`2` to `9`) are captured as part of the constant, so `0b12` is not split.
+                        next_char = self.capture_numeric_constant(
+                            input_iter,
+                            "_0123456789",
+                            tokens,
+                            TokenValue::NumericConstant,
+                        );
                     } else if next_char.is_some()
                         && (next_char.as_ref() == Some(&'.') || next_char.as_ref().unwrap().is_ascii_digit())
                     {
                         // Decimal constant.
-                        next_char = self.capture_numeric_constant(input_iter, "_0123456789.eE+-", tokens);
+                        next_char = self.capture_numeric_constant(
+                            input_iter,
+                            "_0123456789.eE+-",
+                            tokens,
+                            TokenValue::NumericConstant,
+                        );
                     } else if next_char.is_some() {
                         // We found a single zero ('0') followed by a character that is not part of a numeric constant.
                         self.capture_token(tokens, self.offset, self.offset, TokenValue::NumericConstant);
                     } else {
                         // We found a single zero ('0') a the end of the input.
-                        self.capture_token(tokens, self.offset, self.next_offset, TokenValue::NumericConstant);
+                        self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::NumericConstant);
                     }
                 } else {
-                    next_char = self.capture_numeric_constant(input_iter, "_0123456789.eE+-", tokens);
+                    next_char = self.capture_numeric_constant(
+                        input_iter,
+                        "_0123456789.eE+-",
+                        tokens,
+                        TokenValue::NumericConstant,
+                    );
                 }
                 continue; // `next_char` need to be processed by the tokenizer...
             } else if c.is_alphabetic() || c == '_' {
@@ -504,8 +1476,14 @@ impl<'s> Tokenizer<'s> {
                 // Identifier or keyword.
                 //
                 self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
-                next_char = self.try_capture_identifier_or_keyword(input_iter, tokens);
+                next_char = self.try_capture_identifier_or_keyword(input_iter, delimiter, tokens);
                 continue; // `next_char` need to be processed by the tokenizer...
+            } else if c == ',' {
+                //
+                // Comma.
+                //
+                self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
+                self.capture_token(tokens, self.next_offset, self.next_offset, TokenValue::Punctuation);
             } else {
                 //
                 // Any other character that is not an underscore or alphabetic will be considered as a boundary
@@ -533,8 +1511,22 @@ impl<'s> Tokenizer<'s> {
     //
     // Returns true if an operator was found, false otherwise.
     fn try_capture_operator(&mut self, input_iter: &mut std::str::Chars, tokens: &mut Tokens<'s>) -> bool {
-        let remaining_input = &self.input[self.offset..];
-        let operator = OPERATORS.iter().find(|&op| remaining_input.starts_with(op));
+        let remaining_input: &'s str = &self.input[self.offset..];
+        // The built-in operators are listed before their prefixes, the first one matching is the longest.
+        let built_in = OPERATORS
+            .iter()
+            .copied()
+            .find(|&op| remaining_input.starts_with(op) && !(op == "<@" && self.options.at_variables));
+        // The operator is taken from the input so it doesn't borrow the options.
+        let operator = self
+            .options
+            .additional_operators
+            .iter()
+            .map(String::as_str)
+            .filter(|op| !op.is_empty() && remaining_input.starts_with(op))
+            .chain(built_in)
+            .max_by_key(|op| op.len())
+            .map(|op| &remaining_input[..op.len()]);
         if let Some(op) = operator {
             // We found an operator, we need to capture the current token before the operator.
             self.capture_token(tokens, self.offset, self.offset, TokenValue::Any);
@@ -562,10 +1554,13 @@ impl<'s> Tokenizer<'s> {
     // See: https://dev.mysql.com/doc/refman/8.4/en/stored-programs-defining.html
     //
     // This function will panic if the delimiter is an empty string.
+    //
+    // Any change must be mirrored in `Scanner::skip_delimited_token` (splitter).
     fn capture_delimited_token<T: Into<TokenValue<'s>>>(
         &mut self,
         input_iter: &mut std::str::Chars,
         delimiter: &str,
+        unterminated: DiagnosticKind,
         tokens: &mut Tokens<'s>,
         value_constructor: impl Fn(&'s str) -> T,
     ) -> Option<char> {
@@ -596,40 +1591,171 @@ impl<'s> Tokenizer<'s> {
             next_char = self.get_next_char(input_iter);
         }
         // We reached the end of the input without finding the end of the token...
+        self.add_diagnostic(unterminated);
         self.capture_token(tokens, self.next_offset, self.next_offset, value_constructor);
         next_char
     }
 
+    // Tokenize the whole input as a single unit, without splitting it into statements.
+    //
+    // The statement delimiter and the other terminators are regular tokens, and the client commands and the other
+    // constructs changing the boundaries of the statements are not recognized.
+    pub(crate) fn tokenize(input: &'s str, options: &Options) -> Tokens<'s> {
+        let options = Options {
+            interpret_delimiter_command: false,
+            interpret_terminator_directive: false,
+            interpret_set_term: false,
+            psql_meta_commands: false,
+            sqlite_dot_commands: false,
+            sqlplus_script_includes: false,
+            snowsql_bang_commands: false,
+            split_on_blank_lines: false,
+            ..options.clone()
+        };
+        let delimiter = options.statement_delimiter.clone();
+        let mut tokenizer = Tokenizer { split_statements: false, ..Tokenizer::new(input, options) };
+        let mut input_iter = input.chars();
+        let mut tokens = Tokens::new();
+        while tokenizer.capture_fragment(input_iter.by_ref(), &delimiter, &mut tokens).is_some() {
+            // A closing parenthesis without a matching opening parenthesis.
+            tokenizer.capture_token(&mut tokens, tokenizer.next_offset, tokenizer.next_offset, TokenValue::Punctuation);
+        }
+        if tokenizer.options.case_fragments {
+            group_case_expressions(&mut tokens);
+        }
+        tokens
+    }
+
     // Get the next statement from the input.
     // The end of the next statement is determined by the delimiter provided or the end of the input.
     fn get_next_statement(&mut self, input_iter: &mut std::str::Chars, delimiter: &str) -> Option<Statement<'s>> {
         // Capture all tokens until the next semicolon.
         let mut tokens = Tokens::new();
+        let mut stored_program = StoredProgram::default();
+        let mut atomic_block = AtomicBlock::default();
 
         // Under normal circumstances, the tokenizer will either return None if the input is empty or the first
         // character if the delimiter if found.
         // Nevertheless we need to handle the case where the tokenizer was stopped by a closing parenthesis without a
         // matching opening parenthesis. This is why we need to loop until we find the delimiter or reach the end of the
         // input.
-        while self.capture_fragment(input_iter, delimiter, &mut tokens).is_some() {
-            if self.check_delimiter(delimiter) {
+        while let Some(c) = self.capture_fragment(input_iter, delimiter, &mut tokens) {
+            if self.is_meta_command(c) {
+                // The meta-command will be captured by the next statement, moving back to its first character.
+                self.next_offset = self.offset;
+                self.column -= 1;
+                break;
+            } else if let Some(delimiter_len) = self.match_statement_delimiter(c, delimiter) {
+                if self.options.slash_terminator && !self.is_slash_terminator(c) && is_plsql_block(&tokens) {
+                    // PL/SQL blocks can only be terminated by a `/`, the delimiter is a regular token of the block.
+                    self.forward_iter(input_iter, delimiter_len - 1);
+                    self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                    continue;
+                }
+                if (self.options.routine_body_blocks && is_unclosed_routine_body(&tokens))
+                    || (self.options.stored_program_blocks && stored_program.is_unclosed(&tokens, delimiter))
+                    || (self.options.begin_atomic_blocks && atomic_block.is_unclosed(&tokens))
+                {
+                    // The delimiter is a regular token of the routine body until its outermost block is closed.
+                    self.forward_iter(input_iter, delimiter_len - 1);
+                    self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                    continue;
+                }
                 // The delimiter was found but not captured as a token, we need to capture it now.
                 // Moving forward the iterator until the end of the delimiter.
-                self.forward_iter(input_iter, delimiter.chars().count() - 1);
+                self.forward_iter(input_iter, delimiter_len - 1);
                 self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::StatementDelimiter);
                 break;
             } else {
                 // We need to continue the tokenization because we found a closing parenthesis without a matching
                 // opening parenthesis.
                 // We need to capture the closing parenthesis as a token before resuming the tokenization.
-                self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::Any);
+                self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::Punctuation);
             }
         }
 
+        if self.options.case_fragments {
+            group_case_expressions(&mut tokens);
+        }
         match tokens.is_empty() {
             // We reached the end of the input without finding any token.
             true => None,
-            false => Some(Statement { input: self.input, tokens }),
+            false => {
+                self.copy_data_pending = self.options.copy_from_stdin_data
+                    && tokens.last().is_some_and(|token| token.is_statement_delimiter())
+                    && is_copy_from_stdin(&tokens);
+                let delimiter = match tokens.last() {
+                    Some(token) if token.is_statement_delimiter() && !self.options.include_delimiter_token => {
+                        tokens.pop()
+                    }
+                    _ => None,
+                };
+                let diagnostics = std::mem::take(&mut self.diagnostics);
+                Some(Statement {
+                    input: self.input,
+                    tokens,
+                    delimiter,
+                    diagnostics,
+                    calls_are_queries: self.options.calls_are_queries,
+                    from_first_queries: self.options.from_first_queries,
+                })
+            }
+        }
+    }
+
+    // Get the inline data following a `COPY ... FROM STDIN` statement (PostgreSQL).
+    //
+    // The data starts on the line following the `COPY` statement and ends with a line containing only `\.` (or the end
+    // of the input).
+    // See: https://www.postgresql.org/docs/current/sql-copy.html
+    fn get_copy_data(&mut self, input_iter: &mut std::str::Chars) -> Option<Statement<'s>> {
+        // Skip the end of the line of the `COPY` statement.
+        while let Some(c) = self.get_next_char(input_iter) {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+                break;
+            }
+            self.process_newline(c);
+        }
+        self.token_start = Position { line: self.line, column: 1, offset: self.next_offset };
+        let mut tokens = Tokens::new();
+        let mut line_start = true;
+        loop {
+            if line_start
+                && self.input[self.next_offset..]
+                    .strip_prefix("\\.")
+                    .is_some_and(|end| end.is_empty() || end.starts_with('\n') || end.starts_with("\r\n"))
+            {
+                // We found the end of the data.
+                self.forward_iter(input_iter, 2);
+                break;
+            }
+            match self.get_next_char(input_iter) {
+                Some('\n') => {
+                    self.line += 1;
+                    self.column = 0;
+                    line_start = true;
+                }
+                Some(c) => {
+                    self.process_newline(c);
+                    line_start = false;
+                }
+                None => break,
+            }
+        }
+        self.capture_token(&mut tokens, self.next_offset, self.next_offset, TokenValue::CopyData);
+        match tokens.is_empty() {
+            // We reached the end of the input without finding any data.
+            true => None,
+            false => Some(Statement {
+                input: self.input,
+                tokens,
+                delimiter: None,
+                diagnostics: Vec::new(),
+                calls_are_queries: self.options.calls_are_queries,
+                from_first_queries: self.options.from_first_queries,
+            }),
         }
     }
 
@@ -638,18 +1764,32 @@ impl<'s> Tokenizer<'s> {
     // The numeric constant will be captured until we reach any character that is not in the provided `allowed_chars`.
     // `+` and `-` are allowed only if the previous character is `e` (exponential notation: `digits.[digits][e[+-]digits]`),
     // a leading `+` or `-` is not captured as a sign of the numeric constant but as an operator.
-    fn capture_numeric_constant(
+    // Only one decimal point is allowed and it cannot be part of the exponent, so `1..2` is not captured as a single
+    // numeric constant.
+    //
+    // Any change must be mirrored in `Scanner::skip_numeric_constant` (splitter).
+    fn capture_numeric_constant<T: Into<TokenValue<'s>>>(
         &mut self,
         input_iter: &mut std::str::Chars,
         allowed_chars: &str,
         tokens: &mut Tokens<'s>,
+        value_constructor: impl Fn(&'s str) -> T,
     ) -> Option<char> {
+        // The numeric constant may have started with a decimal point already consumed (`.5`, `0.`).
+        let mut decimal_point_allowed = !self.input[self.token_start.offset..self.next_offset].contains('.');
         let mut next_char = self.get_next_char(input_iter);
         while let Some(c) = next_char {
             if !allowed_chars.contains(c) {
                 break;
-            } else if c == 'e' || c == 'E' {
+            } else if c == '.' {
+                if !decimal_point_allowed {
+                    break;
+                }
+                decimal_point_allowed = false;
+            } else if (c == 'e' || c == 'E') && allowed_chars.contains('+') {
+                decimal_point_allowed = false;
                 // Check if the next character is a sign (+ or -) or a digit to allow exponential notation.
+                // The exponential notation is only allowed by decimal constants (`e` is a digit of hexadecimal ones).
                 next_char = self.get_next_char(input_iter);
                 if next_char.is_none()
                     || (next_char.as_ref() != Some(&'+')
@@ -664,9 +1804,20 @@ impl<'s> Tokenizer<'s> {
             }
             next_char = self.get_next_char(input_iter);
         }
+        if allowed_chars.contains('+') && matches!(next_char, Some('f' | 'F' | 'd' | 'D')) {
+            // Oracle `BINARY_FLOAT` (`1.5f`) and `BINARY_DOUBLE` (`3d`) suffixes, only if directly following a digit or
+            // the decimal point and not followed by other identifier characters (`1.5fx` is not a numeric constant).
+            let last_char = self.input[self.token_start.offset..self.offset].chars().last();
+            let following_char = self.input[self.next_offset..].chars().next();
+            if matches!(last_char, Some('0'..='9' | '.'))
+                && !matches!(following_char, Some(c) if c.is_alphanumeric() || c == '_' || c == '$')
+            {
+                next_char = self.get_next_char(input_iter);
+            }
+        }
         // We reached the end of the numeric constant or the end of the input.
         let end_offset = if next_char.is_some() { self.offset } else { self.next_offset };
-        self.capture_token(tokens, end_offset, end_offset, TokenValue::NumericConstant);
+        self.capture_token(tokens, end_offset, end_offset, value_constructor);
         next_char
     }
 
@@ -676,22 +1827,28 @@ impl<'s> Tokenizer<'s> {
     /// non-Latin letters) or an underscore (_). Subsequent characters in an identifier or key word can be letters,
     /// underscores, digits (0-9), or dollar signs ($).
     ///
-    /// If the token is immediately followed by a single quote (') or a double quote (") it will not be captured because
-    /// it should be captured as a part of a constant with a introducer (E'', N'', _latin1'', ...).
+    /// If the token is immediately followed by a single quote (') it will not be captured because it should be captured
+    /// as a part of a constant with a introducer (E'', N'', _latin1'', ...). Same goes for a double quote (") following
+    /// a raw string or bytes introducer (r"", rb"", b"", ...).
     fn try_capture_identifier_or_keyword(
         &mut self,
         input_iter: &mut std::str::Chars,
+        delimiter: &str,
         tokens: &mut Tokens<'s>,
     ) -> Option<char> {
         let mut next_char = self.get_next_char(input_iter);
         while let Some(c) = next_char {
-            if c.is_alphanumeric() || c == '_' || c == '$' {
+            // `$` is allowed in identifiers unless it's the start of the statement delimiter (ex: `END$$`).
+            if c.is_alphanumeric() || c == '_' || (c == '$' && self.match_statement_delimiter(c, delimiter).is_none()) {
                 next_char = self.get_next_char(input_iter);
             } else {
                 break;
             }
         }
-        if next_char.as_ref() == Some(&'\'') {
+        if next_char.as_ref() == Some(&'\'')
+            || (next_char.as_ref() == Some(&'"')
+                && is_double_quoted_string_introducer(&self.input[self.token_start.offset..self.offset]))
+        {
             // The identifier or keyword is followed by a quote, it should be captured as a constant with an introducer.
             return next_char;
         }
@@ -702,9 +1859,339 @@ impl<'s> Tokenizer<'s> {
     }
 }
 
+// Check if the tokens are the beginning of a PL/SQL block (Oracle).
+//
+// A PL/SQL block is either an anonymous block (`DECLARE ...` or `BEGIN ...`) or the definition of a stored program
+// (`CREATE [OR REPLACE] [EDITIONABLE | NONEDITIONABLE] PROCEDURE | FUNCTION | PACKAGE | TRIGGER ...`).
+fn is_plsql_block(tokens: &Tokens) -> bool {
+    let mut keywords = tokens.iter().filter(|token| token.is_identifier_or_keyword());
+    match keywords.next() {
+        Some(keyword) if keyword.is_keyword_eq("DECLARE") || keyword.is_keyword_eq("BEGIN") => true,
+        Some(keyword) if keyword.is_keyword_eq("CREATE") => keywords
+            .find(|keyword| {
+                !["OR", "REPLACE", "EDITIONABLE", "NONEDITIONABLE"].iter().any(|k| keyword.is_keyword_eq(k))
+            })
+            .is_some_and(|keyword| {
+                ["PROCEDURE", "FUNCTION", "PACKAGE", "TRIGGER"].iter().any(|k| keyword.is_keyword_eq(k))
+            }),
+        _ => false,
+    }
+}
+
+// Check if the tokens are the definition of a routine whose `BEGIN ... END` body is not closed yet (SQL Server,
+// SQLite).
+//
+// The routine is defined by `[CREATE [OR ALTER] | ALTER] PROCEDURE | PROC | FUNCTION | TRIGGER ...` or by
+// `CREATE [TEMP | TEMPORARY] TRIGGER ...` (SQLite), the nesting of its body is tracked using the `BEGIN` (including
+// `BEGIN TRY` and `BEGIN CATCH`), `CASE` and `END` keywords. The words of bracketed identifiers (`[End Date]`) and the
+// transaction statements (`BEGIN TRAN`, `END CONVERSATION`) are ignored.
+fn is_unclosed_routine_body(tokens: &Tokens) -> bool {
+    let mut in_brackets = false;
+    let mut keywords = Vec::new();
+    for token in tokens.iter() {
+        match token.value {
+            TokenValue::Any("[") => in_brackets = true,
+            TokenValue::Any("]") => in_brackets = false,
+            _ if !in_brackets && token.is_identifier_or_keyword() => keywords.push(token),
+            _ => {}
+        }
+    }
+    let is_any_of = |index: usize, words: &[&str]| {
+        keywords.get(index).is_some_and(|keyword| words.iter().any(|word| keyword.is_keyword_eq(word)))
+    };
+    let mut index = if is_any_of(0, &["CREATE"]) && is_any_of(1, &["OR"]) && is_any_of(2, &["ALTER"]) {
+        3
+    } else if is_any_of(0, &["CREATE", "ALTER"]) {
+        1
+    } else {
+        return false;
+    };
+    if is_any_of(index, &["TEMP", "TEMPORARY"]) {
+        index += 1;
+    }
+    if !is_any_of(index, &["PROCEDURE", "PROC", "FUNCTION", "TRIGGER"]) {
+        return false;
+    }
+    let mut depth = 0usize;
+    while index < keywords.len() {
+        if is_any_of(index, &["BEGIN"]) && !is_any_of(index + 1, &["TRAN", "TRANSACTION", "DISTRIBUTED", "DIALOG"])
+            || is_any_of(index, &["CASE"])
+        {
+            depth += 1;
+        } else if is_any_of(index, &["END"]) && !is_any_of(index + 1, &["CONVERSATION"]) {
+            depth = depth.saturating_sub(1);
+        }
+        index += 1;
+    }
+    depth > 0
+}
+
+// The nesting of the body of a stored program (MySQL, PL/pgSQL), tracked while the tokens of a statement are captured.
+//
+// The stored program is defined by `CREATE [OR REPLACE] [DEFINER = user] [AGGREGATE] TRIGGER | PROCEDURE | FUNCTION |
+// EVENT ...`, the nesting of its body is tracked using the `BEGIN`, `IF`, `LOOP`, `WHILE`, `REPEAT` and `CASE` keywords
+// closed by `END` (`END IF`, `END LOOP`, ...). The `IF()` and `REPEAT()` functions and the `IF [NOT] EXISTS` clauses
+// are ignored. The `LOOP` following a `WHILE` condition and the `BEGIN` following a `DECLARE` section are part of the
+// same block (PL/pgSQL: `WHILE ... LOOP ... END LOOP`, `DECLARE ... BEGIN ... END`).
+// See: https://dev.mysql.com/doc/refman/8.4/en/sql-compound-statements.html
+// See: https://www.postgresql.org/docs/current/plpgsql-control-structures.html
+#[derive(Default)]
+struct StoredProgram {
+    // The number of tokens already scanned, only the tokens captured since the previous check are scanned.
+    scanned: usize,
+
+    // The part of the definition of the stored program being scanned.
+    part: StoredProgramPart,
+
+    // The nesting depth of the body.
+    depth: usize,
+
+    // Whether the previous token is `END`, the keyword following `END` is closing a block (`END IF`, `END LOOP`, ...).
+    after_end: bool,
+
+    // Whether the tokens are the condition of a `WHILE` block or the declaration section of the body (PL/pgSQL).
+    in_while_condition: bool,
+    in_declare_section: bool,
+
+    // The `IF` or `REPEAT` keyword preceding the token with its end offset, it's a function name if the token is its
+    // arguments (`IF(cond, a, b)`, `REPEAT('x', 3)`).
+    after_keyword: Option<(&'static str, usize)>,
+
+    // The number of `IF` keywords starting a block if a `THEN` is found before the delimiter, unlike `IF EXISTS`.
+    pending_ifs: usize,
+}
+
+// The part of the definition of a stored program being scanned.
+#[derive(Default, PartialEq)]
+enum StoredProgramPart {
+    // The `CREATE` keyword starting the statement.
+    #[default]
+    Create,
+
+    // The `OR REPLACE`, `AGGREGATE` and `DEFINER = user` clauses preceding the kind of stored program.
+    Clauses,
+
+    // The `=` following `DEFINER`, and the user made of adjacent tokens (`'root'@'%'`, `root@localhost`,
+    // `CURRENT_USER()`) with the end offset of its last token.
+    DefinerEquals,
+    DefinerUser(Option<usize>),
+
+    // The body following the kind of stored program (`TRIGGER`, `PROCEDURE`, `FUNCTION`, `EVENT`).
+    Body,
+
+    // The statement is not the definition of a stored program.
+    Other,
+}
+
+impl StoredProgram {
+    // Check if the tokens are the definition of a stored program whose body is not closed yet.
+    fn is_unclosed(&mut self, tokens: &Tokens, delimiter: &str) -> bool {
+        for token in tokens.iter().skip(self.scanned) {
+            if !token.is_comment() && !token.is_hint() && !token.is_versioned_comment() {
+                self.scan(token, delimiter);
+            }
+        }
+        self.scanned = tokens.len();
+        // The delimiter following the tokens is resolving the pending keywords.
+        self.resolve_keyword(None);
+        self.end_condition();
+        self.part == StoredProgramPart::Body && self.depth > 0
+    }
+
+    fn scan(&mut self, token: &Token, delimiter: &str) {
+        let is_any_of = |words: &[&str]| words.iter().any(|word| token.is_keyword_eq(word));
+        self.part = match self.part {
+            StoredProgramPart::Create if is_any_of(&["CREATE"]) => StoredProgramPart::Clauses,
+            StoredProgramPart::DefinerEquals => StoredProgramPart::DefinerUser(None),
+            StoredProgramPart::DefinerUser(end) if end.is_none_or(|end| token.start.offset == end) => {
+                StoredProgramPart::DefinerUser(Some(token.end.offset))
+            }
+            StoredProgramPart::Clauses | StoredProgramPart::DefinerUser(_) => {
+                if is_any_of(&["OR", "REPLACE", "AGGREGATE"]) {
+                    StoredProgramPart::Clauses
+                } else if is_any_of(&["DEFINER"]) {
+                    StoredProgramPart::DefinerEquals
+                } else if is_any_of(&["TRIGGER", "PROCEDURE", "FUNCTION", "EVENT"]) {
+                    StoredProgramPart::Body
+                } else {
+                    StoredProgramPart::Other
+                }
+            }
+            StoredProgramPart::Body => {
+                self.scan_body(token, delimiter);
+                StoredProgramPart::Body
+            }
+            StoredProgramPart::Create | StoredProgramPart::Other => StoredProgramPart::Other,
+        };
+    }
+
+    fn scan_body(&mut self, token: &Token, delimiter: &str) {
+        let is_any_of = |words: &[&str]| words.iter().any(|word| token.is_keyword_eq(word));
+        self.resolve_keyword(Some(token));
+        if matches!(token.value, TokenValue::Any(value) if value == delimiter) {
+            self.end_condition();
+        } else if is_any_of(&["DO"]) {
+            self.in_while_condition = false;
+        } else if is_any_of(&["THEN"]) && self.pending_ifs > 0 {
+            // The `IF` keywords followed by a `THEN` are starting `IF` blocks.
+            self.depth += std::mem::take(&mut self.pending_ifs);
+            self.in_while_condition = false;
+        }
+        let after_end = std::mem::replace(&mut self.after_end, is_any_of(&["END"]));
+        if is_any_of(&["END"]) {
+            self.depth = self.depth.saturating_sub(1);
+        } else if after_end {
+            // The keyword following `END` is closing the block (`END IF`, `END LOOP`, ...).
+        } else if is_any_of(&["LOOP"]) && self.in_while_condition {
+            // The `LOOP` is starting the body of the `WHILE` block (PL/pgSQL).
+            self.in_while_condition = false;
+        } else if is_any_of(&["DECLARE"]) && self.depth == 0 {
+            // The declaration section is starting the body (PL/pgSQL: `DECLARE ... BEGIN ... END`).
+            self.in_declare_section = true;
+            self.depth += 1;
+        } else if is_any_of(&["BEGIN"]) && self.in_declare_section {
+            self.in_declare_section = false;
+        } else if is_any_of(&["BEGIN", "LOOP", "WHILE", "CASE"]) {
+            self.in_while_condition = is_any_of(&["WHILE"]);
+            self.depth += 1;
+        } else if is_any_of(&["IF"]) {
+            self.after_keyword = Some(("IF", token.end.offset));
+        } else if is_any_of(&["REPEAT"]) {
+            self.after_keyword = Some(("REPEAT", token.end.offset));
+        }
+    }
+
+    // Resolve the `IF` or `REPEAT` keyword preceding the token (or the delimiter if `None`), the keyword is a function
+    // name if the token is immediately following it and is starting its arguments.
+    fn resolve_keyword(&mut self, token: Option<&Token>) {
+        match self.after_keyword.take() {
+            Some((_, end)) if token.is_some_and(|token| token.is_parenthesis() && token.start.offset == end) => {}
+            Some(("REPEAT", _)) => {
+                self.in_while_condition = false;
+                self.depth += 1;
+            }
+            Some(_) => self.pending_ifs += 1,
+            None => {}
+        }
+    }
+
+    // The delimiter is ending the condition of a `WHILE` block and the `IF [NOT] EXISTS` clauses.
+    fn end_condition(&mut self) {
+        self.in_while_condition = false;
+        self.pending_ifs = 0;
+    }
+}
+
+// The nesting of a `BEGIN ATOMIC` compound statement (SQL standard, PostgreSQL, DB2), tracked while the tokens of a
+// statement are captured.
+//
+// The nesting of the compound statement is tracked using the `BEGIN`, `CASE` and `END` keywords, the `END` closing the
+// other compound statements (`END IF`, `END LOOP`, `END WHILE`, `END REPEAT`, `END FOR`) are ignored.
+// See: https://www.postgresql.org/docs/current/sql-createfunction.html
+#[derive(Default)]
+struct AtomicBlock {
+    // The number of tokens already scanned, only the tokens captured since the previous check are scanned.
+    scanned: usize,
+
+    // The nesting depth of the compound statement, `None` until `BEGIN ATOMIC` is found.
+    depth: Option<usize>,
+
+    // Whether the previous keyword is `BEGIN` (before the compound statement) or `END` (inside the compound statement).
+    after_begin: bool,
+    after_end: bool,
+}
+
+impl AtomicBlock {
+    // Check if the tokens contain a `BEGIN ATOMIC` compound statement which is not closed yet.
+    fn is_unclosed(&mut self, tokens: &Tokens) -> bool {
+        for token in tokens.iter().skip(self.scanned).filter(|token| token.is_identifier_or_keyword()) {
+            let Some(depth) = &mut self.depth else {
+                if self.after_begin && token.is_keyword_eq("ATOMIC") {
+                    self.depth = Some(1);
+                }
+                self.after_begin = token.is_keyword_eq("BEGIN");
+                continue;
+            };
+            let after_end = std::mem::replace(&mut self.after_end, token.is_keyword_eq("END"));
+            if after_end && !["IF", "LOOP", "WHILE", "REPEAT", "FOR"].iter().any(|word| token.is_keyword_eq(word)) {
+                *depth = depth.saturating_sub(1);
+            }
+            if token.is_keyword_eq("BEGIN") || (token.is_keyword_eq("CASE") && !after_end) {
+                *depth += 1;
+            }
+        }
+        self.scanned = tokens.len();
+        // The `END` followed by the delimiter is closing a compound statement.
+        if let (Some(depth), true) = (&mut self.depth, std::mem::take(&mut self.after_end)) {
+            *depth = depth.saturating_sub(1);
+        }
+        self.depth.is_some_and(|depth| depth > 0)
+    }
+}
+
+// Group the `CASE ... END` expressions of the tokens into fragments, including the ones found in nested fragments.
+//
+// The fragment includes the `CASE` and `END` keywords, an unterminated `CASE` is closed by the statement delimiter or
+// the end of the tokens. The `CASE` following an `END` is closing a `CASE` statement (MySQL: `END CASE`), it's not
+// starting a new expression.
+fn group_case_expressions(tokens: &mut Tokens) {
+    fn close_group<'s>(groups: &mut Vec<Tokens<'s>>) {
+        let group = groups.pop().unwrap();
+        let (start, end) = (group.first().unwrap().start.clone(), group.last().unwrap().end.clone());
+        groups.last_mut().unwrap().push(Token::new(TokenValue::Fragment(group), start, end));
+    }
+    let mut groups = vec![Tokens::new()];
+    let mut after_end = false;
+    for mut token in std::mem::take(&mut **tokens) {
+        if let TokenValue::Fragment(children) = &mut token.value {
+            group_case_expressions(children);
+        }
+        if token.is_statement_delimiter() {
+            while groups.len() > 1 {
+                close_group(&mut groups);
+            }
+        }
+        let is_case = token.is_keyword_eq("CASE") && !after_end;
+        let is_end = token.is_keyword_eq("END") && groups.len() > 1;
+        after_end = token.is_keyword_eq("END");
+        if is_case {
+            groups.push(Tokens::new());
+        }
+        groups.last_mut().unwrap().push(token);
+        if is_end {
+            close_group(&mut groups);
+        }
+    }
+    while groups.len() > 1 {
+        close_group(&mut groups);
+    }
+    *tokens = groups.pop().unwrap();
+}
+
+// Check if the tokens are a `COPY ... FROM STDIN` statement (PostgreSQL).
+fn is_copy_from_stdin(tokens: &Tokens) -> bool {
+    let keywords: Vec<_> = tokens.iter().filter(|token| token.is_identifier_or_keyword()).collect();
+    keywords.first().is_some_and(|keyword| keyword.is_keyword_eq("COPY"))
+        && keywords.windows(2).any(|keywords| keywords[0].is_keyword_eq("FROM") && keywords[1].is_keyword_eq("STDIN"))
+}
+
+// Check if the given introducer is the one of a raw string constant (BigQuery: r'...', R"...", rb'...', Br"...").
+pub(crate) fn is_raw_string_introducer(introducer: &str) -> bool {
+    introducer.eq_ignore_ascii_case("r")
+        || introducer.eq_ignore_ascii_case("rb")
+        || introducer.eq_ignore_ascii_case("br")
+}
+
+// Check if the given introducer can be used with a double-quoted string constant (BigQuery: r"...", b"...", rb"...",
+// MySQL: _utf8mb4"...").
+pub(crate) fn is_double_quoted_string_introducer(introducer: &str) -> bool {
+    introducer.eq_ignore_ascii_case("b") || is_raw_string_introducer(introducer) || introducer.starts_with('_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Dialect;
 
     // A macro that check if the input is captured as a token of the given variant, expected positions and value.
     //
@@ -733,7 +2220,14 @@ mod tests {
 
     macro_rules! assert_tokens {
         ($input:expr, $( $expected:expr ),* ) => {
-            let mut statements = Tokenizer::new($input, Options::default()).into_iter();
+            assert_tokens_with_options!($input, Options::default(), $( $expected ),*);
+        };
+    }
+
+    // Same as `assert_tokens!` but with the given options.
+    macro_rules! assert_tokens_with_options {
+        ($input:expr, $options:expr, $( $expected:expr ),* ) => {
+            let mut statements = Tokenizer::new($input, $options).into_iter();
             let expected_values = vec![$( $expected.as_slice() ),*];
             for expected in expected_values {
                 let statement = statements.next();
@@ -753,6 +2247,27 @@ mod tests {
     fn test_quoted_identifier_with_unicode_escapes() {
         assert_token!(r#"U&"d\\0061t\\+000061""#, QuotedIdentifierOrConstant);
         assert_token!(r#"U&"\\0441\\043B\\043E\\043D""#, QuotedIdentifierOrConstant);
+        assert_token!(r#"u&"d\\0061t""#, QuotedIdentifierOrConstant);
+        assert_token!(r#"U&'d\\0061t\\+000061'"#, QuotedIdentifierOrConstant);
+        assert_token!(r#"u&'d\\0061t'"#, QuotedIdentifierOrConstant);
+        assert_tokens!(
+            r#"SELECT U&'d!0061t!+000061' UESCAPE '!'; SELECT 2"#,
+            ["SELECT", "U&'d!0061t!+000061'", "UESCAPE", "'!'", ";"],
+            ["SELECT", "2"]
+        );
+        // The custom escape character does not escape the quote character, only doubled quotes do.
+        assert_tokens!(
+            r#"SELECT U&'a!''b' UESCAPE '!'; SELECT U&"a!""b" UESCAPE '!'"#,
+            ["SELECT", "U&'a!''b'", "UESCAPE", "'!'", ";"],
+            ["SELECT", r#"U&"a!""b""#, "UESCAPE", "'!'"]
+        );
+        assert_tokens!(
+            r#"SELECT U&'it''s!0021' UESCAPE '!'; SELECT U&"a!0021""b" UESCAPE '!'"#,
+            ["SELECT", "U&'it''s!0021'", "UESCAPE", "'!'", ";"],
+            ["SELECT", r#"U&"a!0021""b""#, "UESCAPE", "'!'"]
+        );
+        assert_tokens!("U & 'x'", ["U", "&", "'x'"]);
+        assert_tokens!("SELECT U&'", ["SELECT", "U&'"]);
     }
 
     #[test]
@@ -765,6 +2280,17 @@ mod tests {
         assert_token!("N'こんにちは'", QuotedIdentifierOrConstant);
         assert_token!("n''", QuotedIdentifierOrConstant);
         assert_token!("n'こんにちは'", QuotedIdentifierOrConstant);
+        assert_token!("N'O''Reilly'", QuotedIdentifierOrConstant);
+        assert_tokens!("SELECT N'text', n'it''s'", ["SELECT", "N'text'", ",", "n'it''s'"]);
+        assert_tokens!("SELECT N 'text'", ["SELECT", "N", "'text'"]);
+        assert_tokens!("SELECT N'text", ["SELECT", "N'text"]);
+        assert_token!(r"E'\\'", QuotedIdentifierOrConstant);
+        assert_token!(r"E'\''", QuotedIdentifierOrConstant);
+        assert_token!(r"e'O\'Reilly'", QuotedIdentifierOrConstant);
+        assert_token!(r"E'O''Reilly'", QuotedIdentifierOrConstant);
+        assert_tokens!(r"SELECT E'O\'Reilly'; SELECT 2", ["SELECT", r"E'O\'Reilly'", ";"], ["SELECT", "2"]);
+        assert_tokens!("SELECT E'", ["SELECT", "E'"]);
+        assert_tokens!(r"SELECT E'\", ["SELECT", r"E'\"]);
     }
 
     #[test]
@@ -773,8 +2299,76 @@ mod tests {
         assert_token!("B''", QuotedIdentifierOrConstant);
         assert_token!("b'100'", QuotedIdentifierOrConstant);
         assert_token!("b''", QuotedIdentifierOrConstant);
+        assert_tokens!("SELECT B'0101', b'0'", ["SELECT", "B'0101'", ",", "b'0'"]);
+        assert_tokens!("SELECT b ''", ["SELECT", "b", "''"]);
+        assert_tokens!("SELECT B'01", ["SELECT", "B'01"]);
+        // Only a single `B` introducer denotes a bit-string constant.
+        assert_tokens!("SELECT bit'O''Reilly'", ["SELECT", "bit'O''Reilly'"]);
+    }
+
+    #[test]
+    fn test_bytes_constant() {
+        assert_token!("b'bytes'", QuotedIdentifierOrConstant);
+        assert_token!(r#"B"bytes""#, QuotedIdentifierOrConstant);
+        assert_token!(r#"b"bytes""#, QuotedIdentifierOrConstant);
+        assert_token!("b'O''Reilly'", QuotedIdentifierOrConstant);
+        assert_token!(r#"B"say ""hi""""#, QuotedIdentifierOrConstant);
+        assert_token!(r#"rb"\x00""#, QuotedIdentifierOrConstant);
+        assert_token!("_binary'bytes'", QuotedIdentifierOrConstant);
+        assert_tokens!(r#"SELECT b "bytes""#, ["SELECT", "b", r#""bytes""#]);
+        assert_tokens!(r#"SELECT b"bytes"; SELECT 2"#, ["SELECT", r#"b"bytes""#, ";"], ["SELECT", "2"]);
         assert_token!("x'1FF'", QuotedIdentifierOrConstant);
         assert_token!("x''", QuotedIdentifierOrConstant);
+        assert_token!("X'ABCD'", QuotedIdentifierOrConstant);
+        assert_token!("X''", QuotedIdentifierOrConstant);
+        assert_tokens!("SELECT X'1F', x'1f'", ["SELECT", "X'1F'", ",", "x'1f'"]);
+        assert_tokens!("SELECT X '1F'", ["SELECT", "X", "'1F'"]);
+        assert_tokens!("SELECT x'1F", ["SELECT", "x'1F"]);
+        assert_tokens!("SELECT xml'a''b'", ["SELECT", "xml'a''b'"]);
+    }
+
+    #[test]
+    fn test_raw_string_constant() {
+        assert_token!(r"r'a\b'", QuotedIdentifierOrConstant);
+        assert_token!(r#"R"no\escape""#, QuotedIdentifierOrConstant);
+        assert_token!(r"r'\''", QuotedIdentifierOrConstant);
+        assert_token!(r#"r"\"""#, QuotedIdentifierOrConstant);
+        assert_token!(r"rb'\x00'", QuotedIdentifierOrConstant);
+        assert_token!(r#"BR"\x00""#, QuotedIdentifierOrConstant);
+        assert_token!(r"bR'\x00'", QuotedIdentifierOrConstant);
+        // Quotes cannot be escaped by doubling them in raw strings.
+        assert_tokens!(r"SELECT r'a''b'", ["SELECT", "r'a'", "'b'"]);
+        assert_tokens!(r"SELECT r'\'; SELECT 2", ["SELECT", r"r'\'; SELECT 2"]);
+        assert_tokens!(r"SELECT r'\\'; SELECT 2", ["SELECT", r"r'\\'", ";"], ["SELECT", "2"]);
+        assert_tokens!(r#"SELECT r "x""#, ["SELECT", "r", r#""x""#]);
+        assert_tokens!(r#"SELECT col"x""#, ["SELECT", "col", r#""x""#]);
+    }
+
+    #[test]
+    fn test_triple_quoted_string_constant() {
+        let options = || Options { triple_quoted_strings: true, ..Default::default() };
+        assert_tokens_with_options!("SELECT '''it's fine'''", options(), ["SELECT", "'''it's fine'''"]);
+        assert_tokens_with_options!("SELECT \"\"\"multi\nline\"\"\"", options(), ["SELECT", "\"\"\"multi\nline\"\"\""]);
+        assert_tokens_with_options!(
+            "SELECT '''x;y''';SELECT 2",
+            options(),
+            ["SELECT", "'''x;y'''", ";"],
+            ["SELECT", "2"]
+        );
+        assert_tokens_with_options!(r"SELECT r'''\d+'''", options(), ["SELECT", r"r'''\d+'''"]);
+        assert_tokens_with_options!(
+            "SELECT '''unterminated;\nSELECT 2",
+            options(),
+            ["SELECT", "'''unterminated;\nSELECT 2"]
+        );
+        assert_tokens_with_options!("SELECT '', 'x'", options(), ["SELECT", "''", ",", "'x'"]);
+        // Without the option, `''''` is a string constant containing a single quote.
+        assert_tokens!("SELECT '''x'''", ["SELECT", "'''x'''"]);
+        assert_tokens!("SELECT ''''", ["SELECT", "''''"]);
+
+        let statement = Tokenizer::new("SELECT '''a\nb''' AS x", options()).next().unwrap();
+        assert_eq!(statement.tokens()[1].end.line, 2);
+        assert_eq!(statement.tokens()[2].start.line, 2);
     }
 
     #[test]
@@ -785,6 +2379,11 @@ mod tests {
         assert_token!("_latin1''", QuotedIdentifierOrConstant);
         assert_token!("_binary'string'", QuotedIdentifierOrConstant);
         assert_token!("_utf8mb4'string'", QuotedIdentifierOrConstant);
+        assert_token!(r#"_utf8mb4"string""#, QuotedIdentifierOrConstant);
+        assert_token!("_utf8mb4'O''Reilly'", QuotedIdentifierOrConstant);
+        assert_tokens!("SELECT _latin1 'string'", ["SELECT", "_latin1", "'string'"]);
+        assert_tokens!("SELECT _latin1 X'4D'", ["SELECT", "_latin1", "X'4D'"]);
+        assert_tokens!(r#"SELECT col"alias""#, ["SELECT", "col", r#""alias""#]);
     }
 
     #[test]
@@ -830,19 +2429,1140 @@ mod tests {
         // Should not capture the +/- as part of the numeric constant if not part of the exponential notation.
         assert_tokens!("1.925e-3+1 1.925-3 1.925+3", ["1.925e-3", "+", "1", "1.925", "-", "3", "1.925", "+", "3"]);
 
-        // Should break invalid numeric constants.
+        // Oracle float and double suffixes.
+        assert_token!("1.5f", NumericConstant);
+        assert_token!("3D", NumericConstant);
+        assert_token!("2.d", NumericConstant);
+        assert_token!("1e5F", NumericConstant);
+        assert_tokens!("SELECT 1.5f, 3D FROM dual", ["SELECT", "1.5f", ",", "3D", "FROM", "dual"]);
+        assert_tokens!("SELECT 1.5fx, 2d2, 1ef", ["SELECT", "1.5", "fx", ",", "2", "d2", ",", "1e", "f"]);
+        assert_tokens!("SELECT 0xFd", ["SELECT", "0xFd"]);
+
+        // A single zero at the end of the input.
+        assert_tokens!("SELECT 0", ["SELECT", "0"]);
+        assert_eq!(Tokenizer::new("SELECT 0", Options::default()).next().unwrap().tokens()[1].end.offset, 8);
+
+        // Underscores as digit separators.
+        assert_token!("1_000_000", NumericConstant);
+        assert_token!("0x_FF_FF", NumericConstant);
+        assert_token!("0b_1010_0101", NumericConstant);
+        assert_token!("1_", NumericConstant);
+        assert_token!("_1000", IdentifierOrKeyword);
+        assert_tokens!("SELECT 1_000.000_1e1_0", ["SELECT", "1_000.000_1e1_0"]);
+
+        // Leading decimal point.
+        assert_tokens!("SELECT .5 + x", ["SELECT", ".5", "+", "x"]);
+        assert_tokens!("SELECT (.5e-2)", ["SELECT", "(", ".5e-2", ")"]);
+        assert_tokens!("SELECT schema.table", ["SELECT", "schema", ".", "table"]);
+        assert_tokens!("SELECT 5.", ["SELECT", "5."]);
+        assert_tokens!("SELECT 1..2", ["SELECT", "1.", ".2"]);
+        assert_tokens!("SELECT .5.5", ["SELECT", ".5", ".5"]);
+        assert_tokens!("SELECT 1e5.5", ["SELECT", "1e5", ".5"]);
+
+        // Scientific notation.
+        assert_token!("2E+10", NumericConstant);
+        assert_token!(".5e7", NumericConstant);
+        assert_token!("1.e5", NumericConstant);
+        assert_tokens!("SELECT 1.5e-3", ["SELECT", "1.5e-3"]);
+        assert_tokens!("SELECT 1e", ["SELECT", "1e"]);
+        assert_tokens!("SELECT 1e+", ["SELECT", "1e+"]);
+        assert_tokens!("SELECT (1e-)", ["SELECT", "(", "1e-", ")"]);
+        assert_tokens!("SELECT 1e-3-1E+3", ["SELECT", "1e-3", "-", "1E+3"]);
+
+        // Binary and octal constants.
+        assert_token!("0b0101", NumericConstant);
+        assert_token!("0B1", NumericConstant);
+        assert_token!("0b", NumericConstant);
+        assert_token!("0b2", NumericConstant);
+        assert_token!("0o78", NumericConstant);
+        assert_tokens!("SELECT 0b101+0o7", ["SELECT", "0b101", "+", "0o7"]);
+
+        // Hexadecimal constants.
+        assert_token!("0xDEADbeef", NumericConstant);
+        assert_token!("0XE1", NumericConstant);
+        assert_token!("0x", NumericConstant);
+        assert_token!("0X", NumericConstant);
+        assert_tokens!("SELECT 0xFF, 0x1e-3", ["SELECT", "0xFF", ",", "0x1e", "-", "3"]);
+
+        // Should break invalid numeric constants.
         assert_tokens!("0xg", ["0x", "g"]);
         assert_tokens!("1.9eg", ["1.9e", "g"]);
     }
 
+    #[test]
+    fn test_dollar_sign() {
+        assert_tokens!("SELECT $5 + $3; SELECT 2", ["SELECT", "$5", "+", "$3", ";"], ["SELECT", "2"]);
+        assert_tokens!("SELECT $12.34+$1e3, $", ["SELECT", "$12.34", "+", "$1e3", ",", "$"]);
+        assert_tokens!("SELECT $1$ || $2$", ["SELECT", "$1", "$", "||", "$2", "$"]);
+        assert_tokens!("SELECT $", ["SELECT", "$"]);
+        assert_token!("$12.34", NumericConstant);
+        assert_token!("$1e3", NumericConstant);
+        assert_token!("$42", ParameterMarker);
+
+        // Without dollar-quoted strings, `$tag$` is not a delimiter.
+        let options = || Options { dollar_quoted_strings: false, ..Default::default() };
+        assert_tokens_with_options!("SELECT $$1$$", options(), ["SELECT", "$", "$1", "$", "$"]);
+        assert_tokens_with_options!("SELECT $a$;SELECT 2", options(), ["SELECT", "$a", "$", ";"], ["SELECT", "2"]);
+    }
+
+    #[test]
+    fn test_stage_reference_token() {
+        let options = || Options { stage_references: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "COPY INTO t FROM @my_stage/data/file.csv.gz;LIST @~/staged",
+            options(),
+            ["COPY", "INTO", "t", "FROM", "@my_stage/data/file.csv.gz", ";"],
+            ["LIST", "@~/staged"]
+        );
+        assert_tokens_with_options!(
+            "PUT file:///tmp/f.csv @%my-table, @db.schema.stage",
+            options(),
+            ["PUT", "file", ":", "/", "/", "/", "tmp", "/", "f", ".", "csv", "@%my-table", ",", "@db.schema.stage"]
+        );
+        let statement = Tokenizer::new("LIST @s/a.csv", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_stage_reference());
+        assert_eq!(statement.tokens()[1].start.offset, 5);
+        assert_eq!(statement.tokens()[1].end.offset, 13);
+
+        // Without the option, `@` is a parameter marker.
+        assert_tokens!("LIST @s/a.csv", ["LIST", "@s", "/", "a", ".", "csv"]);
+    }
+
+    #[test]
+    fn test_go_batch_separator() {
+        let options = || Options { go_batch_separator: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT 1;\nSELECT 2;\nGO\nSELECT category FROM t\n  go 5  \nSELECT 3",
+            options(),
+            ["SELECT", "1", ";", "SELECT", "2", ";", "GO"],
+            ["SELECT", "category", "FROM", "t", "go 5"],
+            ["SELECT", "3"]
+        );
+        assert_tokens_with_options!(
+            "SELECT go FROM t\nGOTO label\nGO x\r\nGo\r\n",
+            options(),
+            ["SELECT", "go", "FROM", "t", "GOTO", "label", "GO", "x", "Go"]
+        );
+        let statement = Tokenizer::new("SELECT 1\nGO 2\n", options()).next().unwrap();
+        assert!(statement.tokens()[2].is_statement_delimiter());
+        assert_eq!(statement.sql(), "SELECT 1\nGO 2");
+
+        // Without the option, `GO` is a regular identifier or keyword.
+        assert_tokens!("SELECT 1\nGO\nSELECT 2", ["SELECT", "1", "GO", "SELECT", "2"]);
+    }
+
+    #[test]
+    fn test_slash_terminator() {
+        let options = || Options { slash_terminator: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT 4 / 2 FROM dual;\nBEGIN\n  x := 1;\n  y := x/2;\nEND;\n/\nSELECT 1 FROM dual;",
+            options(),
+            ["SELECT", "4", "/", "2", "FROM", "dual", ";"],
+            ["BEGIN", "x", ":=", "1", ";", "y", ":=", "x", "/", "2", ";", "END", ";", "/"],
+            ["SELECT", "1", "FROM", "dual", ";"]
+        );
+        assert_tokens_with_options!(
+            "CREATE OR REPLACE PROCEDURE p IS\nBEGIN\n  NULL;\nEND;\n  /  \nDECLARE\n  n NUMBER;\nBEGIN\n  NULL;\nEND;\n/",
+            options(),
+            ["CREATE", "OR", "REPLACE", "PROCEDURE", "p", "IS", "BEGIN", "NULL", ";", "END", ";", "/"],
+            ["DECLARE", "n", "NUMBER", ";", "BEGIN", "NULL", ";", "END", ";", "/"]
+        );
+        assert_tokens_with_options!(
+            "CREATE TABLE t (a INT);\nSELECT 1 FROM dual\n/",
+            options(),
+            ["CREATE", "TABLE", "t", "(", "a", "INT", ")", ";"],
+            ["SELECT", "1", "FROM", "dual", "/"]
+        );
+        let statement = Tokenizer::new("BEGIN NULL; END;\n/\n", options()).next().unwrap();
+        assert!(statement.tokens()[5].is_statement_delimiter());
+        assert_eq!(statement.sql(), "BEGIN NULL; END;\n/");
+
+        // Without the option, `/` is a division operator.
+        assert_tokens!("BEGIN NULL; END;\n/", ["BEGIN", "NULL", ";"], ["END", ";"], ["/"]);
+    }
+
+    #[test]
+    fn test_routine_body_blocks() {
+        let options = || Options { routine_body_blocks: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "CREATE PROCEDURE p AS BEGIN SET NOCOUNT ON; SELECT 1; END; SELECT 2;",
+            options(),
+            ["CREATE", "PROCEDURE", "p", "AS", "BEGIN", "SET", "NOCOUNT", "ON", ";", "SELECT", "1", ";", "END", ";"],
+            ["SELECT", "2", ";"]
+        );
+        // Nested blocks, `BEGIN TRY`/`BEGIN CATCH`, `CASE ... END` and transaction statements.
+        let sql = "CREATE OR ALTER PROC p AS\nBEGIN\n  BEGIN TRY\n    BEGIN TRAN;\n    \
+                   SELECT CASE WHEN 1 = 1 THEN 1 END;\n    COMMIT;\n  END TRY\n  BEGIN CATCH\n    \
+                   IF 1 = 1 BEGIN ROLLBACK; END;\n  END CATCH;\nEND;\nSELECT 2;";
+        let statements: Vec<_> = Tokenizer::new(sql, options()).collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql().ends_with("END CATCH;\nEND;"));
+        assert_eq!(statements[1].sql(), "SELECT 2;");
+
+        // Strings, comments and bracketed identifiers are not affecting the nesting.
+        assert_tokens_with_options!(
+            "CREATE FUNCTION f() RETURNS INT AS BEGIN /* END */ -- END\n\
+             RETURN (SELECT [End] FROM t WHERE x = 'END'); END; SELECT 2",
+            options(),
+            [
+                "CREATE",
+                "FUNCTION",
+                "f",
+                "(",
+                ")",
+                "RETURNS",
+                "INT",
+                "AS",
+                "BEGIN",
+                "/* END */",
+                "-- END",
+                "RETURN",
+                "(",
+                "SELECT",
+                "[",
+                "End",
+                "]",
+                "FROM",
+                "t",
+                "WHERE",
+                "x",
+                "=",
+                "'END'",
+                ")",
+                ";",
+                "END",
+                ";"
+            ],
+            ["SELECT", "2"]
+        );
+
+        // `GO` is not separating statements inside the body.
+        let options = || Options { routine_body_blocks: true, go_batch_separator: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "CREATE TRIGGER t ON x AFTER INSERT AS BEGIN\nGO\nEND\nGO\nSELECT 1",
+            options(),
+            ["CREATE", "TRIGGER", "t", "ON", "x", "AFTER", "INSERT", "AS", "BEGIN", "GO", "END", "GO"],
+            ["SELECT", "1"]
+        );
+
+        // SQLite triggers, a missing `END` ends the statement at the end of the input.
+        let sqlite = || Options::for_dialect(Dialect::Sqlite);
+        assert_tokens_with_options!(
+            "CREATE TEMP TRIGGER tr AFTER INSERT ON t BEGIN UPDATE \"begin\" SET a = CASE WHEN 1 THEN 'begin' END; \
+             DELETE FROM u; END; SELECT 1; CREATE TRIGGER tr2 BEFORE DELETE ON t BEGIN SELECT 1; SELECT 2",
+            sqlite(),
+            [
+                "CREATE",
+                "TEMP",
+                "TRIGGER",
+                "tr",
+                "AFTER",
+                "INSERT",
+                "ON",
+                "t",
+                "BEGIN",
+                "UPDATE",
+                "\"begin\"",
+                "SET",
+                "a",
+                "=",
+                "CASE",
+                "WHEN",
+                "1",
+                "THEN",
+                "'begin'",
+                "END",
+                ";",
+                "DELETE",
+                "FROM",
+                "u",
+                ";",
+                "END",
+                ";"
+            ],
+            ["SELECT", "1", ";"],
+            ["CREATE", "TRIGGER", "tr2", "BEFORE", "DELETE", "ON", "t", "BEGIN", "SELECT", "1", ";", "SELECT", "2"]
+        );
+
+        // Other statements are not affected, neither are the routines without the option.
+        assert_tokens_with_options!("BEGIN TRAN; SELECT 1;", options(), ["BEGIN", "TRAN", ";", "SELECT", "1", ";"]);
+        assert_tokens!(
+            "CREATE PROCEDURE p AS BEGIN SELECT 1; END",
+            ["CREATE", "PROCEDURE", "p", "AS", "BEGIN", "SELECT", "1", ";"],
+            ["END"]
+        );
+    }
+
+    #[test]
+    fn test_stored_program_blocks() {
+        let options = || Options { stored_program_blocks: true, ..Default::default() };
+        let sql = "CREATE DEFINER = 'root'@'%' PROCEDURE p(n INT)\nBEGIN\n  DECLARE i INT DEFAULT 0;\n  \
+                   l: LOOP\n    SET i = i + 1;\n    IF i > n THEN LEAVE l; END IF;\n  END LOOP l;\n  \
+                   WHILE i > 0 DO SET i = i - 1; END WHILE;\n  REPEAT SET i = i + 1; UNTIL i > n END REPEAT;\n  \
+                   CASE i WHEN 0 THEN SELECT 0; ELSE SELECT IF(i > 1, 'a', 'b'); END CASE;\n  \
+                   DROP TABLE IF EXISTS t;\n  SELECT REPEAT('x', 3);\nEND;\nSELECT 2;";
+        let statements: Vec<_> = Tokenizer::new(sql, options()).collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql().ends_with("SELECT REPEAT('x', 3);\nEND;"));
+        assert_eq!(statements[1].sql(), "SELECT 2;");
+
+        // PL/pgSQL blocks without dollar quotes, `IF()` is a function.
+        let sql = "CREATE OR REPLACE FUNCTION f() RETURNS INT AS\nDECLARE i INT := 0;\nBEGIN\n  \
+                   WHILE i < 10 LOOP i := i + 1; END LOOP;\n  FOR r IN SELECT * FROM t LOOP NULL; END LOOP;\n  \
+                   IF (i > 5) THEN RETURN IF(i > 8, 1, 0); ELSIF i > 2 THEN RETURN 2; END IF;\n  RETURN 0;\nEND;\n\
+                   SELECT 2;";
+        let statements: Vec<_> = Tokenizer::new(sql, options()).collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql().ends_with("RETURN 0;\nEND;"));
+        assert_eq!(statements[1].sql(), "SELECT 2;");
+
+        // Trigger with a single `IF` statement as body.
+        assert_tokens_with_options!(
+            "CREATE TRIGGER t BEFORE INSERT ON x FOR EACH ROW IF NEW.a < 0 THEN SET NEW.a = 0; END IF; SELECT 1",
+            options(),
+            [
+                "CREATE", "TRIGGER", "t", "BEFORE", "INSERT", "ON", "x", "FOR", "EACH", "ROW", "IF", "NEW", ".", "a",
+                "<", "0", "THEN", "SET", "NEW", ".", "a", "=", "0", ";", "END", "IF", ";"
+            ],
+            ["SELECT", "1"]
+        );
+
+        // Strings and comments are not affecting the nesting.
+        assert_tokens_with_options!(
+            "CREATE EVENT e ON SCHEDULE EVERY 1 DAY DO BEGIN /* END */ SELECT 'END'; END; SELECT 1",
+            options(),
+            [
+                "CREATE",
+                "EVENT",
+                "e",
+                "ON",
+                "SCHEDULE",
+                "EVERY",
+                "1",
+                "DAY",
+                "DO",
+                "BEGIN",
+                "/* END */",
+                "SELECT",
+                "'END'",
+                ";",
+                "END",
+                ";"
+            ],
+            ["SELECT", "1"]
+        );
+
+        // Other statements are not affected, neither are the stored programs without the option.
+        assert_tokens_with_options!(
+            "CREATE TABLE t (a INT); BEGIN;",
+            options(),
+            ["CREATE", "TABLE", "t", "(", "a", "INT", ")", ";"],
+            ["BEGIN", ";"]
+        );
+        assert_tokens!(
+            "CREATE PROCEDURE p() BEGIN SELECT 1; END",
+            ["CREATE", "PROCEDURE", "p", "(", ")", "BEGIN", "SELECT", "1", ";"],
+            ["END"]
+        );
+    }
+
+    #[test]
+    fn test_stored_program_blocks_large_body() {
+        // The nesting is tracked incrementally, the time spent on each delimiter is not growing with the body.
+        let options = Options { stored_program_blocks: true, ..Default::default() };
+        let sql = format!("CREATE PROCEDURE p()\nBEGIN\n{}END;\nSELECT 2;", "  SET @i = @i + 1;\n".repeat(20_000));
+        let statements: Vec<_> = Tokenizer::new(&sql, options.clone()).collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql().ends_with("SET @i = @i + 1;\nEND;"));
+        assert_eq!(statements[1].sql(), "SELECT 2;");
+
+        // The `IF`, `LOOP`, `WHILE`, `REPEAT` and `CASE` blocks are resolved as their tokens are captured.
+        let block = "  WHILE i < 10 LOOP i := i + 1; END LOOP;\n  IF i > 5 THEN i := IF(i > 8, 1, 0); END IF;\n  \
+                     DROP TABLE IF EXISTS t;\n  CASE i WHEN 0 THEN SELECT REPEAT('x', 3); END CASE;\n";
+        let sql = format!(
+            "CREATE FUNCTION f() RETURNS INT AS\nDECLARE i INT := 0;\nBEGIN\n{}END;\nSELECT 2;",
+            block.repeat(2_000)
+        );
+        let statements: Vec<_> = Tokenizer::new(&sql, options).collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql().ends_with("END CASE;\nEND;"));
+        assert_eq!(statements[1].sql(), "SELECT 2;");
+    }
+
+    #[test]
+    fn test_begin_atomic_blocks() {
+        let options = || Options { begin_atomic_blocks: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "CREATE FUNCTION f() RETURNS INT LANGUAGE SQL BEGIN ATOMIC SELECT 1; SELECT 2; END; SELECT 3;",
+            options(),
+            [
+                "CREATE", "FUNCTION", "f", "(", ")", "RETURNS", "INT", "LANGUAGE", "SQL", "BEGIN", "ATOMIC", "SELECT",
+                "1", ";", "SELECT", "2", ";", "END", ";"
+            ],
+            ["SELECT", "3", ";"]
+        );
+
+        // Nested compound statements (DB2).
+        let sql =
+            "CREATE PROCEDURE p() BEGIN ATOMIC\n  IF x THEN\n    BEGIN\n      SET y = CASE WHEN z THEN 1 END;\n    \
+                   END;\n  END IF;\n  WHILE y > 0 DO SET y = y - 1; END WHILE;\nEND;\nSELECT 1;";
+        let statements: Vec<_> = Tokenizer::new(sql, Options::for_dialect(Dialect::Db2)).collect();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql().ends_with("END WHILE;\nEND;"));
+        assert!(statements[0].is_terminated());
+
+        // A nested compound statement closed before an `IF` statement (PostgreSQL).
+        let sql = "BEGIN ATOMIC BEGIN SELECT 1; END; IF x THEN SELECT 2; END IF; END; SELECT 3;";
+        let statements: Vec<_> = Tokenizer::new(sql, Options::for_dialect(Dialect::PostgreSql)).collect();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1].sql(), "SELECT 3;");
+
+        // An unterminated block ends with the input.
+        let statements: Vec<_> = Tokenizer::new("BEGIN ATOMIC SELECT 1; SELECT 2;", options()).collect();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(statements[0].sql(), "BEGIN ATOMIC SELECT 1; SELECT 2;");
+        assert!(!statements[0].is_terminated());
+
+        // Many statements in a block.
+        let sql = format!("BEGIN ATOMIC {} END; SELECT 1;", "SELECT 1; ".repeat(10_000));
+        assert_eq!(Tokenizer::new(&sql, options()).count(), 2);
+
+        // Without the option (the default), the block is split.
+        assert_eq!(Tokenizer::new("BEGIN ATOMIC SELECT 1; END;", Options::default()).count(), 2);
+        assert_tokens_with_options!("BEGIN; SELECT 1;", options(), ["BEGIN", ";"], ["SELECT", "1", ";"]);
+    }
+
+    #[test]
+    fn test_delimiter_command() {
+        let options = || Options { interpret_delimiter_command: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT 1;\n-- routines\nDELIMITER //\nCREATE PROCEDURE p() BEGIN SELECT 1; END//\ndelimiter ;\nSELECT 2;",
+            options(),
+            ["SELECT", "1", ";"],
+            ["-- routines", "DELIMITER", "//"],
+            ["CREATE", "PROCEDURE", "p", "(", ")", "BEGIN", "SELECT", "1", ";", "END", "//"],
+            ["delimiter", ";"],
+            ["SELECT", "2", ";"]
+        );
+        assert_tokens_with_options!(
+            "DELIMITER $$\nCREATE FUNCTION f() RETURNS INT BEGIN RETURN 1; END$$\nDELIMITER ;",
+            options(),
+            ["DELIMITER", "$$"],
+            ["CREATE", "FUNCTION", "f", "(", ")", "RETURNS", "INT", "BEGIN", "RETURN", "1", ";", "END", "$$"],
+            ["DELIMITER", ";"]
+        );
+        // Not at the start of a statement or a line.
+        assert_tokens_with_options!("SELECT delimiter FROM t;", options(), ["SELECT", "delimiter", "FROM", "t", ";"]);
+        assert_tokens_with_options!("SELECT\nDELIMITER //", options(), ["SELECT", "DELIMITER", "/", "/"]);
+
+        let statement = Tokenizer::new("DELIMITER //\n", options()).next().unwrap();
+        assert_eq!(statement.sql(), "DELIMITER //");
+        assert_eq!(statement.tokens()[1].start.column, 11);
+
+        // Without the option, `DELIMITER` is a regular identifier or keyword.
+        assert_tokens!("DELIMITER //\nSELECT 1;", ["DELIMITER", "/", "/", "SELECT", "1", ";"]);
+    }
+
+    #[test]
+    fn test_terminator_directive() {
+        let options = || Options { interpret_terminator_directive: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT '--#SET TERMINATOR @';\n--#SET TERMINATOR @\nCREATE PROCEDURE p() BEGIN SELECT 1; END@\n--#set terminator ;\nSELECT 2;",
+            options(),
+            ["SELECT", "'--#SET TERMINATOR @'", ";"],
+            ["--#SET TERMINATOR @"],
+            ["CREATE", "PROCEDURE", "p", "(", ")", "BEGIN", "SELECT", "1", ";", "END", "@"],
+            ["--#set terminator ;"],
+            ["SELECT", "2", ";"]
+        );
+        let statement = Tokenizer::new("--#SET TERMINATOR @\n", options()).next().unwrap();
+        assert!(statement.is_empty());
+        assert!(statement.tokens()[0].is_comment());
+
+        // Without the option, the directive is a regular comment.
+        assert_tokens!(
+            "--#SET TERMINATOR @\nSELECT 1@ SELECT 2;",
+            ["--#SET TERMINATOR @", "SELECT", "1", "@", "SELECT", "2", ";"]
+        );
+    }
+
+    #[test]
+    fn test_set_term() {
+        let options = || Options { interpret_set_term: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SET TERM ^;\nCREATE PROCEDURE p AS BEGIN SUSPEND; END^\nset term ; ^\nSELECT 1;SET  TERM ^; ;SELECT 2^;",
+            options(),
+            ["SET", "TERM", "^", ";"],
+            ["CREATE", "PROCEDURE", "p", "AS", "BEGIN", "SUSPEND", ";", "END", "^"],
+            ["set", "term", ";", "^"],
+            ["SELECT", "1", ";"],
+            ["SET", "TERM", "^;", ";"],
+            ["SELECT", "2", "^;"]
+        );
+        let statement = Tokenizer::new("SET TERM !! ;", options()).next().unwrap();
+        assert_eq!(statement.sql(), "SET TERM !! ;");
+        assert_eq!(statement.terminator(), Some(";"));
+        assert_eq!(statement.keywords(), ["SET"]);
+
+        // `SET` statements that are not changing the terminator are regular statements.
+        assert_tokens_with_options!(
+            "SET TERM;\nSET x = 1;",
+            options(),
+            ["SET", "TERM", ";"],
+            ["SET", "x", "=", "1", ";"]
+        );
+
+        // Without the option, the statement is terminated by the first `;`.
+        assert_tokens!("SET TERM ^ ;\nSELECT 1^", ["SET", "TERM", "^", ";"], ["SELECT", "1", "^"]);
+    }
+
+    #[test]
+    fn test_split_on_blank_lines() {
+        let options = || Options { split_on_blank_lines: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "\n\nSELECT 1\nFROM t\n\n \t\r\n\nSELECT 2 -- two\n\nSELECT $$\n\n$$, /*\n\n*/ (\n\n3);\nSELECT 4",
+            options(),
+            ["SELECT", "1", "FROM", "t"],
+            ["SELECT", "2", "-- two"],
+            ["SELECT", "$$\n\n$$", ",", "/*\n\n*/", "(", "3", ")", ";"],
+            ["SELECT", "4"]
+        );
+        let statement = Tokenizer::new("SELECT 1\r\n\r\nSELECT 2", options()).next().unwrap();
+        assert_eq!(statement.sql(), "SELECT 1");
+
+        // Without the option, blank lines are whitespace.
+        assert_tokens!("SELECT 1\n\nSELECT 2", ["SELECT", "1", "SELECT", "2"]);
+    }
+
+    #[test]
+    fn test_word_delimiter() {
+        let options = || Options { statement_delimiter: "GO".to_string(), ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT CATEGORY_ID, GOAL, MARGOLIS, 'GO', _GO, GO_ FROM t GO SELECT 1\nGO\nSELECT 2 GO",
+            options(),
+            [
+                "SELECT",
+                "CATEGORY_ID",
+                ",",
+                "GOAL",
+                ",",
+                "MARGOLIS",
+                ",",
+                "'GO'",
+                ",",
+                "_GO",
+                ",",
+                "GO_",
+                "FROM",
+                "t",
+                "GO"
+            ],
+            ["SELECT", "1", "GO"],
+            ["SELECT", "2", "GO"]
+        );
+
+        // A delimiter following a digit is not at a word boundary: `2GO` is a numeric constant and an identifier.
+        let statements: Vec<_> = Tokenizer::new("SELECT 2GO", options()).collect();
+        assert_eq!(statements.len(), 1);
+        let tokens = statements[0].tokens();
+        assert_eq!(tokens.as_str_array(), ["SELECT", "2", "GO"]);
+        assert!(tokens[1].is_numeric_constant());
+        assert!(tokens[2].is_identifier_or_keyword());
+        assert!(!statements[0].is_terminated());
+        assert!(Tokenizer::new("SELECT 2 GO", options()).next().unwrap().is_terminated());
+
+        // Delimiters made of non-identifier characters are not affected.
+        assert_tokens_with_options!(
+            "SELECT a//SELECT b//",
+            Options { statement_delimiter: "//".to_string(), ..Default::default() },
+            ["SELECT", "a", "//"],
+            ["SELECT", "b", "//"]
+        );
+    }
+
+    #[test]
+    fn test_delimiter_case_insensitive() {
+        let options = || Options {
+            statement_delimiter: "GO".to_string(),
+            delimiter_case_insensitive: true,
+            ..Default::default()
+        };
+        assert_tokens_with_options!(
+            "SELECT 1 GO SELECT 2 go SELECT 3 Go SELECT gone",
+            options(),
+            ["SELECT", "1", "GO"],
+            ["SELECT", "2", "go"],
+            ["SELECT", "3", "Go"],
+            ["SELECT", "gone"]
+        );
+        let statement = Tokenizer::new("SELECT 1 gO", options()).next().unwrap();
+        assert!(statement.tokens()[2].is_statement_delimiter());
+        assert_eq!(statement.terminator(), Some("gO"));
+
+        // Without the option, only the exact spelling is a delimiter.
+        assert_tokens_with_options!(
+            "SELECT 1 go GO",
+            Options { statement_delimiter: "GO".to_string(), ..Default::default() },
+            ["SELECT", "1", "go", "GO"]
+        );
+    }
+
+    #[test]
+    fn test_delimiter_requires_own_line() {
+        let options = |delimiter: &str| Options {
+            statement_delimiter: delimiter.to_string(),
+            delimiter_requires_own_line: true,
+            ..Default::default()
+        };
+        assert_tokens_with_options!("SELECT 1 GO 2", options("GO"), ["SELECT", "1", "GO", "2"]);
+        assert_tokens_with_options!(
+            "SELECT 1\nGO\nSELECT 2\n  GO  \r\nSELECT 3\nGO",
+            options("GO"),
+            ["SELECT", "1", "GO"],
+            ["SELECT", "2", "GO"],
+            ["SELECT", "3", "GO"]
+        );
+        assert_tokens_with_options!(
+            "SELECT 1; SELECT 2\n;\nSELECT 3;",
+            options(";"),
+            ["SELECT", "1", ";", "SELECT", "2", ";"],
+            ["SELECT", "3", ";"]
+        );
+    }
+
+    #[test]
+    fn test_skip_empty_statements() {
+        let options = || Options { skip_empty_statements: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT 1;;\n;  -- done\n/*!40101 SET NAMES utf8 */;\n/* c */ ;SELECT 2;\n",
+            options(),
+            ["SELECT", "1", ";"],
+            ["-- done", "/*!40101 SET NAMES utf8 */", ";"],
+            ["SELECT", "2", ";"]
+        );
+        let statements: Vec<_> = Tokenizer::new("\n;\nSELECT 1;", options()).collect();
+        assert_eq!(statements.len(), 1);
+        assert_eq!((statements[0].start().line, statements[0].start().column, statements[0].start().offset), (3, 1, 3));
+        assert_eq!(Tokenizer::new("; -- nothing", options()).count(), 0);
+    }
+
+    #[test]
+    fn test_exclude_delimiter_token() {
+        let options = || Options { include_delimiter_token: false, ..Default::default() };
+        assert_tokens_with_options!("SELECT 1;\n;SELECT 2", options(), ["SELECT", "1"], [], ["SELECT", "2"]);
+        let statements: Vec<_> = Tokenizer::new("SELECT 1;\n;SELECT 2", options()).collect();
+        assert_eq!(statements[0].sql(), "SELECT 1");
+        assert_eq!(statements[0].terminator(), Some(";"));
+        assert!(statements[1].is_empty());
+        assert_eq!(statements[1].sql(), "");
+        assert_eq!(statements[1].start().offset, 10);
+        assert_eq!(statements[2].terminator(), None);
+        assert_eq!(
+            (statements[2].start().line, statements[2].start().column, statements[2].start().offset),
+            (2, 2, 11)
+        );
+    }
+
+    #[test]
+    fn test_copy_from_stdin_data() {
+        let options = || Options { copy_from_stdin_data: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "COPY t (a, b) FROM stdin;\n1\ta;b\n2\t\\.\n\\.\nSELECT 1;\nCOPY t TO stdout;\nSELECT 2;",
+            options(),
+            ["COPY", "t", "(", "a", ",", "b", ")", "FROM", "stdin", ";"],
+            ["1\ta;b\n2\t\\.\n\\."],
+            ["SELECT", "1", ";"],
+            ["COPY", "t", "TO", "stdout", ";"],
+            ["SELECT", "2", ";"]
+        );
+        let statements: Vec<_> = Tokenizer::new("COPY t FROM STDIN;\r\n1\r\n\\.\r\nSELECT 1;", options()).collect();
+        assert!(statements[1].is_copy_data());
+        assert_eq!(statements[1].sql(), "1\r\n\\.");
+        assert_eq!((statements[1].start().line, statements[1].start().column), (2, 1));
+        assert_eq!((statements[2].start().line, statements[2].start().column), (4, 1));
+
+        // The data is captured up to the end of the input if the terminator is missing.
+        assert_tokens_with_options!(
+            "COPY t FROM stdin;\n1;\n2",
+            options(),
+            ["COPY", "t", "FROM", "stdin", ";"],
+            ["1;\n2"]
+        );
+
+        // Without the option, the data is tokenized as SQL.
+        assert_tokens!("COPY t FROM stdin;\n1;\n\\.", ["COPY", "t", "FROM", "stdin", ";"], ["1", ";"], ["\\", "."]);
+    }
+
+    #[test]
+    fn test_dialects() {
+        let options = Options::for_dialect;
+        // The generic dialect behaves like the default options.
+        assert_tokens_with_options!("SELECT 1 # one\nGO", options(Dialect::Generic), ["SELECT", "1", "# one", "GO"]);
+        assert_tokens_with_options!("SELECT a #> b", options(Dialect::PostgreSql), ["SELECT", "a", "#>", "b"]);
+        assert_tokens_with_options!("SELECT 5--2", options(Dialect::MySql), ["SELECT", "5", "-", "-", "2"]);
+        assert_tokens_with_options!(
+            "SELECT 1\nGO\nSELECT 2",
+            options(Dialect::SqlServer),
+            ["SELECT", "1", "GO"],
+            ["SELECT", "2"]
+        );
+        assert_tokens_with_options!("SELECT &v FROM t", options(Dialect::Oracle), ["SELECT", "&v", "FROM", "t"]);
+        assert_tokens_with_options!(".tables\nSELECT 1", options(Dialect::Sqlite), [".tables"], ["SELECT", "1"]);
+        assert_tokens_with_options!("LIST @s/p", options(Dialect::Snowflake), ["LIST", "@s/p"]);
+        assert_tokens_with_options!("SELECT '''a'''", options(Dialect::BigQuery), ["SELECT", "'''a'''"]);
+        assert_tokens_with_options!("SELECT { c } 1", options(Dialect::Informix), ["SELECT", "{ c }", "1"]);
+        assert_tokens_with_options!(
+            "--#SET TERMINATOR @\nSELECT 1@",
+            options(Dialect::Db2),
+            ["--#SET TERMINATOR @"],
+            ["SELECT", "1", "@"]
+        );
+        assert_tokens_with_options!(
+            "SET TERM ^ ;\nSELECT 1^",
+            options(Dialect::Firebird),
+            ["SET", "TERM", "^", ";"],
+            ["SELECT", "1", "^"]
+        );
+    }
+
+    #[test]
+    fn test_directive_comments() {
+        let options = || Options { honor_directive_comments: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "-- dialect: tsql\nSELECT 1;\nGO\n-- loose-sqlparse: dialect=mysql delimiter=// unknown=1 hash_comments=maybe\nSELECT 2; # two\n//-- loose-sqlparse: hash_comments=false\nSELECT 3 # 4//",
+            options(),
+            ["-- dialect: tsql", "SELECT", "1", ";", "GO"],
+            ["-- loose-sqlparse: dialect=mysql delimiter=// unknown=1 hash_comments=maybe", "SELECT", "2", ";"],
+            ["# two", "//"],
+            ["-- loose-sqlparse: hash_comments=false", "SELECT", "3", "#", "4", "//"]
+        );
+        let statement = Tokenizer::new("-- dialect: unknown\nSELECT 1 GO", options()).next().unwrap();
+        assert!(statement.tokens()[0].is_comment());
+        assert_eq!(statement.tokens().as_str_array(), ["-- dialect: unknown", "SELECT", "1", "GO"]);
+
+        // A directive making the options unusable is ignored.
+        for directive in ["delimiter=", "delimiter=--", "delimiter='", "delimiter=/*x", "delimiter=#", "delimiter={"] {
+            let sql = format!("-- loose-sqlparse: brace_comments=true {directive}\nSELECT 1;SELECT 2 {{3}};");
+            let statements: Vec<_> = Tokenizer::new(&sql, options()).collect();
+            assert_eq!(statements.len(), 2, "{directive}");
+            assert_eq!(statements[1].tokens().as_str_array(), ["SELECT", "2", "{", "3", "}", ";"], "{directive}");
+        }
+        assert_tokens_with_options!(
+            "-- loose-sqlparse: delimiter=# hash_comments=false\nSELECT 1;SELECT 2#-- loose-sqlparse: hash_comments=true\nSELECT 3#",
+            options(),
+            ["-- loose-sqlparse: delimiter=# hash_comments=false", "SELECT", "1", ";"],
+            ["SELECT", "2", "#"],
+            ["-- loose-sqlparse: hash_comments=true", "SELECT", "3", "#"]
+        );
+
+        // Without the option, directive comments are regular comments.
+        assert_tokens!(
+            "-- loose-sqlparse: delimiter=//\nSELECT 1//SELECT 2;",
+            ["-- loose-sqlparse: delimiter=//", "SELECT", "1", "/", "/", "SELECT", "2", ";"]
+        );
+    }
+
+    #[test]
+    fn test_backslash_g_terminators() {
+        let options = || Options { backslash_g_terminators: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT 1\\GSELECT 2\\g SELECT '\\G' FROM t; SELECT 3 \\n",
+            options(),
+            ["SELECT", "1", "\\G"],
+            ["SELECT", "2", "\\g"],
+            ["SELECT", "'\\G'", "FROM", "t", ";"],
+            ["SELECT", "3", "\\", "n"]
+        );
+        let statement = Tokenizer::new("SELECT * FROM t\\G", options()).next().unwrap();
+        assert!(statement.tokens()[4].is_statement_delimiter());
+        assert_eq!(statement.terminator(), Some("\\G"));
+
+        // Without the option, `\G` is not a statement delimiter.
+        assert_tokens!("SELECT 1\\G", ["SELECT", "1", "\\", "G"]);
+    }
+
+    #[test]
+    fn test_psql_meta_commands() {
+        let options = || Options { psql_meta_commands: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "\\connect mydb\n\\set ON_ERROR_STOP on \r\nSELECT 'a\n\\b', 1 \\ 2\n  \\gexec\nSELECT 3;",
+            options(),
+            ["\\connect mydb"],
+            ["\\set ON_ERROR_STOP on"],
+            ["SELECT", "'a\n\\b'", ",", "1", "\\", "2"],
+            ["\\gexec"],
+            ["SELECT", "3", ";"]
+        );
+        let mut statements = Tokenizer::new("-- init\n\\i other.sql\nSELECT 1", options());
+        let statement = statements.next().unwrap();
+        assert!(statement.is_client_command());
+        assert!(statement.tokens()[1].is_client_command());
+        assert_eq!(statement.sql(), "-- init\n\\i other.sql");
+        let statement = statements.next().unwrap();
+        assert!(!statement.is_client_command());
+        assert_eq!((statement.start().line, statement.start().column), (3, 1));
+
+        // Without the option, `\` is a regular token.
+        assert_tokens!("\\connect mydb\nSELECT 1;", ["\\", "connect", "mydb", "SELECT", "1", ";"]);
+    }
+
+    #[test]
+    fn test_sqlite_dot_commands() {
+        let options = || Options { sqlite_dot_commands: true, ..Default::default() };
+        assert_tokens_with_options!(
+            ".mode csv\n.import data.csv t\nSELECT a\n.b FROM t;\n.5;\n  .tables\n",
+            options(),
+            [".mode csv"],
+            [".import data.csv t"],
+            ["SELECT", "a", ".", "b", "FROM", "t", ";"],
+            [".5", ";"],
+            [".tables"]
+        );
+        let statement = Tokenizer::new("/* setup */\n.headers on", options()).next().unwrap();
+        assert!(statement.is_client_command());
+        assert!(statement.tokens()[1].is_client_command());
+
+        // Without the option, `.` is a regular token.
+        assert_tokens!(".mode csv\nSELECT 1;", [".", "mode", "csv", "SELECT", "1", ";"]);
+    }
+
+    #[test]
+    fn test_sqlplus_script_includes() {
+        let options = || Options { sqlplus_script_includes: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "@schema/tables.sql\n@@relative.sql\nSELECT *\n@var FROM t;\n  @ no_file.sql",
+            options(),
+            ["@schema/tables.sql"],
+            ["@@relative.sql"],
+            ["SELECT", "*", "@var", "FROM", "t", ";"],
+            ["@ no_file.sql"]
+        );
+        let statement = Tokenizer::new("-- tables\n@create/tables.sql", options()).next().unwrap();
+        assert!(statement.is_client_command());
+        assert_eq!(statement.tokens()[1].value.as_ref(), "@create/tables.sql");
+
+        // Stage references are still captured when not at the start of a statement.
+        assert_tokens_with_options!(
+            "@install.sql\nLIST @my_stage/path;",
+            Options { sqlplus_script_includes: true, stage_references: true, ..Default::default() },
+            ["@install.sql"],
+            ["LIST", "@my_stage/path", ";"]
+        );
+
+        // Without the option, `@` is a parameter marker.
+        assert_tokens!("@install.sql\nSELECT 1;", ["@install", ".", "sql", "SELECT", "1", ";"]);
+    }
+
+    #[test]
+    fn test_snowsql_bang_commands() {
+        let options = || Options { snowsql_bang_commands: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "!set variable_substitution=true\n!source file.sql\nSELECT 1\n!= 2;\n  !exit",
+            options(),
+            ["!set variable_substitution=true"],
+            ["!source file.sql"],
+            ["SELECT", "1", "!=", "2", ";"],
+            ["!exit"]
+        );
+        let statement = Tokenizer::new("-- load\n!source file.sql\n", options()).next().unwrap();
+        assert!(statement.is_client_command());
+        assert_eq!(statement.sql(), "-- load\n!source file.sql");
+
+        // Without the option, `!` is an operator.
+        assert_tokens!("!source file.sql", ["!", "source", "file", ".", "sql"]);
+    }
+
+    #[test]
+    fn test_substitution_variables() {
+        let options = || Options { substitution_variables: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT * FROM &schema..emp_&&suffix WHERE id = &id AND a &&b OR c & d",
+            options(),
+            [
+                "SELECT", "*", "FROM", "&schema.", ".", "emp_", "&&suffix", "WHERE", "id", "=", "&id", "AND", "a",
+                "&&b", "OR", "c", "&", "d"
+            ]
+        );
+        let statement = Tokenizer::new("SELECT &col FROM t", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_substitution_variable());
+
+        // Without the option, `&` and `&&` are operators.
+        assert_tokens!("SELECT a &&b, c & d", ["SELECT", "a", "&&", "b", ",", "c", "&", "d"]);
+    }
+
     #[test]
     fn test_comma() {
         assert_tokens!("1, 2, /* , */", ["1", ",", "2", ",", "/* , */"]);
     }
 
+    #[test]
+    fn test_positional_parameters() {
+        assert_tokens!(
+            "SELECT $1, $2::int, $body$ $3 $body$ FROM t WHERE id = $10 AND a = $$$1$$",
+            [
+                "SELECT",
+                "$1",
+                ",",
+                "$2",
+                "::",
+                "int",
+                ",",
+                "$body$ $3 $body$",
+                "FROM",
+                "t",
+                "WHERE",
+                "id",
+                "=",
+                "$10",
+                "AND",
+                "a",
+                "=",
+                "$$$1$$"
+            ]
+        );
+        let statement = Tokenizer::new("SELECT $1::int, $$ $2 $$, $2", Options::default()).next().unwrap();
+        assert!(statement.tokens()[1].is_parameter_marker());
+        assert!(statement.tokens()[5].is_quoted_identifier_or_constant());
+        assert!(statement.tokens()[7].is_parameter_marker());
+    }
+
+    #[test]
+    fn test_variable_token() {
+        let options = || Options { at_variables: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT @rownum := @rownum + 1, @@version, @@session.sql_mode;SET @x = 1",
+            options(),
+            ["SELECT", "@rownum", ":=", "@rownum", "+", "1", ",", "@@version", ",", "@@session.sql_mode", ";"],
+            ["SET", "@x", "=", "1"]
+        );
+        assert_tokens_with_options!(
+            "SELECT @'weird name', @`x`, @\"it\"\"s\", @@ROWCOUNT",
+            options(),
+            ["SELECT", "@'weird name'", ",", "@`x`", ",", "@\"it\"\"s\"", ",", "@@ROWCOUNT"]
+        );
+        let statement = Tokenizer::new("SET @x=@@y", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_variable());
+        assert!(statement.tokens()[3].is_variable());
+        assert_eq!(statement.tokens()[3].start.offset, 7);
+
+        // A quoted name spanning several lines, the positions of the next tokens are the same as after a string.
+        let start = |sql| {
+            let statement = Tokenizer::new(sql, options()).next().unwrap();
+            (statement.tokens()[2].start.line, statement.tokens()[2].start.column)
+        };
+        assert_eq!(start("SET @\"a\nb\" = 1"), start("SET 'a\nb' = 1"));
+        assert_eq!(start("SET @`a\r\n``b` = 1"), start("SET 'a\r\n''b' = 1"));
+
+        // Stage references are taking precedence over variables.
+        let options = Options { at_variables: true, stage_references: true, ..Default::default() };
+        let statement = Tokenizer::new("LIST @s/a.csv", options).next().unwrap();
+        assert!(statement.tokens()[1].is_stage_reference());
+
+        // Without the option, `@x` is a parameter marker.
+        assert_tokens!("SET @x = 1", ["SET", "@x", "=", "1"]);
+        assert!(Tokenizer::new("SET @x", Options::default()).next().unwrap().tokens()[1].is_parameter_marker());
+    }
+
+    #[test]
+    fn test_pyformat_placeholders() {
+        let options = || Options { pyformat_placeholders: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "INSERT INTO t VALUES (%s, %(user_id)s);SELECT a % 2, a%b FROM t WHERE c LIKE '%s%' AND d = %s",
+            options(),
+            ["INSERT", "INTO", "t", "VALUES", "(", "%s", ",", "%(user_id)s", ")", ";"],
+            [
+                "SELECT", "a", "%", "2", ",", "a", "%", "b", "FROM", "t", "WHERE", "c", "LIKE", "'%s%'", "AND", "d",
+                "=", "%s"
+            ]
+        );
+        assert_tokens_with_options!(
+            "SELECT 10%step, %(a b)s",
+            options(),
+            ["SELECT", "10", "%", "step", ",", "%", "(", "a", "b", ")", "s"]
+        );
+        let statement = Tokenizer::new("SELECT %(id)s", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_parameter_marker());
+        assert_eq!(statement.tokens()[1].end.offset, 13);
+
+        // Without the option, `%` is the modulo operator.
+        assert_tokens!("SELECT %s", ["SELECT", "%", "s"]);
+    }
+
+    #[test]
+    fn test_template_placeholders() {
+        let options = || Options { template_placeholders: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT * FROM ${tableName} WHERE id = #{userId} # comment\n AND a = #{b, jdbcType={x}};SELECT $$a$$",
+            options(),
+            [
+                "SELECT",
+                "*",
+                "FROM",
+                "${tableName}",
+                "WHERE",
+                "id",
+                "=",
+                "#{userId}",
+                "# comment",
+                "AND",
+                "a",
+                "=",
+                "#{b, jdbcType={x}}",
+                ";"
+            ],
+            ["SELECT", "$$a$$"]
+        );
+        assert_tokens_with_options!("SELECT #{a", options(), ["SELECT", "#{a"]);
+        assert_tokens_with_options!("SELECT #{", options(), ["SELECT", "#{"]);
+        let statement = Tokenizer::new("SELECT #{\nid}", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_parameter_marker());
+        assert_eq!(statement.tokens()[1].end.line, 2);
+        assert_eq!(statement.tokens()[1].end.column, 3);
+
+        // Without the option, `#` starts a comment.
+        assert_tokens!("SELECT #{userId}", ["SELECT", "#{userId}"]);
+        assert!(Tokenizer::new("SELECT #{userId}", Options::default()).next().unwrap().tokens()[1].is_comment());
+    }
+
+    #[test]
+    fn test_hive_substitutions() {
+        let options = || Options { hive_substitutions: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SET x=${env:HOME};SELECT * FROM ${hivevar:db}.t LIMIT ${spark.sql.shuffle.partitions}",
+            options(),
+            ["SET", "x", "=", "${env:HOME}", ";"],
+            ["SELECT", "*", "FROM", "${hivevar:db}", ".", "t", "LIMIT", "${spark.sql.shuffle.partitions}"]
+        );
+        let statement = Tokenizer::new("SELECT ${a}, $$ ${b} $$", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_substitution_variable());
+        assert!(statement.tokens()[3].is_quoted_identifier_or_constant());
+        let options = Options { hive_substitutions: true, template_placeholders: true, ..Default::default() };
+        let statement = Tokenizer::new("SELECT ${a}, #{b}", options).next().unwrap();
+        assert!(statement.tokens()[1].is_substitution_variable());
+        assert!(statement.tokens()[3].is_parameter_marker());
+
+        // Without the option, dollar-quoted strings are still supported.
+        assert_tokens!("SELECT $tag$ ${a} $tag$, $1", ["SELECT", "$tag$ ${a} $tag$", ",", "$1"]);
+    }
+
+    #[test]
+    fn test_jdbc_escapes() {
+        let options = || Options { jdbc_escapes: true, ..Default::default() };
+        let statement = Tokenizer::new("SELECT {fn CONCAT(a, b)}, {d '2024-01-01'} FROM t", options()).next().unwrap();
+        assert_eq!(statement.tokens()[1].value.as_ref(), "{");
+        assert!(statement.tokens()[2].is_fragment());
+        assert_eq!(statement.tokens()[3].value.as_ref(), "}");
+        assert!(statement.tokens()[6].is_fragment());
+        assert_eq!(statement.tokens()[6].children().unwrap().len(), 2);
+        assert_tokens_with_options!(
+            "{call my_proc(?)};{? = call f}",
+            options(),
+            ["{", "call", "my_proc", "(", "?", ")", "}", ";"],
+            ["{", "?", "=", "call", "f", "}"]
+        );
+
+        // Unbalanced braces.
+        assert_tokens_with_options!("SELECT {fn f(a}", options(), ["SELECT", "{", "fn", "f", "(", "a", "}"]);
+        assert_tokens_with_options!("SELECT a} FROM {t", options(), ["SELECT", "a", "}", "FROM", "{", "t"]);
+
+        // Without the option, braces are not grouping tokens.
+        let statement = Tokenizer::new("{call p}", Options::default()).next().unwrap();
+        assert_eq!(statement.tokens().len(), 4);
+    }
+
+    #[test]
+    fn test_case_fragments() {
+        let options = || Options { case_fragments: true, ..Default::default() };
+        let sql = "SELECT CASE WHEN a THEN CASE b WHEN 1 THEN 'x' END ELSE (CASE END) END AS c FROM t;";
+        let statement = Tokenizer::new(sql, options()).next().unwrap();
+        let tokens = statement.tokens();
+        assert_eq!(statement.keywords(), ["SELECT", "AS", "FROM"]);
+        assert_eq!(tokens.len(), 7);
+        assert!(tokens[1].is_fragment());
+        assert_eq!(tokens[1].start.offset, 7);
+        assert_eq!(tokens[1].end.offset, 70);
+        assert!(sql[7..70].starts_with("CASE") && sql[7..70].ends_with("END"));
+        let case = tokens[1].children().unwrap();
+        assert_eq!(
+            case.as_str_array(),
+            [
+                "CASE", "WHEN", "a", "THEN", "CASE", "b", "WHEN", "1", "THEN", "'x'", "END", "ELSE", "(", "CASE",
+                "END", ")", "END"
+            ]
+        );
+        assert!(case[4].is_fragment());
+        assert_eq!(case[4].children().unwrap().len(), 7);
+        assert!(case[7].children().unwrap()[0].is_fragment());
+
+        // An unterminated `CASE` is closed by the statement delimiter or the end of the input.
+        let statement = Tokenizer::new("SELECT CASE WHEN a THEN 1; SELECT 2", options()).next().unwrap();
+        assert_eq!(statement.tokens().len(), 3);
+        assert_eq!(statement.tokens()[1].children().unwrap().len(), 5);
+        assert!(statement.tokens()[2].is_statement_delimiter());
+        let statement = Tokenizer::new("SELECT CASE WHEN a THEN 1", options()).next().unwrap();
+        assert_eq!(statement.tokens().len(), 2);
+        assert_eq!(statement.tokens()[1].end.offset, 25);
+
+        // `END CASE` is closing a `CASE` statement (MySQL).
+        let statement = Tokenizer::new("CASE x WHEN 1 THEN SELECT 1 END CASE", options()).next().unwrap();
+        assert_eq!(statement.tokens().len(), 2);
+        assert!(statement.tokens()[1].is_keyword_eq("CASE"));
+
+        // Without the option, `CASE` expressions are not grouped.
+        let statement = Tokenizer::new("SELECT CASE WHEN a THEN 1 END", Options::default()).next().unwrap();
+        assert!(!statement.tokens().iter().any(|t| t.is_fragment()));
+    }
+
+    #[test]
+    fn test_array_subscripts() {
+        let options = || Options { array_subscripts: true, ..Default::default() };
+        let statement =
+            Tokenizer::new("SELECT arr[1], matrix[1][2], arr[2:3], ARRAY[f(:a)]", options()).next().unwrap();
+        let tokens = statement.tokens();
+        assert!(tokens[2].is_punctuation() && tokens[2].value.as_ref() == "[");
+        assert_eq!(tokens[3].children().unwrap().as_str_array(), ["1"]);
+        assert!(tokens[4].is_punctuation() && tokens[4].value.as_ref() == "]");
+        assert!(tokens[8].is_fragment() && tokens[11].is_fragment());
+        assert_eq!(tokens[16].children().unwrap().as_str_array(), ["2", ":", "3"]);
+        assert!(!tokens[16].children().unwrap()[1].is_parameter_marker());
+        let array = tokens[21].children().unwrap();
+        assert!(array[2].children().unwrap()[0].is_parameter_marker());
+
+        // A `]` without an opening bracket is a regular token.
+        let statement = Tokenizer::new("SELECT a]b, c;", options()).next().unwrap();
+        assert_eq!(statement.tokens().as_str_array(), ["SELECT", "a", "]", "b", ",", "c", ";"]);
+        assert!(!statement.tokens().iter().any(|t| t.is_fragment()));
+
+        // An unbalanced `[` falls back to flat tokens, up to the delimiter, the end of the input or a parenthesis.
+        for sql in ["SELECT arr[1, 2; SELECT 3", "SELECT arr[1, 2"] {
+            let statement = Tokenizer::new(sql, options()).next().unwrap();
+            let tokens = statement.tokens();
+            assert!(tokens[2].is_any() && tokens[2].value.as_ref() == "[", "{sql}");
+            assert!(!tokens.iter().any(|t| t.is_fragment()), "{sql}");
+            let flat = Tokenizer::new(sql, Options::default()).next().unwrap();
+            assert_eq!(tokens.as_str_array(), flat.tokens().as_str_array(), "{sql}");
+        }
+        let statement = Tokenizer::new("SELECT f(arr[1, 2), 3", options()).next().unwrap();
+        let tokens = statement.tokens();
+        assert_eq!(tokens[2].value.as_ref(), "(");
+        assert_eq!(tokens[3].children().unwrap().as_str_array(), ["arr", "[", "1", ",", "2"]);
+        assert!(!tokens[3].children().unwrap().iter().any(|t| t.is_fragment()));
+        assert_eq!(tokens[4].value.as_ref(), ")");
+        let statement = Tokenizer::new("SELECT arr[1][2", options()).next().unwrap();
+        let tokens = statement.tokens();
+        assert_eq!(tokens[3].children().unwrap().as_str_array(), ["1"]);
+        assert!(tokens[5].is_any() && tokens[5].value.as_ref() == "[");
+        assert!(tokens[6].is_numeric_constant());
+
+        // Without the option, square brackets are not grouping tokens.
+        let statement = Tokenizer::new("SELECT arr[1]", Options::default()).next().unwrap();
+        assert!(!statement.tokens().iter().any(|t| t.is_fragment()));
+    }
+
     #[test]
     fn test_parameter_marker_token() {
-        assert_token!("?", ParameterMarker);
+        assert_token!("?", Placeholder);
+        assert_token!("?2", Placeholder);
         assert_token!("$1", ParameterMarker);
         assert_token!(":username", ParameterMarker);
         assert_token!("$username", ParameterMarker);
@@ -853,6 +3573,14 @@ mod tests {
             ["id", "=", "?", "AND", "name", "=", "'_prefix'", "||", "?", "||", "'_suffix'"]
         );
         assert_tokens!("id = $1 AND name = $2", ["id", "=", "$1", "AND", "name", "=", "$2"]);
+        assert_tokens!("(:a,:b)", ["(", ":a", ",", ":b", ")"]);
+        assert_tokens!(":a::int, ?::int, $a::int", [":a", "::", "int", ",", "?", "::", "int", ",", "$a", "::", "int"]);
+        assert_tokens!(":a = :a", [":a", "=", ":a"]);
+        assert_tokens!("a = ? AND b = ?2 OR c = ?10", ["a", "=", "?", "AND", "b", "=", "?2", "OR", "c", "=", "?10"]);
+        assert_tokens!(
+            "data ?| keys AND data ?& ? AND ?||'x'",
+            ["data", "?|", "keys", "AND", "data", "?&", "?", "AND", "?", "||", "'x'"]
+        );
         assert_tokens!(
             "id = :user_id AND name = :user_name",
             ["id", "=", ":user_id", "AND", "name", "=", ":user_name"]
@@ -878,6 +3606,18 @@ mod tests {
         assert_token!("<<", Operator);
         assert_token!(">>", Operator);
         assert_token!("::", Operator);
+        assert_token!(":=", Operator);
+        assert_token!("=>", Operator);
+        assert_token!("**", Operator);
+        assert_token!("&&", Operator);
+        assert_token!("->", Operator);
+        assert_token!("->>", Operator);
+        assert_token!("#>", Operator);
+        assert_token!("#>>", Operator);
+        assert_token!("@>", Operator);
+        assert_token!("<@", Operator);
+        assert_token!("?|", Operator);
+        assert_token!("?&", Operator);
         assert_token!("~*", Operator);
         assert_token!("!~", Operator);
         assert_token!("+", Operator);
@@ -898,6 +3638,139 @@ mod tests {
             ["1", "+", "2", "+", "3", "-", "4", "-", "5", "*", "6", "*", "7", "/", "8", "/", "9"]
         );
         assert_tokens!("123::TEXT '2024-08-22'::DATE", ["123", "::", "TEXT", "'2024-08-22'", "::", "DATE"]);
+        assert_tokens!(
+            "SELECT x::text, $1::int, x :: int, (y)::int, :p::int",
+            [
+                "SELECT", "x", "::", "text", ",", "$1", "::", "int", ",", "x", "::", "int", ",", "(", "y", ")", "::",
+                "int", ",", ":p", "::", "int"
+            ]
+        );
+        assert_tokens!("GRANT SELECT ON SCHEMA::dbo TO u", ["GRANT", "SELECT", "ON", "SCHEMA", "::", "dbo", "TO", "u"]);
+        assert_tokens!(
+            "SELECT data#>>'{a,b}' FROM t; SELECT 2;",
+            ["SELECT", "data", "#>>", "'{a,b}'", "FROM", "t", ";"],
+            ["SELECT", "2", ";"]
+        );
+        assert_tokens!(
+            "SELECT a->'b'->>'c', a#>'{b}', a@>b, a<@b, a?|b, a?&b # comment",
+            [
+                "SELECT",
+                "a",
+                "->",
+                "'b'",
+                "->>",
+                "'c'",
+                ",",
+                "a",
+                "#>",
+                "'{b}'",
+                ",",
+                "a",
+                "@>",
+                "b",
+                ",",
+                "a",
+                "<@",
+                "b",
+                ",",
+                "a",
+                "?|",
+                "b",
+                ",",
+                "a",
+                "?&",
+                "b",
+                "# comment"
+            ]
+        );
+        let statement = Tokenizer::new("first_name || ' ' || last_name", Options::default()).next().unwrap();
+        let offsets: Vec<_> = statement.tokens().iter().map(|t| (t.start.offset, t.end.offset)).collect();
+        assert_eq!(offsets, [(0, 10), (11, 13), (14, 17), (18, 20), (21, 30)]);
+        assert!(statement.tokens()[1].is_operator() && statement.tokens()[3].is_operator());
+        assert_tokens!("a | |b && c &/**/& d", ["a", "|", "|", "b", "&&", "c", "&", "/**/", "&", "d"]);
+        let options = Options { additional_operators: vec!["<=>".to_string(), "".to_string()], ..Default::default() };
+        assert_tokens_with_options!("a<=>b<=c", options, ["a", "<=>", "b", "<=", "c"]);
+        let options = Options { additional_operators: vec!["!".to_string(), "|/".to_string()], ..Default::default() };
+        assert_tokens_with_options!("a!=b |/ c!d", options, ["a", "!=", "b", "|/", "c", "!", "d"]);
+        let statement = Tokenizer::new("a->>'b'", Options::default()).next().unwrap();
+        assert_eq!((statement.tokens()[1].start.offset, statement.tokens()[1].end.offset), (1, 4));
+        let options = Options { at_variables: true, stage_references: true, ..Default::default() };
+        assert_tokens_with_options!("a<@b, a@>b", options, ["a", "<", "@b", ",", "a", "@>", "b"]);
+        let statement = Tokenizer::new("x::text", Options::default()).next().unwrap();
+        assert!(statement.tokens()[1].is_operator());
+        assert_eq!((statement.tokens()[1].start.offset, statement.tokens()[1].end.offset), (1, 3));
+        assert_tokens!(
+            "a>=b AND a<>b OR a!=b||c AND f(x=>1, y:=2**3) --c\n/*c*/-1",
+            [
+                "a", ">=", "b", "AND", "a", "<>", "b", "OR", "a", "!=", "b", "||", "c", "AND", "f", "(", "x", "=>",
+                "1", ",", "y", ":=", "2", "**", "3", ")", "--c", "/*c*/", "-", "1"
+            ]
+        );
+        let statement = Tokenizer::new("a<=b", Options::default()).next().unwrap();
+        let offsets: Vec<_> = statement.tokens().iter().map(|t| (t.start.offset, t.end.offset)).collect();
+        assert_eq!(offsets, [(0, 1), (1, 3), (3, 4)]);
+        assert!(statement.tokens()[1].is_operator());
+    }
+
+    #[test]
+    fn test_greedy_operators() {
+        let options = || Options { greedy_operators: true, hash_comments: false, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT a<->-1, a @@ b, ||/ 27, a*-1, a=?, a@-1, a <-> b--c\n, a+/*c*/b, @x;SELECT 2",
+            options(),
+            [
+                "SELECT", "a", "<->", "-", "1", ",", "a", "@@", "b", ",", "||/", "27", ",", "a", "*", "-", "1", ",",
+                "a", "=?", ",", "a", "@-", "1", ",", "a", "<->", "b", "--c", ",", "a", "+", "/*c*/", "b", ",", "@x",
+                ";"
+            ],
+            ["SELECT", "2"]
+        );
+        let statement = Tokenizer::new("a<->b", options()).next().unwrap();
+        assert!(statement.tokens()[1].is_operator());
+        assert_eq!((statement.tokens()[1].start.offset, statement.tokens()[1].end.offset), (1, 4));
+        let options = Options { greedy_operators: true, statement_delimiter: "//".to_string(), ..Default::default() };
+        assert_tokens_with_options!("SELECT a<>//SELECT 2", options, ["SELECT", "a", "<>", "//"], ["SELECT", "2"]);
+
+        // Without the option, custom operators are split.
+        assert_tokens!("a <-> b", ["a", "<", "->", "b"]);
+    }
+
+    #[test]
+    fn test_operator_classification() {
+        // Operators are classified the same way at the top level of a statement and in nested fragments.
+        let statement = Tokenizer::new("SELECT a=1+b*c, (a=1+b*c);", Options::default()).next().unwrap();
+        let kinds = |tokens: &Tokens| tokens.iter().map(|t| t.is_operator()).collect::<Vec<_>>();
+        let top_level = kinds(statement.tokens());
+        assert_eq!(top_level, [false, false, true, false, true, false, true, false, false, false, false, false, false]);
+        assert_eq!(kinds(statement.tokens()[10].children().unwrap()), top_level[1..8]);
+        for index in [8, 9, 11] {
+            assert!(statement.tokens()[index].is_punctuation());
+        }
+        assert!(statement.tokens()[12].is_statement_delimiter());
+    }
+
+    #[test]
+    fn test_hash_operators_without_hash_comments() {
+        let options = || Options { hash_comments: false, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT data #>> '{a,b}' FROM t; SELECT 2;",
+            options(),
+            ["SELECT", "data", "#>>", "'{a,b}'", "FROM", "t", ";"],
+            ["SELECT", "2", ";"]
+        );
+        assert_tokens_with_options!(
+            "SELECT data#>'{a}', p # q",
+            options(),
+            ["SELECT", "data", "#>", "'{a}'", ",", "p", "#", "q"]
+        );
+        let statement = Tokenizer::new("SELECT a # b", options()).next().unwrap();
+        assert!(statement.tokens()[2].is_operator());
+
+        // With the default options, `#` starts a comment (MySQL) unless followed by `>`.
+        assert_tokens!(
+            "SELECT data #>> '{a,b}' FROM t # comment; SELECT 2;",
+            ["SELECT", "data", "#>>", "'{a,b}'", "FROM", "t", "# comment; SELECT 2;"]
+        );
     }
 
     #[test]
@@ -915,6 +3788,14 @@ mod tests {
         assert_token!("$tag$with_tag$tag$", QuotedIdentifierOrConstant);
         assert_token!("$x$__$__$x$", QuotedIdentifierOrConstant);
         assert_tokens!("$$O'Reilly", ["$$O'Reilly"]);
+
+        // Unterminated dollar-quoted strings are captured up to the end of the input.
+        assert_tokens!("SELECT $$", ["SELECT", "$$"]);
+        assert_tokens!("SELECT $a$;SELECT 1", ["SELECT", "$a$;SELECT 1"]);
+        let statement = Tokenizer::new("SELECT $a$\nx", Options::default()).next().unwrap();
+        assert!(statement.tokens()[1].is_unterminated());
+        assert_eq!(statement.tokens()[1].end.offset, 12);
+        assert_eq!(statement.tokens()[1].end.line, 2);
     }
 
     #[test]
@@ -924,11 +3805,26 @@ mod tests {
         assert_token!("/** comment **/", Comment);
         assert_token!("/* comment */", Comment);
         assert_token!("/* /*nested*/comment */", Comment);
-        assert_token!("/*+ SET_VAR(foreign_key_checks=OFF) */", Comment);
         assert_tokens!("BEGIN /* not closed...", ["BEGIN", "/* not closed..."]);
         assert_tokens!("BEGIN /* not closed...; BEGIN", ["BEGIN", "/* not closed...; BEGIN"]);
         assert_tokens!("/* line 1 \r\n line 2 */", ["/* line 1 \r\n line 2 */"]);
 
+        // versioned comment (MySQL)
+        assert_token!("/*!40101 SET NAMES utf8 */", VersionedComment);
+        assert_token!("/*! STRAIGHT_JOIN */", VersionedComment);
+        assert_token!("/*!50613 SET @a = 1 /* nested */ */", VersionedComment);
+        assert_tokens!("/*!40101 SET NAMES utf8 */;SELECT 1", ["/*!40101 SET NAMES utf8 */", ";"], ["SELECT", "1"]);
+        assert_token!("/* ! not versioned */", Comment);
+
+        // optimizer hint (Oracle, MySQL)
+        assert_token!("/*+ SET_VAR(foreign_key_checks=OFF) */", Hint);
+        assert_token!("/*+ INDEX(t idx) */", Hint);
+        assert_token!("/* + not a hint */", Comment);
+        assert_tokens!("SELECT --+ FULL(t)\n* FROM t", ["SELECT", "--+ FULL(t)", "*", "FROM", "t"]);
+        let statement = Tokenizer::new("SELECT --+ FULL(t)\n-- comment", Options::default()).next().unwrap();
+        assert!(statement.tokens()[1].is_hint());
+        assert!(statement.tokens()[2].is_comment());
+
         // single-line comment
         assert_tokens!(
             "-- comment\n--comment\n# comment\n#comment",
@@ -936,6 +3832,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_double_dash_comment_requiring_space() {
+        let options = || Options { require_space_after_double_dash: true, ..Default::default() };
+        assert_tokens_with_options!("SELECT a--b", options(), ["SELECT", "a", "-", "-", "b"]);
+        assert_tokens_with_options!("SELECT 5--2;", options(), ["SELECT", "5", "-", "-", "2", ";"]);
+        assert_tokens_with_options!("SELECT a-- b; c", options(), ["SELECT", "a", "-- b; c"]);
+        assert_tokens_with_options!("SELECT a--\tb", options(), ["SELECT", "a", "--\tb"]);
+        assert_tokens_with_options!("SELECT a--\nFROM t--", options(), ["SELECT", "a", "--", "FROM", "t", "--"]);
+
+        // Without the option, any `--` starts a comment.
+        assert_tokens!("SELECT a--b", ["SELECT", "a", "--b"]);
+    }
+
+    #[test]
+    fn test_brace_comment_token() {
+        let options = || Options { brace_comments: true, ..Default::default() };
+        assert_tokens_with_options!(
+            "SELECT { comment; } 1; { a { nested; } comment }",
+            options(),
+            ["SELECT", "{ comment; }", "1", ";"],
+            ["{ a { nested; } comment }"]
+        );
+        assert_tokens_with_options!("SELECT 1 { not closed; ...", options(), ["SELECT", "1", "{ not closed; ..."]);
+
+        let statement = Tokenizer::new("{ line 1\nline 2 } SELECT", options()).next().unwrap();
+        assert!(statement.tokens()[0].is_comment());
+        assert_eq!(statement.tokens()[0].end.line, 2);
+        assert_eq!(statement.tokens()[1].start.line, 2);
+
+        // Without the option, braces are regular tokens.
+        assert_tokens!("SELECT {a} 1", ["SELECT", "{", "a", "}", "1"]);
+    }
+
     #[test]
     fn test_quoted_identifier_or_constant() {
         assert_token!(r#"''"#, QuotedIdentifierOrConstant); // empty
@@ -967,9 +3896,275 @@ mod tests {
         assert_eq!(s.len(), 0);
     }
 
+    #[test]
+    fn test_punctuation_token() {
+        assert_token!(",", Punctuation);
+        let statement = Tokenizer::new("SELECT a.b, f(x), 'a,b' FROM s.;SELECT a)", Options::default()).next().unwrap();
+        let punctuation: Vec<_> =
+            statement.tokens().iter().filter(|t| t.is_punctuation()).map(|t| t.value.as_ref()).collect();
+        assert_eq!(punctuation, [".", ",", "(", ")", ",", "."]);
+        assert!(statement.tokens()[7].children().unwrap()[0].is_identifier_or_keyword());
+        let statement = Tokenizer::new("SELECT a)", Options::default()).next().unwrap();
+        assert!(statement.tokens()[2].is_punctuation());
+        let statement = Tokenizer::new("SELECT s.", Options::default()).next().unwrap();
+        assert!(statement.tokens()[2].is_punctuation());
+        // Other characters are still captured as `Any` tokens.
+        let statement = Tokenizer::new("SELECT a\\b", Options::default()).next().unwrap();
+        assert!(statement.tokens().iter().any(|t| t.is_any()));
+    }
+
     #[test]
     fn test_some_random_syntax_edge_cases() {
-        assert_token!(".", Any);
+        assert_token!(".", Punctuation);
         assert_tokens!(".x2", [".", "x2"]);
     }
+
+    #[test]
+    fn test_multi_byte_offsets() {
+        fn assert_offsets(input: &str, tokens: &Tokens) {
+            for token in tokens.iter() {
+                match token.children() {
+                    Some(children) => assert_offsets(input, children),
+                    None => assert_eq!(&input[token.start.offset..token.end.offset], token.value.as_ref()),
+                }
+            }
+        }
+        let inputs = [
+            "SELECT 'héllo'; SELECT 2;",
+            "SELECT naïve, \"日本\", `ü` FROM t -- コメント\n/* ü */ WHERE x = 'ß' AND y = N'こんにちは';é;\nSELECT (ñ + 1)",
+            "é;ü;SELECT $$ü$$, $é, :ñ, 'ü'::text;😀",
+            "SELECT 1 # ü\n;SELECT 'unterminated ü",
+            "SELECT @ü, @@ß, [ñ], {ü}, &é, ${ü}\nGO\n/\nSELECT x[ü]",
+        ];
+        let dialects = [
+            Dialect::Generic,
+            Dialect::PostgreSql,
+            Dialect::MySql,
+            Dialect::SqlServer,
+            Dialect::Oracle,
+            Dialect::Sqlite,
+            Dialect::Snowflake,
+            Dialect::BigQuery,
+            Dialect::Informix,
+            Dialect::Db2,
+            Dialect::Firebird,
+            Dialect::DuckDb,
+            Dialect::ClickHouse,
+        ];
+        for (input, dialect) in inputs.iter().flat_map(|input| dialects.iter().map(move |dialect| (input, dialect))) {
+            let statements: Vec<_> = Tokenizer::new(input, Options::for_dialect(*dialect)).collect();
+            assert!(!statements.is_empty());
+            for statement in statements {
+                assert_offsets(input, statement.tokens());
+            }
+        }
+
+        // The columns are counted in characters.
+        let statement = Tokenizer::new("SELECT 'héllo', 日本", Options::default()).next().unwrap();
+        assert_eq!(statement.tokens()[1].end.column, 14);
+        assert_eq!(statement.tokens()[1].end.offset, 15);
+        assert_eq!(statement.tokens()[3].start.column, 17);
+        assert_eq!(statement.tokens()[3].end.column, 18);
+        let statement = Tokenizer::new("SELECT 'é'; SELECT 2;", Options::default()).nth(1).unwrap();
+        assert_eq!(statement.sql(), "SELECT 2;");
+        assert_eq!(statement.tokens()[0].start.column, 13);
+    }
+
+    #[test]
+    fn test_tab_width() {
+        fn positions(input: &str, tab_width: Option<usize>) -> Vec<(usize, usize, usize, usize)> {
+            let options = Options { tab_width, ..Default::default() };
+            let statement = Tokenizer::new(input, options).next().unwrap();
+            statement
+                .tokens()
+                .iter()
+                .map(|token| (token.start.line, token.start.column, token.end.line, token.end.column))
+                .collect()
+        }
+
+        // Tabs before the tokens.
+        assert_eq!(positions("\tSELECT\t1", None), vec![(1, 2, 1, 7), (1, 9, 1, 9)]);
+        assert_eq!(positions("\tSELECT\t1", Some(4)), vec![(1, 5, 1, 10), (1, 13, 1, 13)]);
+        assert_eq!(positions("\tSELECT\t1", Some(8)), vec![(1, 9, 1, 14), (1, 17, 1, 17)]);
+        assert_eq!(positions("SELECT\n\t\tx", Some(4)), vec![(1, 1, 1, 6), (2, 9, 2, 9)]);
+        assert_eq!(positions("SELECT\r\n  \tx", Some(4)), vec![(1, 1, 1, 6), (2, 5, 2, 5)]);
+
+        // Tabs inside strings.
+        assert_eq!(
+            positions("SELECT 'a\tb', x", Some(8)),
+            vec![(1, 1, 1, 6), (1, 8, 1, 18), (1, 19, 1, 19), (1, 21, 1, 21)]
+        );
+        assert_eq!(
+            positions("SELECT 'a\tb', x", None),
+            vec![(1, 1, 1, 6), (1, 8, 1, 12), (1, 13, 1, 13), (1, 15, 1, 15)]
+        );
+        assert_eq!(positions("SELECT '\n\tb'\tx", Some(4)), vec![(1, 1, 1, 6), (1, 8, 2, 6), (2, 9, 2, 9)]);
+
+        // Tabs after multi-byte characters.
+        assert_eq!(positions("SELECT 'é'\t1", Some(4)), vec![(1, 1, 1, 6), (1, 8, 1, 10), (1, 13, 1, 13)]);
+        assert_eq!(positions("SELECT '日本'\tx", Some(4)), vec![(1, 1, 1, 6), (1, 8, 1, 11), (1, 13, 1, 13)]);
+        assert_eq!(
+            positions("SELECT (é,\tb)", Some(4)),
+            vec![(1, 1, 1, 6), (1, 8, 1, 8), (1, 9, 1, 13), (1, 14, 1, 14)]
+        );
+    }
+
+    #[test]
+    fn test_line_breaks() {
+        fn positions(input: &str, options: Options) -> Vec<Vec<(usize, usize, usize, usize)>> {
+            Tokenizer::new(input, options)
+                .map(|statement| {
+                    statement
+                        .tokens()
+                        .iter()
+                        .map(|token| (token.start.line, token.start.column, token.end.line, token.end.column))
+                        .collect()
+                })
+                .collect()
+        }
+        let unicode_line_breaks = Options { unicode_line_breaks: true, ..Default::default() };
+
+        // CRLF, CR alone and mixed line endings.
+        let expected = vec![vec![(1, 1, 1, 6), (1, 8, 1, 8), (1, 9, 1, 9)], vec![(2, 1, 2, 6), (3, 3, 3, 3)]];
+        assert_eq!(positions("SELECT 1;\nSELECT\n  2", Options::default()), expected);
+        assert_eq!(positions("SELECT 1;\r\nSELECT\r\n  2", Options::default()), expected);
+        assert_eq!(positions("SELECT 1;\rSELECT\r  2", Options::default()), expected);
+        assert_eq!(positions("SELECT 1;\r\nSELECT\r  2", Options::default()), expected);
+        assert_eq!(positions("SELECT 1;\rSELECT\n  2", Options::default()), expected);
+        assert_eq!(
+            positions("SELECT a,\r\n b,\r c,\n d\r\r e", Options::default()),
+            vec![vec![
+                (1, 1, 1, 6),
+                (1, 8, 1, 8),
+                (1, 9, 1, 9),
+                (2, 2, 2, 2),
+                (2, 3, 2, 3),
+                (3, 2, 3, 2),
+                (3, 3, 3, 3),
+                (4, 2, 4, 2),
+                (6, 2, 6, 2)
+            ]]
+        );
+
+        // Line breaks inside tokens.
+        assert_eq!(
+            positions("SELECT 'a\rb', x /* c\r\n\rd */", Options::default()),
+            vec![vec![(1, 1, 1, 6), (1, 8, 2, 2), (2, 3, 2, 3), (2, 5, 2, 5), (2, 7, 4, 4)]]
+        );
+        assert_eq!(positions("-- a\rb\nSELECT", Options::default()), vec![vec![(1, 1, 2, 1), (3, 1, 3, 6)]]);
+
+        // Unicode line breaks.
+        assert_eq!(positions("SELECT\u{2028}1", Options::default()), vec![vec![(1, 1, 1, 6), (1, 8, 1, 8)]]);
+        assert_eq!(positions("SELECT\u{2028}1", unicode_line_breaks.clone()), vec![vec![(1, 1, 1, 6), (2, 1, 2, 1)]]);
+        assert_eq!(
+            positions("SELECT\u{0085}'a\u{2029}b',\u{2028}\u{2028}x", unicode_line_breaks.clone()),
+            vec![vec![(1, 1, 1, 6), (2, 1, 3, 2), (3, 3, 3, 3), (5, 1, 5, 1)]]
+        );
+        let options = Options { unicode_line_breaks: true, tab_width: Some(4), ..Default::default() };
+        assert_eq!(positions("SELECT\u{2028}\tx\r\ty", options), vec![vec![(1, 1, 1, 6), (2, 5, 2, 5), (3, 5, 3, 5)]]);
+
+        // The line breaks are not changing how statements are split.
+        let input = "SELECT 1; -- a\rSELECT 2;\u{2028}SELECT 3";
+        assert_eq!(Tokenizer::new(input, Options::default()).count(), 2);
+        assert_eq!(Tokenizer::new(input, unicode_line_breaks).count(), 2);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        fn diagnostics(input: &str, options: Options) -> Vec<(DiagnosticKind, usize, usize, usize)> {
+            Tokenizer::new(input, options)
+                .flat_map(|statement| statement.diagnostics().to_vec())
+                .map(|d| (d.kind, d.position.line, d.position.column, d.position.offset))
+                .collect()
+        }
+        use DiagnosticKind::*;
+        let options = Options::default;
+
+        assert!(diagnostics("SELECT 'a', \"b\", `c`, $$d$$, /* e */ 1;", options()).is_empty());
+        assert_eq!(diagnostics("SELECT 1;\nSELECT 'a", options()), vec![(UnterminatedString, 2, 8, 17)]);
+        assert_eq!(diagnostics("SELECT 'a'';", options()), vec![(UnterminatedString, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT E'a\\'", options()), vec![(UnterminatedString, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT X'1F", options()), vec![(UnterminatedString, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT b\"a", options()), vec![(UnterminatedString, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT \"a", options()), vec![(UnterminatedQuotedIdentifier, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT U&\"a", options()), vec![(UnterminatedQuotedIdentifier, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT `a", options()), vec![(UnterminatedQuotedIdentifier, 1, 8, 7)]);
+        assert_eq!(diagnostics("SELECT (1, /* a", options()), vec![(UnterminatedComment, 1, 12, 11)]);
+        assert_eq!(diagnostics("SELECT /* a /* b */", options()), vec![(UnterminatedComment, 1, 8, 7)]);
+        let brace_comments = Options { brace_comments: true, ..Default::default() };
+        assert_eq!(diagnostics("SELECT { a { b }", brace_comments), vec![(UnterminatedComment, 1, 8, 7)]);
+        let triple_quoted_strings = Options { triple_quoted_strings: true, ..Default::default() };
+        assert_eq!(diagnostics("SELECT '''a''", triple_quoted_strings), vec![(UnterminatedString, 1, 8, 7)]);
+
+        // Unterminated dollar-quoted strings are captured up to the end of the input.
+        for input in ["SELECT $$", "SELECT $$a$", "SELECT $tag$a$$", "SELECT $tag$a\n$ta"] {
+            let statement = Tokenizer::new(input, options()).next().unwrap();
+            assert_eq!(statement.tokens()[1].value.as_ref(), &input[7..]);
+            assert_eq!(diagnostics(input, options()), vec![(UnterminatedDollarQuotedString, 1, 8, 7)]);
+        }
+    }
+
+    #[test]
+    fn test_token_positions() {
+        // Compute the position of the character found at the given offset.
+        fn position(input: &str, offset: usize) -> (usize, usize) {
+            let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+            (input[..offset].matches('\n').count() + 1, input[line_start..offset].chars().count() + 1)
+        }
+        fn assert_positions(input: &str, tokens: &Tokens) {
+            for token in tokens.iter() {
+                if let Some(children) = token.children() {
+                    assert_positions(input, children);
+                    continue;
+                }
+                assert_eq!((token.start.line, token.start.column), position(input, token.start.offset), "{token:?}");
+                let last_char_offset = input[..token.end.offset].char_indices().next_back().unwrap().0;
+                assert_eq!((token.end.line, token.end.column), position(input, last_char_offset), "{token:?}");
+            }
+        }
+        let inputs = [
+            "SELECT a,\n  b /* x\n y */ c\n FROM 'l1\nl2' t;\n  SELECT 2",
+            "SELECT (a\n, b)\n\tx $$\n$$ y -- c\n  -- d\nx # e\nü",
+            "-- c\n  -- d\nx;\n\n  SELECT\n    (\n      1\n    )",
+            "SELECT 1\r\n, '''a\nb''', \"x\ny\", { c\n d } /*+ h\n */ @v\nGO\nSELECT #{a\n}, ${b\n}, E'\\\n'",
+        ];
+        let dialects = [
+            Dialect::Generic,
+            Dialect::PostgreSql,
+            Dialect::MySql,
+            Dialect::SqlServer,
+            Dialect::BigQuery,
+            Dialect::Informix,
+        ];
+        for (input, dialect) in inputs.iter().flat_map(|input| dialects.iter().map(move |dialect| (input, dialect))) {
+            let options = Options { template_placeholders: true, ..Options::for_dialect(*dialect) };
+            for statement in Tokenizer::new(input, options) {
+                assert_positions(input, statement.tokens());
+            }
+        }
+
+        // The span of a fragment is the content between its parentheses.
+        let input = "SELECT f( a, (b)\n), ()";
+        let statement = Tokenizer::new(input, Options::default()).next().unwrap();
+        let tokens = statement.tokens();
+        assert_eq!(&input[tokens[3].start.offset..tokens[3].end.offset], " a, (b)\n");
+        assert_eq!((tokens[3].start.line, tokens[3].start.column), (1, 10));
+        let nested = &tokens[3].children().unwrap()[3];
+        assert_eq!(&input[nested.start.offset..nested.end.offset], "b");
+        assert_eq!(tokens[7].start.offset, tokens[7].end.offset);
+
+        // An unclosed fragment ends with the input.
+        let input = "SELECT (a\n";
+        let statement = Tokenizer::new(input, Options::default()).next().unwrap();
+        assert_eq!(statement.sql(), input);
+        assert_eq!((statement.end().line, statement.end().column), (2, 0));
+
+        // Start positions of the tokens on the second and third lines of a statement.
+        let statement = Tokenizer::new("SELECT a, -- first\n  b\nFROM (\n  t)", Options::default()).next().unwrap();
+        let tokens = statement.tokens();
+        assert_eq!((tokens[4].start.line, tokens[4].start.column), (2, 3));
+        assert_eq!((tokens[5].start.line, tokens[5].start.column), (3, 1));
+        assert_eq!((tokens[7].start.line, tokens[7].start.column), (3, 7));
+        assert_eq!((tokens[7].end.line, tokens[7].end.column), (4, 3));
+    }
 }
@@ -1,22 +1,85 @@
+use crate::keywords::{is_keyword, is_reserved_keyword};
+use crate::tokenizer::is_raw_string_introducer;
 use crate::Position;
+use std::borrow::Cow;
 use std::convert::AsRef;
 use std::ops::{Deref, DerefMut};
 
 #[cfg(feature = "serialize")]
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 
+// The units that can follow the value of an interval literal.
+const INTERVAL_UNITS: [&str; 30] = [
+    "MICROSECOND",
+    "MILLISECOND",
+    "SECOND",
+    "MINUTE",
+    "HOUR",
+    "DAY",
+    "WEEK",
+    "MONTH",
+    "QUARTER",
+    "YEAR",
+    "MICROSECONDS",
+    "MILLISECONDS",
+    "SECONDS",
+    "MINUTES",
+    "HOURS",
+    "DAYS",
+    "WEEKS",
+    "MONTHS",
+    "QUARTERS",
+    "YEARS",
+    "SECOND_MICROSECOND",
+    "MINUTE_MICROSECOND",
+    "MINUTE_SECOND",
+    "HOUR_MICROSECOND",
+    "HOUR_SECOND",
+    "HOUR_MINUTE",
+    "DAY_MICROSECOND",
+    "DAY_SECOND",
+    "DAY_MINUTE",
+    "DAY_HOUR",
+];
+
 // A token extracted from the input string.
 #[derive(Debug)]
 pub enum TokenValue<'s> {
     /// Any token that does not match any of the other variants.
     Any(&'s str),
 
+    /// A punctuation character: `,`, `.`, and the parentheses (`(`, `)`) enclosing a [`TokenValue::Fragment`].
+    ///
+    /// The brackets and braces enclosing a fragment are also punctuation characters when
+    /// [`crate::Options::array_subscripts`] or [`crate::Options::jdbc_escapes`] is enabled.
+    Punctuation(&'s str),
+
     /// A comment.
     ///
     /// - Single-line comments start with `--` or '#' and continue to the end of the line.
     /// - Multi-line comments start with `/*` and end with `*/`.
     Comment(&'s str),
 
+    /// A MySQL versioned comment.
+    ///
+    /// The content of a versioned comment is executed by MySQL (if the server version is greater or equal to the
+    /// optional version number), so unlike [`TokenValue::Comment`] it's not ignored by [`crate::Statement::is_empty`].
+    ///
+    /// ```sql
+    /// /*!40101 SET NAMES utf8 */;
+    /// ```
+    VersionedComment(&'s str),
+
+    /// An optimizer hint (Oracle, MySQL).
+    ///
+    /// Hints are comments starting with `/*+` or `--+` that are meaningful to the optimizer. Like comments they are
+    /// ignored by [`crate::Statement::is_empty`].
+    ///
+    /// ```sql
+    /// SELECT /*+ INDEX(t idx) */ * FROM t;
+    /// ```
+    Hint(&'s str),
+
     /// A quoted identifier or a non numeric constant.
     ///
     /// - *Quoted identifiers* are enclosed in double quotes (`"`). They are identifiers (like a table name, column name,
@@ -41,6 +104,8 @@ pub enum TokenValue<'s> {
     ///   Notes:
     ///     - MySQL and MariaDB are also allowing single quotes (`'`) for string literals.
     ///     - PostgreSQL is also allowing dollar-quoted strings (`$tag$...$tag$`) for string literals.
+    ///     - String constants can be prefixed by an introducer immediately followed by the opening quote (`E'...'`,
+    ///       `N'...'`, `B'...'`, `X'...'`, `U&'...'`, `_utf8mb4'...'`). The introducer is part of the token.
     QuotedIdentifierOrConstant(&'s str),
 
     /// A Numeric Constant
@@ -58,6 +123,8 @@ pub enum TokenValue<'s> {
     ///
     /// For visual grouping, underscores can be inserted between digits (e.g. `1_000_000`).
     ///
+    /// Decimal constants can be followed by the Oracle `f` (`BINARY_FLOAT`) or `d` (`BINARY_DOUBLE`) suffixes.
+    ///
     /// Examples:
     ///
     /// ```sql
@@ -78,6 +145,8 @@ pub enum TokenValue<'s> {
     /// 0o_1_755
     /// 0xFFFF_FFFF
     /// 1.618_034
+    /// 1.5f
+    /// 3D
     /// ```
     NumericConstant(&'s str),
 
@@ -86,6 +155,10 @@ pub enum TokenValue<'s> {
     /// SQL identifiers and key words must begin with a letter (a-z, but also letters with diacritical marks and
     /// non-Latin letters) or an underscore (_). Subsequent characters in an identifier or key word can be letters,
     /// underscores, digits (0-9), or dollar signs ($).
+    ///
+    /// Use [`Token::is_keyword`], [`Token::is_reserved_keyword`] and [`Token::is_identifier`] to classify the token
+    /// using the built-in keyword table. When serialized, the type of the token is `Keyword` for reserved keywords,
+    /// `IdentifierOrKeyword` for non-reserved keywords and `Identifier` for any other word.
     IdentifierOrKeyword(&'s str),
 
     /// An operator
@@ -106,15 +179,90 @@ pub enum TokenValue<'s> {
     /// Parameter markers indicates where data values are to be bound to the query later when executed.
     ///
     /// ```sql
-    /// SELECT * FROM users WHERE id = ? AND name = ?;
+    /// SELECT * FROM users WHERE id = $1 AND name = :name;
     /// ```
     ///
-    /// - Question Mark (`?`) Syntax: Widely used in databases like SQLite, MySQL, PostgreSQL.
     /// - Dollar Sign (`$n`) Syntax: PostgreSQL.
     /// - Named Parameters with (`:`), (`$`) or (`@`) Syntax (ex: `:user_id`, `$user_id`, `@user_id`).
+    /// - Python DB-API (`%s`, `%(name)s`) Syntax: psycopg (see [`crate::Options::pyformat_placeholders`]).
+    /// - Template (`#{name}`, `${name}`) Syntax: MyBatis (see [`crate::Options::template_placeholders`]).
+    ///
+    /// The question mark markers (`?`, `?NNN`) are captured as [`TokenValue::Placeholder`].
     ParameterMarker(&'s str),
 
+    /// Question Mark Placeholder
+    ///
+    /// A positional (`?`) or numbered (`?NNN`) parameter marker: JDBC, ODBC, SQLite, MySQL.
+    ///
+    /// ```sql
+    /// SELECT * FROM users WHERE id = ? AND name = ?2;
+    /// ```
+    ///
+    /// Their indices are given by [`crate::Statement::placeholders`].
+    Placeholder(&'s str),
+
+    /// A user-defined or system variable (MySQL, SQL Server).
+    ///
+    /// Only captured if [`crate::Options::at_variables`] is enabled.
+    ///
+    /// ```sql
+    /// SELECT @rownum := @rownum + 1, @`my var`, @@version;
+    /// ```
+    Variable(&'s str),
+
+    /// A stage reference (Snowflake).
+    ///
+    /// Only captured if [`crate::Options::stage_references`] is enabled.
+    ///
+    /// ```sql
+    /// COPY INTO my_table FROM @my_stage/data/file.csv.gz;
+    /// ```
+    StageReference(&'s str),
+
+    /// A substitution variable (Oracle SQL*Plus, Hive).
+    ///
+    /// Only captured if [`crate::Options::substitution_variables`] or [`crate::Options::hive_substitutions`] is
+    /// enabled. The optional period terminating a SQL*Plus variable name is part of the token.
+    ///
+    /// ```sql
+    /// SELECT * FROM &schema..employees WHERE id = &&employee_id;
+    /// LOAD DATA INPATH '${hivevar:path}' INTO TABLE ${db}.logs;
+    /// ```
+    SubstitutionVariable(&'s str),
+
+    /// The inline data of a `COPY ... FROM STDIN` statement (PostgreSQL).
+    ///
+    /// Only captured if [`crate::Options::copy_from_stdin_data`] is enabled. The token spans all the lines following
+    /// the `COPY` statement, up to and including the `\.` terminating the data.
+    ///
+    /// ```text
+    /// COPY t (a, b) FROM stdin;
+    /// 1    one
+    /// 2    two
+    /// \.
+    /// ```
+    CopyData(&'s str),
+
+    /// A client command (psql meta-command, SQLite dot-command, SQL*Plus script include directive, SnowSQL command).
+    ///
+    /// Only captured if [`crate::Options::psql_meta_commands`], [`crate::Options::sqlite_dot_commands`],
+    /// [`crate::Options::sqlplus_script_includes`] or [`crate::Options::snowsql_bang_commands`] are enabled, the token
+    /// spans the whole line of the command including its arguments.
+    ///
+    /// ```text
+    /// \connect mydb
+    /// \set ON_ERROR_STOP on
+    /// .mode csv
+    /// @@relative.sql
+    /// !source file.sql
+    /// ```
+    ClientCommand(&'s str),
+
     /// A fragment of tokens, typically used for the content of parenthesis.
+    ///
+    /// The span of the fragment is the content between the parentheses (or brackets, braces) without them, so slicing
+    /// the input with the fragment's offsets yields the inner text (`2+1` for `(2+1)`). The span of a `CASE ... END`
+    /// fragment (see [`crate::Options::case_fragments`]) includes the `CASE` and `END` keywords.
     Fragment(Tokens<'s>),
 }
 
@@ -122,13 +270,22 @@ impl<'s> AsRef<str> for TokenValue<'s> {
     fn as_ref(&self) -> &str {
         match self {
             TokenValue::Any(value) => value,
+            TokenValue::Punctuation(value) => value,
             TokenValue::Comment(value) => value,
+            TokenValue::VersionedComment(value) => value,
+            TokenValue::Hint(value) => value,
             TokenValue::QuotedIdentifierOrConstant(value) => value,
             TokenValue::Operator(value) => value,
             TokenValue::StatementDelimiter(value) => value,
             TokenValue::NumericConstant(value) => value,
             TokenValue::IdentifierOrKeyword(value) => value,
             TokenValue::ParameterMarker(value) => value,
+            TokenValue::Placeholder(value) => value,
+            TokenValue::Variable(value) => value,
+            TokenValue::StageReference(value) => value,
+            TokenValue::SubstitutionVariable(value) => value,
+            TokenValue::CopyData(value) => value,
+            TokenValue::ClientCommand(value) => value,
             TokenValue::Fragment(_) => {
                 panic!("TokenValue::Fragment does not contain a single &str")
             }
@@ -165,6 +322,14 @@ impl<'s> Token<'s> {
         matches!(self.value, TokenValue::Comment(_))
     }
 
+    pub fn is_versioned_comment(&self) -> bool {
+        matches!(self.value, TokenValue::VersionedComment(_))
+    }
+
+    pub fn is_hint(&self) -> bool {
+        matches!(self.value, TokenValue::Hint(_))
+    }
+
     pub fn is_quoted_identifier_or_constant(&self) -> bool {
         matches!(self.value, TokenValue::QuotedIdentifierOrConstant(_))
     }
@@ -181,16 +346,24 @@ impl<'s> Token<'s> {
         matches!(self.value, TokenValue::Operator(_))
     }
 
+    pub fn is_punctuation(&self) -> bool {
+        matches!(self.value, TokenValue::Punctuation(_))
+    }
+
+    pub fn is_dot(&self) -> bool {
+        matches!(self.value, TokenValue::Punctuation("."))
+    }
+
     pub fn is_parenthesis(&self) -> bool {
         match &self.value {
-            TokenValue::Any(value) => *value == "(" || *value == ")",
+            TokenValue::Punctuation(value) => *value == "(" || *value == ")",
             _ => false,
         }
     }
 
     pub fn is_comma(&self) -> bool {
         match &self.value {
-            TokenValue::Any(value) => *value == ",",
+            TokenValue::Punctuation(value) => *value == ",",
             _ => false,
         }
     }
@@ -203,6 +376,368 @@ impl<'s> Token<'s> {
         matches!(self.value, TokenValue::ParameterMarker(_))
     }
 
+    pub fn is_placeholder(&self) -> bool {
+        matches!(self.value, TokenValue::Placeholder(_))
+    }
+
+    pub fn is_variable(&self) -> bool {
+        matches!(self.value, TokenValue::Variable(_))
+    }
+
+    pub fn is_stage_reference(&self) -> bool {
+        matches!(self.value, TokenValue::StageReference(_))
+    }
+
+    pub fn is_substitution_variable(&self) -> bool {
+        matches!(self.value, TokenValue::SubstitutionVariable(_))
+    }
+
+    pub fn is_copy_data(&self) -> bool {
+        matches!(self.value, TokenValue::CopyData(_))
+    }
+
+    pub fn is_client_command(&self) -> bool {
+        matches!(self.value, TokenValue::ClientCommand(_))
+    }
+
+    /// Returns whether the token is a keyword of the built-in keyword table, reserved or not.
+    ///
+    /// Non-reserved keywords (ex: `DATE`, `NAME`) can also be used as identifiers.
+    pub fn is_keyword(&self) -> bool {
+        matches!(self.value, TokenValue::IdentifierOrKeyword(value) if is_keyword(value))
+    }
+
+    /// Returns whether the token is a reserved keyword (ex: `SELECT`, `FROM`), which cannot be an unquoted identifier.
+    pub fn is_reserved_keyword(&self) -> bool {
+        matches!(self.value, TokenValue::IdentifierOrKeyword(value) if is_reserved_keyword(value))
+    }
+
+    /// Returns whether the token can be an identifier.
+    ///
+    /// Quoted identifiers are always identifiers, whatever their content. Unquoted words are identifiers unless they
+    /// are reserved keywords.
+    pub fn is_identifier(&self) -> bool {
+        match self.value {
+            TokenValue::IdentifierOrKeyword(value) => !is_reserved_keyword(value),
+            _ => self.is_quoted_identifier(),
+        }
+    }
+
+    // Check if the token is a quoted identifier (i.e. a quoted identifier using double quotes or backticks).
+    //
+    // The triple-quoted strings (`"""x"""`) are string constants (BigQuery), including the ones which can also be read
+    // as a quoted identifier made of doubled quotes.
+    pub(crate) fn is_quoted_identifier(&self) -> bool {
+        matches!(self.delimiter(), Some("\"" | "`")) && !self.is_triple_quoted()
+    }
+
+    // Check if the token is the given identifier or keyword (case-insensitive).
+    pub(crate) fn is_keyword_eq(&self, keyword: &str) -> bool {
+        match &self.value {
+            TokenValue::IdentifierOrKeyword(value) => value.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    // Check if the token is a string constant (i.e. a quoted constant using single quotes).
+    pub(crate) fn is_string_constant(&self) -> bool {
+        match &self.value {
+            TokenValue::QuotedIdentifierOrConstant(value) => value.starts_with('\''),
+            _ => false,
+        }
+    }
+
+    /// Returns the value of an integer constant (`42`, `1_000`, `0xFF`, `0b101`, `0o755`), or `None` if the token is
+    /// not an integer constant or if its value does not fit in an `i64`.
+    ///
+    /// A negative number is tokenized as a `-` operator followed by the numeric constant, so the returned value is
+    /// never negative.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT 1_000, 0xFF, 1.5").next().unwrap();
+    /// assert_eq!(stmt.tokens()[1].as_i64(), Some(1000));
+    /// assert_eq!(stmt.tokens()[3].as_i64(), Some(255));
+    /// assert_eq!(stmt.tokens()[5].as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        let (radix, digits) = self.numeric_digits()?;
+        i64::from_str_radix(&digits, radix).ok()
+    }
+
+    /// Returns the value of a numeric constant (`42`, `3.5`, `.5e-3`, `0xFF`, `1.5f`, `$12.34`), or `None` if the token
+    /// is not a valid numeric constant or if its value is not finite.
+    ///
+    /// A negative number is tokenized as a `-` operator followed by the numeric constant, so the returned value is
+    /// never negative.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT 1.5e3, 0b11, 1e999").next().unwrap();
+    /// assert_eq!(stmt.tokens()[1].as_f64(), Some(1500.0));
+    /// assert_eq!(stmt.tokens()[3].as_f64(), Some(3.0));
+    /// assert_eq!(stmt.tokens()[5].as_f64(), None);
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        let (radix, digits) = self.numeric_digits()?;
+        let value = match radix {
+            10 => digits.parse::<f64>().ok()?,
+            _ => u128::from_str_radix(&digits, radix).ok()? as f64,
+        };
+        value.is_finite().then_some(value)
+    }
+
+    /// Returns the decimal representation of a numeric constant, or `None` if the token is not a valid numeric
+    /// constant.
+    ///
+    /// The digit separators, the float and double suffixes (`1.5f`, `3D`) and the currency sign (`$12.34`) are removed,
+    /// and the hexadecimal, binary and octal constants are converted to decimal (`None` is returned if they do not fit
+    /// in an `u128`). The decimal constants are not rounded.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT 1_000.000_1, 0xFF, 'a'").next().unwrap();
+    /// assert_eq!(stmt.tokens()[1].as_decimal_str().unwrap(), "1000.0001");
+    /// assert_eq!(stmt.tokens()[3].as_decimal_str().unwrap(), "255");
+    /// assert!(stmt.tokens()[5].as_decimal_str().is_none());
+    /// ```
+    pub fn as_decimal_str(&self) -> Option<Cow<'s, str>> {
+        let (radix, digits) = self.numeric_digits()?;
+        match radix {
+            10 => digits.parse::<f64>().is_ok().then_some(digits),
+            _ => Some(Cow::Owned(u128::from_str_radix(&digits, radix).ok()?.to_string())),
+        }
+    }
+
+    // Get the radix and the digits of a numeric constant, without its prefix, suffix and digit separators.
+    fn numeric_digits(&self) -> Option<(u32, Cow<'s, str>)> {
+        let TokenValue::NumericConstant(value) = self.value else {
+            return None;
+        };
+        // Money constant (T-SQL: $12.34).
+        let value = value.strip_prefix('$').unwrap_or(value);
+        let (radix, digits) = match value.get(..2) {
+            Some("0x" | "0X") => (16, &value[2..]),
+            Some("0b" | "0B") => (2, &value[2..]),
+            Some("0o" | "0O") => (8, &value[2..]),
+            // Float and double suffixes (Oracle: 1.5f, 3D).
+            _ => (10, value.strip_suffix(['f', 'F', 'd', 'D']).unwrap_or(value)),
+        };
+        let digits = match digits.contains('_') {
+            true => Cow::Owned(digits.replace('_', "")),
+            false => Cow::Borrowed(digits),
+        };
+        (!digits.is_empty()).then_some((radix, digits))
+    }
+
+    /// Returns the text of a comment without its delimiters (`--`, `#`, `/*`, `*/`, `{`, `}`), or `None` if the token
+    /// is not a comment.
+    ///
+    /// The text is trimmed. The leading `*` gutter of the lines of a block comment is removed when all the non-blank
+    /// lines following the first one are starting with a `*`, the newlines are preserved.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("-- name: GetUser :one\nSELECT 1 /**\n * Line 1\n * Line 2\n */").next().unwrap();
+    /// assert_eq!(stmt.tokens()[0].comment_text().unwrap(), "name: GetUser :one");
+    /// assert_eq!(stmt.tokens()[3].comment_text().unwrap(), "Line 1\nLine 2");
+    /// assert!(stmt.tokens()[1].comment_text().is_none());
+    /// ```
+    pub fn comment_text(&self) -> Option<Cow<'s, str>> {
+        let TokenValue::Comment(value) = self.value else {
+            return None;
+        };
+        let text = if let Some(text) = value.strip_prefix("--").or_else(|| value.strip_prefix('#')) {
+            text
+        } else if let Some(text) = value.strip_prefix('{') {
+            text.strip_suffix('}').unwrap_or(text)
+        } else {
+            // The comment may be unterminated.
+            let text = value.strip_prefix("/*").unwrap_or(value);
+            text.strip_suffix("*/").unwrap_or(text).trim_matches('*')
+        };
+        let mut lines = text.lines().skip(1).filter(|line| !line.trim().is_empty()).peekable();
+        if lines.peek().is_some() && lines.all(|line| line.trim_start().starts_with('*')) {
+            let lines: Vec<_> = text
+                .lines()
+                .enumerate()
+                .map(|(index, line)| match line.trim_start().strip_prefix('*') {
+                    Some(line) if index > 0 => line.strip_prefix(' ').unwrap_or(line),
+                    _ => line,
+                })
+                .collect();
+            return Some(Cow::Owned(lines.join("\n").trim().to_string()));
+        }
+        Some(Cow::Borrowed(text.trim()))
+    }
+
+    /// Returns the value of a quoted identifier or constant without its quotes, or `None` if the token is not quoted.
+    ///
+    /// The introducer of a string constant (`E`, `N`, `_utf8mb4`, ...) is removed and the doubled quotes are collapsed
+    /// (`'O''Reilly'` is `O'Reilly`), so are the quotes escaped by a backslash in the escape and raw strings
+    /// (`E'O\'Reilly'` is `O'Reilly`), the other backslash escape sequences are kept as is (`E'a\nb'` is `a\nb`).
+    /// Dollar-quoted and triple-quoted strings are returned as is without their quotes, a triple-quoted string is only
+    /// recognized when it cannot be read as a regular string (`'''a'''` is `'a'`).
+    /// The value of an unterminated quoted token is decoded up to the end of the input.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse(r#"SELECT 'O''Reilly', "My Table", $$a$$, 1"#).next().unwrap();
+    /// assert_eq!(stmt.tokens()[1].unquoted_value().unwrap(), "O'Reilly");
+    /// assert_eq!(stmt.tokens()[3].unquoted_value().unwrap(), "My Table");
+    /// assert_eq!(stmt.tokens()[5].unquoted_value().unwrap(), "a");
+    /// assert!(stmt.tokens()[7].unquoted_value().is_none());
+    /// ```
+    pub fn unquoted_value(&self) -> Option<Cow<'s, str>> {
+        let (delimiter, inner_text, _) = self.split_delimited()?;
+        if delimiter.len() != 1 {
+            // Dollar-quoted and triple-quoted strings cannot contain escaped quotes.
+            return Some(Cow::Borrowed(inner_text));
+        }
+        // The quotes are escaped using the same rules as the tokenizer: a backslash in the escape and raw strings
+        // (`E'a\'b'`, `r'a\'b'`) and a doubled quote in all but the raw strings.
+        let value = self.value.as_ref();
+        let introducer = &value[..value.find(delimiter).unwrap_or_default()];
+        let backslash_escapes = introducer.eq_ignore_ascii_case("e") || is_raw_string_introducer(introducer);
+        let doubled_escapes = !is_raw_string_introducer(introducer);
+        let doubled_quote = delimiter.repeat(2);
+        let is_escaped = (backslash_escapes && inner_text.contains('\\'))
+            || (doubled_escapes && inner_text.contains(&doubled_quote));
+        if !is_escaped {
+            return Some(Cow::Borrowed(inner_text));
+        }
+        let mut unquoted = String::with_capacity(inner_text.len());
+        let mut chars = inner_text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' && backslash_escapes {
+                // Only the escaped quote is collapsed, the other escape sequences (`\n`, `\\`) are kept as is.
+                match chars.next() {
+                    Some(escaped_char) if delimiter.starts_with(escaped_char) => unquoted.push(escaped_char),
+                    Some(escaped_char) => unquoted.extend([c, escaped_char]),
+                    None => unquoted.push(c),
+                }
+            } else {
+                unquoted.push(c);
+                if doubled_escapes && delimiter.starts_with(c) && chars.as_str().starts_with(c) {
+                    chars.next();
+                }
+            }
+        }
+        Some(Cow::Owned(unquoted))
+    }
+
+    /// Returns the opening delimiter of a quoted identifier or constant, or `None` if the token is not quoted.
+    ///
+    /// The delimiter is the quote (`'`, `"` or `` ` ``), the triple quote (`'''` or `"""`) or the dollar-quoted tag
+    /// (`$$`, `$body$`), the introducer of a string constant (`E`, `N`, `_utf8mb4`, ...) is not part of it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT $body$ SELECT 1 $body$, E'a'").next().unwrap();
+    /// assert_eq!(stmt.tokens()[1].delimiter(), Some("$body$"));
+    /// assert_eq!(stmt.tokens()[3].delimiter(), Some("'"));
+    /// ```
+    pub fn delimiter(&self) -> Option<&'s str> {
+        self.split_delimited().map(|(delimiter, _, _)| delimiter)
+    }
+
+    /// Returns the raw content between the delimiters of a quoted identifier or constant, or `None` if the token is not
+    /// quoted.
+    ///
+    /// Unlike [`Token::unquoted_value`], the escaped quotes are kept as is.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT $body$ SELECT 1 $body$, 'O''Reilly'").next().unwrap();
+    /// assert_eq!(stmt.tokens()[1].inner_text(), Some(" SELECT 1 "));
+    /// assert_eq!(stmt.tokens()[3].inner_text(), Some("O''Reilly"));
+    /// ```
+    pub fn inner_text(&self) -> Option<&'s str> {
+        self.split_delimited().map(|(_, inner_text, _)| inner_text)
+    }
+
+    /// Check if the token is a quoted identifier or constant whose closing delimiter is missing (the end of the input
+    /// was reached before finding it).
+    pub fn is_unterminated(&self) -> bool {
+        self.split_delimited().is_some_and(|(_, _, terminated)| !terminated)
+    }
+
+    // Split a quoted identifier or constant into its opening delimiter, its inner text and whether the closing delimiter
+    // was found.
+    //
+    // A value starting with 3 quotes is either a triple-quoted string or a string starting with a doubled quote, it is
+    // only considered as a triple-quoted string when it cannot be read as a regular string (`'''a'''` is `'a'`).
+    fn split_delimited(&self) -> Option<(&'s str, &'s str, bool)> {
+        let TokenValue::QuotedIdentifierOrConstant(value) = self.value else {
+            return None;
+        };
+        if let Some(rest) = value.strip_prefix('$') {
+            // Dollar-quoted string ($tag$...$tag$).
+            let delimiter = rest.find('$').map_or(value, |index| &value[..index + 2]);
+            let text = &value[delimiter.len()..];
+            return Some(match text.strip_suffix(delimiter) {
+                Some(inner_text) => (delimiter, inner_text, true),
+                None => (delimiter, text, false),
+            });
+        }
+        // The introducer is made of the characters preceding the opening quote.
+        let start = value.find(['\'', '"', '`'])?;
+        let introducer = &value[..start];
+        let quote = value.as_bytes()[start];
+        let text = &value[start + 1..];
+
+        // Looking for the closing quote using the same escaping rules as the tokenizer.
+        let backslash_escapes = introducer.eq_ignore_ascii_case("e") || is_raw_string_introducer(introducer);
+        let doubled_escapes = !is_raw_string_introducer(introducer) && !introducer.eq_ignore_ascii_case("x");
+        let bytes = text.as_bytes();
+        let mut index = 0;
+        let mut closing_quote = None;
+        while index < bytes.len() {
+            let is_escaped_quote = bytes[index] == quote && doubled_escapes && bytes.get(index + 1) == Some(&quote);
+            if (bytes[index] == b'\\' && backslash_escapes) || is_escaped_quote {
+                // The next character is escaped.
+                index += 2;
+            } else if bytes[index] == quote {
+                closing_quote = Some(index);
+                break;
+            } else {
+                index += 1;
+            }
+        }
+        let delimiter = &value[start..start + 1];
+        match closing_quote {
+            Some(index) if index + 1 == text.len() => Some((delimiter, &text[..index], true)),
+            Some(_) if self.is_triple_quoted() => {
+                // Triple-quoted string ('''...''', """...""").
+                let delimiter = &value[start..start + 3];
+                let text = &value[start + 3..];
+                Some(match text.strip_suffix(delimiter) {
+                    Some(inner_text) => (delimiter, inner_text, true),
+                    None => (delimiter, text, false),
+                })
+            }
+            Some(index) => Some((delimiter, &text[..index], true)),
+            None => Some((delimiter, text, false)),
+        }
+    }
+
+    // Check if the quoted identifier or constant is starting with a triple quote (`'''`, `"""`) after its introducer.
+    fn is_triple_quoted(&self) -> bool {
+        let TokenValue::QuotedIdentifierOrConstant(value) = self.value else {
+            return false;
+        };
+        value.find(['\'', '"', '`']).is_some_and(|start| {
+            value[start..].len() >= 3 && value[start..start + 3].bytes().all(|c| c == value.as_bytes()[start])
+        })
+    }
+
     pub fn children(&self) -> Option<&Tokens<'s>> {
         match &self.value {
             TokenValue::Fragment(tokens) => Some(tokens),
@@ -214,13 +749,22 @@ impl<'s> Token<'s> {
     pub fn as_str_array(&self) -> Vec<&str> {
         match &self.value {
             TokenValue::Any(value) => vec![value],
+            TokenValue::Punctuation(value) => vec![value],
             TokenValue::Comment(value) => vec![value],
+            TokenValue::VersionedComment(value) => vec![value],
+            TokenValue::Hint(value) => vec![value],
             TokenValue::QuotedIdentifierOrConstant(value) => vec![value],
             TokenValue::StatementDelimiter(value) => vec![value],
             TokenValue::Operator(value) => vec![value],
             TokenValue::NumericConstant(value) => vec![value],
             TokenValue::IdentifierOrKeyword(value) => vec![value],
             TokenValue::ParameterMarker(value) => vec![value],
+            TokenValue::Placeholder(value) => vec![value],
+            TokenValue::Variable(value) => vec![value],
+            TokenValue::StageReference(value) => vec![value],
+            TokenValue::SubstitutionVariable(value) => vec![value],
+            TokenValue::CopyData(value) => vec![value],
+            TokenValue::ClientCommand(value) => vec![value],
             TokenValue::Fragment(tokens) => tokens.iter().flat_map(|t| t.as_str_array()).collect(),
         }
     }
@@ -257,13 +801,28 @@ impl<'s> Serialize for Token<'s> {
         let mut state = serializer.serialize_struct("Token", 4)?;
         match &self.value {
             TokenValue::Any(value) => ser_token_value!(state, Any, value),
+            TokenValue::Punctuation(value) => ser_token_value!(state, Punctuation, value),
             TokenValue::Comment(value) => ser_token_value!(state, Comment, value),
+            TokenValue::VersionedComment(value) => ser_token_value!(state, VersionedComment, value),
+            TokenValue::Hint(value) => ser_token_value!(state, Hint, value),
             TokenValue::QuotedIdentifierOrConstant(value) => ser_token_value!(state, QuotedIdentifierOrConstant, value),
             TokenValue::Operator(value) => ser_token_value!(state, Operator, value),
             TokenValue::StatementDelimiter(value) => ser_token_value!(state, StatementDelimiter, value),
             TokenValue::NumericConstant(value) => ser_token_value!(state, NumericConstant, value),
-            TokenValue::IdentifierOrKeyword(value) => ser_token_value!(state, IdentifierOrKeyword, value),
+            TokenValue::IdentifierOrKeyword(value) if is_reserved_keyword(value) => {
+                ser_token_value!(state, Keyword, value)
+            }
+            TokenValue::IdentifierOrKeyword(value) if is_keyword(value) => {
+                ser_token_value!(state, IdentifierOrKeyword, value)
+            }
+            TokenValue::IdentifierOrKeyword(value) => ser_token_value!(state, Identifier, value),
             TokenValue::ParameterMarker(value) => ser_token_value!(state, ParameterMarker, value),
+            TokenValue::Placeholder(value) => ser_token_value!(state, Placeholder, value),
+            TokenValue::Variable(value) => ser_token_value!(state, Variable, value),
+            TokenValue::StageReference(value) => ser_token_value!(state, StageReference, value),
+            TokenValue::SubstitutionVariable(value) => ser_token_value!(state, SubstitutionVariable, value),
+            TokenValue::CopyData(value) => ser_token_value!(state, CopyData, value),
+            TokenValue::ClientCommand(value) => ser_token_value!(state, ClientCommand, value),
             TokenValue::Fragment(tokens) => {
                 state.serialize_field("type", "Fragment")?;
                 state.serialize_field("value", &tokens)?;
@@ -298,6 +857,190 @@ impl<'s> Tokens<'s> {
     pub fn as_str_array(&self) -> Vec<&str> {
         self.iter().flat_map(|t| t.as_str_array()).collect()
     }
+
+    /// Returns the typed datetime literals found in the tokens, including the ones found in nested fragments.
+    ///
+    /// A typed datetime literal is a `DATE`, `TIME`, `TIMESTAMP` or `TIMESTAMPTZ` keyword immediately followed by a
+    /// string constant. `TIME` and `TIMESTAMP` can also be followed by `WITH TIME ZONE` or `WITHOUT TIME ZONE`.
+    /// Each literal is returned as the slice of tokens it spans, from the keyword to the string constant.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let sql = "SELECT DATE '2024-01-01', TIMESTAMP WITH TIME ZONE '2024-01-01 12:00:00+02'";
+    /// let stmt = loose_sqlparse(sql).next().unwrap();
+    /// let literals = stmt.tokens().datetime_literals();
+    /// assert_eq!(literals.len(), 2);
+    /// let (start, end) = (&literals[1][0].start, &literals[1][literals[1].len() - 1].end);
+    /// assert_eq!(&sql[start.offset..end.offset], "TIMESTAMP WITH TIME ZONE '2024-01-01 12:00:00+02'");
+    /// ```
+    pub fn datetime_literals(&self) -> Vec<&[Token<'s>]> {
+        let mut literals = Vec::new();
+        let mut index = 0;
+        while index < self.len() {
+            let token = &self[index];
+            if let Some(children) = token.children() {
+                literals.extend(children.datetime_literals());
+            } else if ["DATE", "TIME", "TIMESTAMP", "TIMESTAMPTZ"].iter().any(|k| token.is_keyword_eq(k)) {
+                let mut end = index + 1;
+                if (token.is_keyword_eq("TIME") || token.is_keyword_eq("TIMESTAMP"))
+                    && self.len() > end + 2
+                    && (self[end].is_keyword_eq("WITH") || self[end].is_keyword_eq("WITHOUT"))
+                    && self[end + 1].is_keyword_eq("TIME")
+                    && self[end + 2].is_keyword_eq("ZONE")
+                {
+                    end += 3;
+                }
+                if end < self.len() && self[end].is_string_constant() {
+                    literals.push(&self[index..=end]);
+                    index = end;
+                }
+            }
+            index += 1;
+        }
+        literals
+    }
+
+    /// Returns the interval literals found in the tokens, including the ones found in nested fragments.
+    ///
+    /// An interval literal is an `INTERVAL` keyword followed by:
+    /// - a string constant and optional units (PostgreSQL: `INTERVAL '7 days'`, `INTERVAL '1-2' YEAR TO MONTH`),
+    /// - or a numeric constant, optionally signed, and a unit (MySQL: `INTERVAL 7 DAY`, `INTERVAL -1 HOUR`).
+    ///
+    /// Each literal is returned as the slice of tokens it spans, from the `INTERVAL` keyword to the last unit.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let sql = "SELECT now() - INTERVAL '7 days', CURRENT_DATE + INTERVAL 1 DAY";
+    /// let stmt = loose_sqlparse(sql).next().unwrap();
+    /// let literals = stmt.tokens().interval_literals();
+    /// assert_eq!(literals.len(), 2);
+    /// let (start, end) = (&literals[1][0].start, &literals[1][literals[1].len() - 1].end);
+    /// assert_eq!(&sql[start.offset..end.offset], "INTERVAL 1 DAY");
+    /// ```
+    pub fn interval_literals(&self) -> Vec<&[Token<'s>]> {
+        let is_unit = |token: &Token| INTERVAL_UNITS.iter().any(|unit| token.is_keyword_eq(unit));
+        let mut literals = Vec::new();
+        let mut index = 0;
+        while index < self.len() {
+            let token = &self[index];
+            if let Some(children) = token.children() {
+                literals.extend(children.interval_literals());
+            } else if token.is_keyword_eq("INTERVAL") {
+                let mut end = index + 1;
+                if end + 1 < self.len()
+                    && matches!(self[end].value, TokenValue::Operator("-" | "+"))
+                    && self[end + 1].is_numeric_constant()
+                {
+                    // Signed numeric value (MySQL: INTERVAL -1 DAY).
+                    end += 1;
+                }
+                let has_value = end < self.len() && (self[end].is_string_constant() || self[end].is_numeric_constant());
+                let has_unit = end + 1 < self.len() && is_unit(&self[end + 1]);
+                // A unit is mandatory if the value is a numeric constant.
+                if has_value && (has_unit || self[end].is_string_constant()) {
+                    if has_unit {
+                        end += 1;
+                        if end + 2 < self.len() && self[end + 1].is_keyword_eq("TO") && is_unit(&self[end + 2]) {
+                            // Range of units (YEAR TO MONTH).
+                            end += 2;
+                        }
+                    }
+                    literals.push(&self[index..=end]);
+                    index = end;
+                }
+            }
+            index += 1;
+        }
+        literals
+    }
+
+    /// Returns the compound identifiers found in the tokens, including the ones found in nested fragments.
+    ///
+    /// A compound identifier is a chain of identifiers or quoted identifiers joined by dots without any whitespace in
+    /// between (ex: `analytics.events.user_id`, `"My Schema"."My Table"`). Each compound identifier is returned as the
+    /// slice of tokens it spans, alternating the parts and the dots.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let sql = "SELECT analytics.events.user_id FROM \"My Schema\".\"My Table\"";
+    /// let stmt = loose_sqlparse(sql).next().unwrap();
+    /// let identifiers = stmt.tokens().compound_identifiers();
+    /// assert_eq!(identifiers.len(), 2);
+    /// let (start, end) = (&identifiers[1][0].start, &identifiers[1][identifiers[1].len() - 1].end);
+    /// assert_eq!(&sql[start.offset..end.offset], "\"My Schema\".\"My Table\"");
+    /// let parts: Vec<_> = identifiers[0].iter().step_by(2).map(|t| t.value.as_ref()).collect();
+    /// assert_eq!(parts, ["analytics", "events", "user_id"]);
+    /// ```
+    pub fn compound_identifiers(&self) -> Vec<&[Token<'s>]> {
+        let is_part = |token: &Token| token.is_identifier_or_keyword() || token.is_quoted_identifier();
+        let is_joined = |left: &Token, right: &Token| left.end.offset == right.start.offset;
+        let mut identifiers = Vec::new();
+        let mut index = 0;
+        while index < self.len() {
+            let token = &self[index];
+            if let Some(children) = token.children() {
+                identifiers.extend(children.compound_identifiers());
+            } else if is_part(token) {
+                let mut end = index;
+                while end + 2 < self.len()
+                    && self[end + 1].is_dot()
+                    && is_part(&self[end + 2])
+                    && is_joined(&self[end], &self[end + 1])
+                    && is_joined(&self[end + 1], &self[end + 2])
+                {
+                    end += 2;
+                }
+                if end > index {
+                    identifiers.push(&self[index..=end]);
+                    index = end;
+                }
+            }
+            index += 1;
+        }
+        identifiers
+    }
+
+    /// Returns the deepest token containing the given byte offset, descending into the fragments.
+    ///
+    /// A token contains the offsets from its start offset (included) to its end offset (excluded). The offsets of the
+    /// whitespace between two tokens are not contained by any token, unless inside a fragment which is then returned.
+    /// The tokens are searched by a binary search on their offsets.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT f(a, b)").next().unwrap();
+    /// assert_eq!(stmt.tokens().find_at_offset(10).unwrap().value.as_ref(), ",");
+    /// assert_eq!(stmt.tokens().find_at_offset(13).unwrap().value.as_ref(), ")");
+    /// assert!(stmt.tokens().find_at_offset(6).is_none());
+    /// ```
+    pub fn find_at_offset(&self, offset: usize) -> Option<&Token<'s>> {
+        let index = self.partition_point(|token| token.end.offset <= offset);
+        let token = self.get(index).filter(|token| token.start.offset <= offset)?;
+        token.children().and_then(|children| children.find_at_offset(offset)).or(Some(token))
+    }
+
+    /// Returns the deepest token containing the character at the given line and column, descending into the fragments.
+    ///
+    /// A token contains the characters from its start position to its end position (both included, see
+    /// [`crate::Position`]). Same as [`Tokens::find_at_offset`] otherwise.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use loose_sqlparser::loose_sqlparse;
+    /// let stmt = loose_sqlparse("SELECT 'a\nb', (1 +\n  2)").next().unwrap();
+    /// assert_eq!(stmt.tokens().find_at_position(2, 1).unwrap().value.as_ref(), "'a\nb'");
+    /// assert_eq!(stmt.tokens().find_at_position(3, 3).unwrap().value.as_ref(), "2");
+    /// assert!(stmt.tokens().find_at_position(3, 4).unwrap().is_parenthesis());
+    /// ```
+    pub fn find_at_position(&self, line: usize, column: usize) -> Option<&Token<'s>> {
+        let index = self.partition_point(|token| (token.end.line, token.end.column) < (line, column));
+        let token = self.get(index).filter(|token| (token.start.line, token.start.column) <= (line, column))?;
+        token.children().and_then(|children| children.find_at_position(line, column)).or(Some(token))
+    }
 }
 
 // Implement Deref to delegate method calls to the inner Vec<Token<'s>>
@@ -327,6 +1070,9 @@ mod tests {
         assert!(Token::new(TokenValue::NumericConstant("42"), Position::new(1, 1, 0), Position::new(1, 2, 1))
             .is_numeric_constant());
         assert!(Token::new(TokenValue::Comment("--"), Position::new(1, 1, 0), Position::new(1, 3, 2)).is_comment());
+        assert!(Token::new(TokenValue::VersionedComment("/*!*/"), Position::new(1, 1, 0), Position::new(1, 5, 5))
+            .is_versioned_comment());
+        assert!(Token::new(TokenValue::Hint("/*+*/"), Position::new(1, 1, 0), Position::new(1, 5, 5)).is_hint());
         assert!(Token::new(
             TokenValue::QuotedIdentifierOrConstant("'Hello'"),
             Position::new(1, 1, 0),
@@ -338,17 +1084,267 @@ mod tests {
         assert!(Token::new(TokenValue::StatementDelimiter(";"), Position::new(1, 1, 0), Position::new(1, 1, 0))
             .is_statement_delimiter());
         assert!(Token::new(TokenValue::Operator("+"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_operator());
-        assert!(Token::new(TokenValue::Any("("), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_parenthesis());
-        assert!(Token::new(TokenValue::Any(")"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_parenthesis());
+        assert!(
+            Token::new(TokenValue::Punctuation("("), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_parenthesis()
+        );
+        assert!(
+            Token::new(TokenValue::Punctuation(")"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_parenthesis()
+        );
         assert!(!Token::new(TokenValue::Any("}"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_parenthesis());
         assert!(!Token::new(TokenValue::Operator("+"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_parenthesis());
-        assert!(Token::new(TokenValue::Any(","), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_comma());
-        assert!(!Token::new(TokenValue::Any("."), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_comma());
+        assert!(Token::new(TokenValue::Punctuation(","), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_comma());
+        assert!(!Token::new(TokenValue::Punctuation("."), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_comma());
+        assert!(!Token::new(TokenValue::Any(","), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_comma());
+        assert!(Token::new(TokenValue::Punctuation("."), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_dot());
+        assert!(
+            Token::new(TokenValue::Punctuation(","), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_punctuation()
+        );
         assert!(!Token::new(TokenValue::Operator("+"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_comma());
         assert!(Token::new(TokenValue::IdentifierOrKeyword("SELECT"), Position::new(1, 1, 0), Position::new(1, 6, 5))
             .is_identifier_or_keyword());
-        assert!(Token::new(TokenValue::ParameterMarker("?"), Position::new(1, 1, 0), Position::new(1, 1, 0))
+        assert!(Token::new(TokenValue::ParameterMarker("$1"), Position::new(1, 1, 0), Position::new(1, 2, 1))
+            .is_parameter_marker());
+        assert!(
+            Token::new(TokenValue::Placeholder("?"), Position::new(1, 1, 0), Position::new(1, 1, 0)).is_placeholder()
+        );
+        assert!(!Token::new(TokenValue::Placeholder("?"), Position::new(1, 1, 0), Position::new(1, 1, 0))
             .is_parameter_marker());
+        assert!(Token::new(TokenValue::Variable("@v"), Position::new(1, 1, 0), Position::new(1, 2, 2)).is_variable());
+        assert!(Token::new(TokenValue::StageReference("@s"), Position::new(1, 1, 0), Position::new(1, 2, 2))
+            .is_stage_reference());
+        assert!(Token::new(TokenValue::SubstitutionVariable("&v"), Position::new(1, 1, 0), Position::new(1, 2, 2))
+            .is_substitution_variable());
+        assert!(Token::new(TokenValue::CopyData("1\\."), Position::new(1, 1, 0), Position::new(2, 2, 4)).is_copy_data());
+        assert!(Token::new(TokenValue::ClientCommand("\\i a.sql"), Position::new(1, 1, 0), Position::new(1, 8, 8))
+            .is_client_command());
+    }
+
+    #[test]
+    fn test_datetime_literals() {
+        let sql = "SELECT DATE '2024-01-01', time '12:00', TIMESTAMP WITH TIME ZONE '2024-01-01 12:00:00+02', \
+                   (TIME WITHOUT TIME ZONE '12:00'), DATE, DATE \"2024\", TIMESTAMP 'x'";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let literals = statement.tokens().datetime_literals();
+        let literals: Vec<_> = literals.iter().map(|l| &sql[l[0].start.offset..l[l.len() - 1].end.offset]).collect();
+        assert_eq!(
+            literals,
+            [
+                "DATE '2024-01-01'",
+                "time '12:00'",
+                "TIMESTAMP WITH TIME ZONE '2024-01-01 12:00:00+02'",
+                "TIME WITHOUT TIME ZONE '12:00'",
+                "TIMESTAMP 'x'"
+            ]
+        );
+
+        let statement = crate::loose_sqlparse("SELECT DATE FROM t").next().unwrap();
+        assert!(statement.tokens().datetime_literals().is_empty());
+    }
+
+    #[test]
+    fn test_interval_literals() {
+        let sql = "SELECT now() - INTERVAL '7 days', INTERVAL 7 DAY, interval '1-2' year to month, \
+                   INTERVAL -1 HOUR, DATE_ADD(d, INTERVAL '1:30' HOUR_MINUTE), INTERVAL '1' DAY, \
+                   \"interval\" '1', INTERVAL 7, INTERVAL, INTERVAL x DAY";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let literals = statement.tokens().interval_literals();
+        let literals: Vec<_> = literals.iter().map(|l| &sql[l[0].start.offset..l[l.len() - 1].end.offset]).collect();
+        assert_eq!(
+            literals,
+            [
+                "INTERVAL '7 days'",
+                "INTERVAL 7 DAY",
+                "interval '1-2' year to month",
+                "INTERVAL -1 HOUR",
+                "INTERVAL '1:30' HOUR_MINUTE",
+                "INTERVAL '1' DAY"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compound_identifiers() {
+        let sql = "SELECT a.b.c, 1.5, t .x, `db`.`t`, (SELECT s.\"T\" FROM u), 'x'.y, d..e, f.g.";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let identifiers = statement.tokens().compound_identifiers();
+        let identifiers: Vec<_> =
+            identifiers.iter().map(|i| &sql[i[0].start.offset..i[i.len() - 1].end.offset]).collect();
+        assert_eq!(identifiers, ["a.b.c", "`db`.`t`", "s.\"T\"", "f.g"]);
+
+        let statement = crate::loose_sqlparse("SELECT schema.").next().unwrap();
+        assert!(statement.tokens().compound_identifiers().is_empty());
+    }
+
+    #[test]
+    fn test_keywords_and_identifiers() {
+        let statement = crate::loose_sqlparse("SELECT name, \"select\", `from`, users.date FROM users").next().unwrap();
+        let tokens = statement.tokens();
+        assert!(tokens[0].is_keyword() && tokens[0].is_reserved_keyword() && !tokens[0].is_identifier());
+        assert!(tokens[1].is_keyword() && !tokens[1].is_reserved_keyword() && tokens[1].is_identifier());
+        assert!(!tokens[3].is_keyword() && tokens[3].is_identifier());
+        assert!(!tokens[5].is_keyword() && tokens[5].is_identifier());
+        assert!(!tokens[7].is_keyword() && tokens[7].is_identifier());
+        assert!(tokens[9].is_keyword() && tokens[9].is_identifier());
+        assert!(!tokens[10].is_identifier() && tokens[11].is_identifier());
+        let statement = crate::loose_sqlparse("SELECT 'x', 1").next().unwrap();
+        assert!(!statement.tokens()[1].is_identifier() && !statement.tokens()[3].is_keyword());
+
+        let options = crate::Options::for_dialect(crate::Dialect::BigQuery);
+        let statement =
+            crate::loose_sqlparse_with_options("SELECT \"\"\"x\"\"\", `y`, \"z", options).unwrap().next().unwrap();
+        let tokens = statement.tokens();
+        assert!(!tokens[1].is_identifier() && tokens[3].is_identifier() && tokens[5].is_identifier());
+    }
+
+    #[test]
+    fn test_comment_text() {
+        let sql = "-- name: GetUser :one\r\n#x\n/* a\n   b */ /*\n * a\n *\n *   b\n */ {c} /** d **/ /**/ /* e";
+        let options = crate::Options { brace_comments: true, ..Default::default() };
+        let statement = crate::loose_sqlparse_with_options(sql, options).unwrap().next().unwrap();
+        let texts: Vec<_> = statement.tokens().iter().map(|t| t.comment_text().unwrap()).collect();
+        assert_eq!(texts, ["name: GetUser :one", "x", "a\n   b", "a\n\n  b", "c", "d", "", "e"]);
+        assert!(matches!(texts[0], Cow::Borrowed(_)));
+        assert!(matches!(texts[3], Cow::Owned(_)));
+
+        let statement = crate::loose_sqlparse("SELECT /*+ hint */ 1").next().unwrap();
+        assert!(statement.tokens().iter().all(|t| t.comment_text().is_none()));
+    }
+
+    #[test]
+    fn test_numeric_values() {
+        let cases = [
+            ("42", Some(42), Some(42.0), Some("42")),
+            ("1_000", Some(1000), Some(1000.0), Some("1000")),
+            ("3.5", None, Some(3.5), Some("3.5")),
+            ("4.", None, Some(4.0), Some("4.")),
+            (".5e-3", None, Some(0.0005), Some(".5e-3")),
+            ("1.e2", None, Some(100.0), Some("1.e2")),
+            ("0xff", Some(255), Some(255.0), Some("255")),
+            ("0B101", Some(5), Some(5.0), Some("5")),
+            ("0o_17", Some(15), Some(15.0), Some("15")),
+            ("1.5f", None, Some(1.5), Some("1.5")),
+            ("3D", Some(3), Some(3.0), Some("3")),
+            ("1e", None, None, None),
+            ("0x", None, None, None),
+            ("99999999999999999999", None, Some(1e20), Some("99999999999999999999")),
+            ("1e999", None, None, Some("1e999")),
+            ("'1'", None, None, None),
+        ];
+        for (sql, integer, float, decimal) in cases {
+            let statement = crate::loose_sqlparse(sql).next().unwrap();
+            let token = &statement.tokens()[0];
+            assert_eq!(token.as_i64(), integer, "{sql}");
+            assert_eq!(token.as_f64(), float, "{sql}");
+            assert_eq!(token.as_decimal_str().as_deref(), decimal, "{sql}");
+        }
+
+        // The sign is a separate operator token.
+        let statement = crate::loose_sqlparse("SELECT -1, $12.34").next().unwrap();
+        assert!(statement.tokens()[1].as_i64().is_none());
+        assert_eq!(statement.tokens()[2].as_i64(), Some(1));
+        assert_eq!(statement.tokens()[4].as_f64(), Some(12.34));
+    }
+
+    #[test]
+    fn test_unquoted_value() {
+        let sql = "SELECT 'O''Reilly', \"a\"\"b\", `c`, E'd', N'', $tag$e$tag$, _utf8mb4'f', '''g''', 'h";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let values: Vec<_> = statement.tokens().iter().filter_map(|t| t.unquoted_value()).collect();
+        assert_eq!(values, ["O'Reilly", "a\"b", "c", "d", "", "e", "f", "'g'", "h"]);
+        assert!(matches!(values[0], Cow::Owned(_)));
+        assert!(matches!(values[2], Cow::Borrowed(_)));
+
+        let options = crate::Options { triple_quoted_strings: true, ..Default::default() };
+        let statement =
+            crate::loose_sqlparse_with_options("SELECT '''a'b''', \"\"\"c\"d\"\"\"", options).unwrap().next().unwrap();
+        assert_eq!(statement.tokens()[1].unquoted_value().unwrap(), "a'b");
+        assert_eq!(statement.tokens()[3].unquoted_value().unwrap(), "c\"d");
+
+        // The quotes escaped by a backslash are collapsed, the other escape sequences are kept.
+        let sql = r"SELECT E'a\'b', E'it''s', E'c\nd\\', E'e\\''f', e'\'', 'g\', r'h\'i\d'";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let values: Vec<_> = statement.tokens().iter().filter_map(|t| t.unquoted_value()).collect();
+        assert_eq!(values, ["a'b", "it's", r"c\nd\\", r"e\\'f", "'", r"g\", r"h'i\d"]);
+    }
+
+    #[test]
+    fn test_delimiter_and_inner_text() {
+        let sql = "SELECT $body$a$b$body$, $$$$, 'O''Reilly', E'a\\'b', X'1F', `c`, $tag$d";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let quoted: Vec<_> = statement.tokens().iter().filter(|t| t.delimiter().is_some()).collect();
+        let delimiters: Vec<_> = quoted.iter().filter_map(|t| t.delimiter()).collect();
+        assert_eq!(delimiters, ["$body$", "$$", "'", "'", "'", "`", "$tag$"]);
+        let inner_texts: Vec<_> = quoted.iter().filter_map(|t| t.inner_text()).collect();
+        assert_eq!(inner_texts, ["a$b", "", "O''Reilly", "a\\'b", "1F", "c", "d"]);
+        let unterminated: Vec<_> = quoted.iter().map(|t| t.is_unterminated()).collect();
+        assert_eq!(unterminated, [false, false, false, false, false, false, true]);
+        assert!(statement.tokens()[0].delimiter().is_none());
+        assert!(statement.tokens()[0].inner_text().is_none());
+        assert!(!statement.tokens()[0].is_unterminated());
+
+        for sql in ["SELECT 'O''", "SELECT E'a\\'", "SELECT $$", "SELECT $a$b$a"] {
+            let statement = crate::loose_sqlparse(sql).next().unwrap();
+            assert!(statement.tokens()[1].is_unterminated(), "{sql}");
+        }
+
+        let options = crate::Options { triple_quoted_strings: true, ..Default::default() };
+        let statement =
+            crate::loose_sqlparse_with_options("SELECT '''a'b''', '''c'd", options).unwrap().next().unwrap();
+        assert_eq!(statement.tokens()[1].delimiter(), Some("'''"));
+        assert_eq!(statement.tokens()[1].inner_text(), Some("a'b"));
+        assert!(!statement.tokens()[1].is_unterminated());
+        assert_eq!(statement.tokens()[3].inner_text(), Some("c'd"));
+        assert!(statement.tokens()[3].is_unterminated());
+    }
+
+    #[test]
+    fn test_find_at_offset_and_position() {
+        let sql = "SELECT a.b, f( x,\n  (y) ) -- c\nFROM t;";
+        let statement = crate::loose_sqlparse(sql).next().unwrap();
+        let tokens = statement.tokens();
+        let value_at_offset = |offset| tokens.find_at_offset(offset).map(|token| token.value.as_ref());
+        assert_eq!(value_at_offset(0), Some("SELECT"));
+        assert_eq!(value_at_offset(5), Some("SELECT"));
+        assert_eq!(value_at_offset(6), None);
+        assert_eq!(value_at_offset(8), Some("."));
+        assert_eq!(value_at_offset(15), Some("x"));
+        assert_eq!(value_at_offset(21), Some("y"));
+        assert_eq!(value_at_offset(22), Some(")"));
+        // Whitespace inside a fragment.
+        assert!(tokens.find_at_offset(23).unwrap().is_fragment());
+        assert_eq!(value_at_offset(27), Some("-- c"));
+        assert_eq!(value_at_offset(sql.len() - 1), Some(";"));
+        assert_eq!(value_at_offset(sql.len()), None);
+        // Every offset of a token is found, descending into the fragments.
+        fn assert_found(all_tokens: &Tokens, tokens: &Tokens) {
+            for token in tokens.iter() {
+                if let Some(children) = token.children() {
+                    assert_found(all_tokens, children);
+                    continue;
+                }
+                for offset in token.start.offset..token.end.offset {
+                    assert!(std::ptr::eq(all_tokens.find_at_offset(offset).unwrap(), token), "{offset}");
+                }
+                assert!(std::ptr::eq(
+                    all_tokens.find_at_position(token.start.line, token.start.column).unwrap(),
+                    token
+                ));
+                assert!(std::ptr::eq(all_tokens.find_at_position(token.end.line, token.end.column).unwrap(), token));
+            }
+        }
+        assert_found(tokens, tokens);
+
+        let value_at_position = |line, column| tokens.find_at_position(line, column).map(|token| token.value.as_ref());
+        assert_eq!(value_at_position(1, 1), Some("SELECT"));
+        assert_eq!(value_at_position(1, 7), None);
+        assert_eq!(value_at_position(1, 10), Some("b"));
+        assert_eq!(value_at_position(2, 4), Some("y"));
+        assert!(tokens.find_at_position(2, 1).unwrap().is_fragment());
+        assert_eq!(value_at_position(2, 11), Some("-- c"));
+        assert_eq!(value_at_position(3, 7), Some(";"));
+        assert_eq!(value_at_position(3, 8), None);
+        assert_eq!(value_at_position(4, 1), None);
+        assert!(Tokens::new().find_at_offset(0).is_none());
     }
 
     #[test]